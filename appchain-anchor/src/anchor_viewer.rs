@@ -27,6 +27,45 @@ impl AnchorViewer for AppchainAnchor {
         self.protocol_settings.get().unwrap()
     }
     //
+    fn get_slashing_settings(&self) -> SlashingSettings {
+        self.slashing_settings.get().unwrap()
+    }
+    //
+    fn get_reward_schedule(&self) -> RewardSchedule {
+        self.reward_schedule.get().unwrap()
+    }
+    //
+    fn get_payload_codec(&self) -> PayloadCodec {
+        self.payload_codec.clone()
+    }
+    //
+    fn get_contract_call_settings(&self) -> ContractCallSettings {
+        self.contract_call_settings.get().unwrap()
+    }
+    //
+    fn get_expired_reward_sweep_history_of(
+        &self,
+        era_number: U64,
+    ) -> Option<ExpiredRewardSweepHistory> {
+        self.expired_reward_sweep_histories.get(&era_number.0)
+    }
+    //
+    fn get_reward_settings_of(&self, account_id: AccountId) -> Option<RewardSettings> {
+        self.reward_settings.get(&account_id)
+    }
+    //
+    fn get_reward_credit_of(&self, account_id: AccountId) -> U128 {
+        U128::from(self.reward_credits.get(&account_id).unwrap_or(0))
+    }
+    //
+    fn get_untransferred_slashed_amount(&self) -> U128 {
+        U128::from(self.untransferred_slashed_amount)
+    }
+    //
+    fn get_storage_fund_balance(&self) -> U128 {
+        U128::from(self.storage_fund_balance)
+    }
+    //
     fn get_oct_token(&self) -> OctToken {
         self.oct_token.get().unwrap()
     }
@@ -176,10 +215,26 @@ impl AnchorViewer for AppchainAnchor {
                 Vec::new()
             }
         } else {
-            self.next_validator_set.get().unwrap().get_validator_list()
+            self.next_validator_set.get().unwrap().get_validator_list(
+                self.protocol_settings
+                    .get()
+                    .unwrap()
+                    .maximum_validator_count
+                    .0,
+            )
         }
     }
     //
+    fn get_appchain_validator_list_of(&self, era_number: U64) -> Vec<AppchainValidator> {
+        self.validator_set_histories
+            .get()
+            .unwrap()
+            .get(&era_number.0)
+            .map_or(Vec::new(), |validator_set_of_era| {
+                validator_set_of_era.get_appchain_validator_list()
+            })
+    }
+    //
     fn get_delegators_of_validator_in_era(
         &self,
         era_number: Option<U64>,
@@ -261,6 +316,7 @@ impl AnchorViewer for AppchainAnchor {
                         era_number: U64::from(reference.era_number),
                         account_id: validator_id,
                         amount,
+                        staking_history_index: U64::from(reference.staking_history_index),
                         unlock_time: U64::from(
                             validator_set.start_timestamp()
                                 + protocol_settings.unlock_period_of_validator_deposit.0
@@ -286,6 +342,7 @@ impl AnchorViewer for AppchainAnchor {
                         era_number: U64::from(reference.era_number),
                         account_id: delegator_id,
                         amount,
+                        staking_history_index: U64::from(reference.staking_history_index),
                         unlock_time: U64::from(
                             validator_set.start_timestamp()
                                 + protocol_settings.unlock_period_of_delegator_deposit.0
@@ -343,14 +400,11 @@ impl AnchorViewer for AppchainAnchor {
                 if let Some(reward) =
                     validator_set.get_delegator_rewards_of(&delegator_id, &validator_id)
                 {
-                    let unwithdrawn_reward = match self.unwithdrawn_delegator_rewards.get(&(
-                        era_number,
-                        delegator_id.clone(),
-                        validator_id.clone(),
-                    )) {
-                        Some(reward) => reward,
-                        None => 0,
-                    };
+                    let unwithdrawn_reward = self.get_unwithdrawn_delegator_reward_of(
+                        &validator_set,
+                        &delegator_id,
+                        &validator_id,
+                    );
                     reward_histories.push(RewardHistory {
                         era_number: U64::from(era_number),
                         total_reward: U128::from(reward),
@@ -362,6 +416,43 @@ impl AnchorViewer for AppchainAnchor {
         reward_histories
     }
     //
+    fn get_incentive_pools(&self) -> Vec<IncentivePool> {
+        self.incentive_pools.get().unwrap().to_vec()
+    }
+    //
+    fn get_validator_incentive_rewards_of(
+        &self,
+        token_contract: AccountId,
+        start_era: U64,
+        end_era: U64,
+        validator_id: AccountId,
+    ) -> Vec<RewardHistory> {
+        self.get_incentive_reward_histories_of(
+            &token_contract,
+            start_era.0,
+            end_era.0,
+            &None,
+            &validator_id,
+        )
+    }
+    //
+    fn get_delegator_incentive_rewards_of(
+        &self,
+        token_contract: AccountId,
+        start_era: U64,
+        end_era: U64,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    ) -> Vec<RewardHistory> {
+        self.get_incentive_reward_histories_of(
+            &token_contract,
+            start_era.0,
+            end_era.0,
+            &Some(delegator_id),
+            &validator_id,
+        )
+    }
+    //
     fn get_storage_balance(&self) -> U128 {
         U128::from(u128::from(env::storage_usage()) * env::storage_byte_cost())
     }
@@ -499,6 +590,67 @@ impl AnchorViewer for AppchainAnchor {
             .get_by_id_in_appchain(&formatted_id.to_string())
     }
     //
+    fn get_validator_commission_of(&self, validator_id: AccountId) -> Option<ValidatorCommission> {
+        self.validator_commissions.get().unwrap().get(&validator_id)
+    }
+    //
+    fn get_validator_commission_percent_of(
+        &self,
+        validator_id: AccountId,
+        era_number: Option<U64>,
+    ) -> u16 {
+        if let Some(era_number) = era_number {
+            let validator_set_histories = self.validator_set_histories.get().unwrap();
+            if validator_set_histories.contains(&era_number.0) {
+                let validator_set = validator_set_histories.get(&era_number.0).unwrap();
+                if let Some(commission_percent) =
+                    validator_set.get_validator_commission_percent_of(&validator_id)
+                {
+                    return commission_percent;
+                }
+            }
+            self.get_commission_percent_of_validator_in_era(&validator_id, era_number.0)
+        } else {
+            let next_validator_set = self.next_validator_set.get().unwrap();
+            self.get_commission_percent_of_validator_in_era(
+                &validator_id,
+                next_validator_set.era_number(),
+            )
+        }
+    }
+    //
+    fn get_validator_operator_of(&self, validator_id: AccountId) -> Option<AccountId> {
+        self.validator_profiles
+            .get()
+            .unwrap()
+            .get_operator_of(&validator_id)
+    }
+    //
+    fn get_delegation_policy_of(&self, validator_id: AccountId) -> Option<DelegationPolicy> {
+        self.delegation_policies.get(&validator_id)
+    }
+    //
+    fn get_pending_staking_deposit_of(
+        &self,
+        account_id: AccountId,
+    ) -> Option<PendingStakingDeposit> {
+        self.pending_staking_deposits.get(&account_id)
+    }
+    //
+    fn get_jailed_validators(&self) -> Vec<JailedValidator> {
+        self.next_validator_set
+            .get()
+            .unwrap()
+            .get_jailed_validators()
+    }
+    //
+    fn get_queued_unbonding_validators(&self) -> Vec<QueuedUnbondingValidator> {
+        self.next_validator_set
+            .get()
+            .unwrap()
+            .get_queued_unbonding_validators()
+    }
+    //
     fn get_latest_commitment_of_appchain(&self) -> Option<AppchainCommitment> {
         if let Some(light_client) = self.beefy_light_client_state.get() {
             if let Some(commitment) = light_client.get_latest_commitment() {
@@ -578,28 +730,57 @@ impl AnchorViewer for AppchainAnchor {
         appchain_messages.get_processing_results(&start_nonce, quantity)
     }
     //
-    fn get_appchain_challenge(&self, index: Option<U64>) -> Option<AppchainChallenge> {
+    fn get_missing_appchain_message_nonces(
+        &self,
+        start_nonce: u32,
+        quantity: Option<u32>,
+    ) -> Vec<u32> {
+        let appchain_messages = self.appchain_messages.get().unwrap();
+        appchain_messages.get_missing_nonces(&start_nonce, quantity)
+    }
+    //
+    fn get_appchain_challenge(&self, index: Option<U64>) -> Option<AppchainChallengeInfo> {
+        let appchain_challenges = self.appchain_challenges.get().unwrap();
         let index = match index {
             Some(index) => index,
-            None => {
-                self.appchain_challenges
+            None => appchain_challenges.index_range().end_index,
+        };
+        appchain_challenges
+            .get(&index.0)
+            .map(|challenge| AppchainChallengeInfo {
+                index,
+                challenge,
+                status: self
+                    .appchain_challenge_statuses
                     .get()
                     .unwrap()
-                    .index_range()
-                    .end_index
-            }
-        };
-        self.appchain_challenges.get().unwrap().get(&index.0)
+                    .get_status(&index.0),
+            })
     }
     //
     fn get_appchain_challenges(
         &self,
         start_index: U64,
         quantity: Option<U64>,
-    ) -> Vec<AppchainChallenge> {
-        self.appchain_challenges
-            .get()
-            .unwrap()
-            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    ) -> Vec<AppchainChallengeInfo> {
+        let appchain_challenges = self.appchain_challenges.get().unwrap();
+        let appchain_challenge_statuses = self.appchain_challenge_statuses.get().unwrap();
+        let start_index = std::cmp::max(
+            start_index.0,
+            appchain_challenges.index_range().start_index.0,
+        );
+        appchain_challenges
+            .get_slice_of(&start_index, quantity.map(|q| q.0))
+            .into_iter()
+            .enumerate()
+            .map(|(offset, challenge)| {
+                let index = start_index + offset as u64;
+                AppchainChallengeInfo {
+                    index: U64::from(index),
+                    challenge,
+                    status: appchain_challenge_statuses.get_status(&index),
+                }
+            })
+            .collect()
     }
 }
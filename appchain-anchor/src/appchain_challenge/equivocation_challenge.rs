@@ -77,6 +77,28 @@ pub enum GrandpaMessage {
 }
 
 impl EquivocationProof {
+    /// The public key of the equivocating voter.
+    pub fn identity(&self) -> &PublicKey {
+        match &self.equivocation {
+            Equivocation::Prevote(equivocation) => &equivocation.identity,
+            Equivocation::Precommit(equivocation) => &equivocation.identity,
+        }
+    }
+    /// The key of the equivocation, which is the same for all proofs of a voter
+    /// equivocating in a certain round of a certain voter set.
+    pub fn equivocation_key(&self) -> String {
+        let (vote_kind, round_number) = match &self.equivocation {
+            Equivocation::Prevote(equivocation) => ("prevote", equivocation.round_number),
+            Equivocation::Precommit(equivocation) => ("precommit", equivocation.round_number),
+        };
+        format!(
+            "{}:{}:{}:{}",
+            self.set_id,
+            round_number,
+            vote_kind,
+            hex::encode(&self.identity().0)
+        )
+    }
     ///
     pub fn is_valid(&self) -> bool {
         // NOTE: the bare `Prevote` and `Precommit` types don't share any trait,
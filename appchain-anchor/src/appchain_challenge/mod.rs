@@ -1,4 +1,4 @@
-mod equivocation_challenge;
+pub mod equivocation_challenge;
 
 use crate::*;
 
@@ -16,3 +16,78 @@ pub enum AppchainChallenge {
         block_number: u32,
     },
 }
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainChallengeStatus {
+    /// The challenge is recorded but not resolved yet.
+    Pending,
+    /// The penalty has been applied to the challenged validator.
+    Applied {
+        validator_id: AccountId,
+        slashed_amount: U128,
+        bounty: U128,
+        bounty_transfer_status: BountyTransferStatus,
+    },
+    /// The challenge is rejected and no penalty is applied.
+    Rejected { reason: String },
+    /// The same equivocation has already been challenged in an earlier challenge.
+    Duplicate { original_index: U64 },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum BountyTransferStatus {
+    Transferring,
+    Transferred,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainChallengeInfo {
+    pub index: U64,
+    pub challenge: AppchainChallenge,
+    pub status: AppchainChallengeStatus,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AppchainChallengeStatuses {
+    /// The statuses of resolved challenges, mapped by the index of challenge.
+    /// A challenge without a record here is pending.
+    statuses: LookupMap<u64, AppchainChallengeStatus>,
+    /// The index of the first challenge of an equivocation, mapped by
+    /// the key of the equivocation.
+    equivocation_challenge_indexes: LookupMap<String, u64>,
+}
+
+impl AppchainChallengeStatuses {
+    ///
+    pub fn new() -> Self {
+        Self {
+            statuses: LookupMap::new(StorageKey::AppchainChallengeStatusesMap.into_bytes()),
+            equivocation_challenge_indexes: LookupMap::new(
+                StorageKey::EquivocationChallengeIndexesMap.into_bytes(),
+            ),
+        }
+    }
+    ///
+    pub fn get_status(&self, index: &u64) -> AppchainChallengeStatus {
+        self.statuses
+            .get(index)
+            .unwrap_or(AppchainChallengeStatus::Pending)
+    }
+    ///
+    pub fn set_status(&mut self, index: &u64, status: &AppchainChallengeStatus) {
+        self.statuses.insert(index, status);
+    }
+    ///
+    pub fn get_index_of_equivocation(&self, equivocation_key: &String) -> Option<u64> {
+        self.equivocation_challenge_indexes.get(equivocation_key)
+    }
+    ///
+    pub fn set_index_of_equivocation(&mut self, equivocation_key: &String, index: &u64) {
+        self.equivocation_challenge_indexes
+            .insert(equivocation_key, index);
+    }
+}
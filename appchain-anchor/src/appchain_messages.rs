@@ -9,6 +9,7 @@ pub enum PayloadType {
     PlanNewEra,
     EraPayout,
     LockNft,
+    ContractCall,
 }
 
 #[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
@@ -28,13 +29,13 @@ pub struct LockPayload {
     pub amount: u128,
 }
 
-#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[derive(Encode, Decode, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct PlanNewEraPayload {
     pub new_era: u32,
 }
 
-#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[derive(Encode, Decode, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Offender {
     pub kind: String,
@@ -42,7 +43,7 @@ pub struct Offender {
     pub offences: u32,
 }
 
-#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[derive(Encode, Decode, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct EraPayoutPayload {
     pub end_era: u32,
@@ -60,6 +61,198 @@ pub struct LockNftPayload {
     pub metadata: TokenMetadata,
 }
 
+#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractCallPayload {
+    pub sender: String,
+    pub contract_id: AccountId,
+    pub method_name: String,
+    /// The arguments of the function call, in JSON.
+    pub args: String,
+    /// The deposit (in yocto NEAR) attached to the function call.
+    pub deposit: u128,
+    /// The prepaid gas of the function call.
+    pub gas: u64,
+}
+
+/// The SCALE encoded version of `BurnAssetPayload`.
+#[derive(Encode, Decode, Clone)]
+pub struct ScaleBurnAssetPayload {
+    pub token_id: String,
+    pub sender: String,
+    pub receiver_id: String,
+    pub amount: u128,
+}
+
+/// The SCALE encoded version of `LockPayload`.
+#[derive(Encode, Decode, Clone)]
+pub struct ScaleLockPayload {
+    pub sender: String,
+    pub receiver_id: String,
+    pub amount: u128,
+}
+
+/// The SCALE encoded version of `TokenMetadata` of NEP-171.
+#[derive(Encode, Decode, Clone)]
+pub struct ScaleTokenMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub media_hash: Option<Vec<u8>>,
+    pub copies: Option<u64>,
+    pub issued_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub starts_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub extra: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<Vec<u8>>,
+}
+
+/// The SCALE encoded version of `LockNftPayload`.
+#[derive(Encode, Decode, Clone)]
+pub struct ScaleLockNftPayload {
+    pub sender: String,
+    pub receiver_id: String,
+    pub class: u128,
+    pub instance: u128,
+    pub metadata: ScaleTokenMetadata,
+}
+
+/// The SCALE encoded version of `ContractCallPayload`.
+#[derive(Encode, Decode, Clone)]
+pub struct ScaleContractCallPayload {
+    pub sender: String,
+    pub contract_id: String,
+    pub method_name: String,
+    pub args: String,
+    pub deposit: u128,
+    pub gas: u64,
+}
+
+/// The payload of appchain message which can be decoded from both Borsh and SCALE encoding.
+pub trait AppchainMessagePayload: BorshDeserialize {
+    /// Decode the payload from SCALE encoded bytes.
+    fn decode_scale(bytes: &[u8]) -> Result<Self, String>;
+    /// Decode the payload of the message with the given nonce by the given codec.
+    fn decode_payload(
+        bytes: &[u8],
+        nonce: u64,
+        payload_codec: &PayloadCodec,
+    ) -> Result<Self, String> {
+        let decode_borsh = || {
+            <Self as BorshDeserialize>::deserialize(&mut &bytes[..]).map_err(|err| err.to_string())
+        };
+        match payload_codec {
+            PayloadCodec::Borsh => decode_borsh(),
+            PayloadCodec::Scale => Self::decode_scale(bytes),
+            PayloadCodec::ScaleFromNonce {
+                nonce: cut_over_nonce,
+            } => match nonce >= u64::from(*cut_over_nonce) {
+                true => Self::decode_scale(bytes),
+                false => decode_borsh(),
+            },
+        }
+    }
+}
+
+// Decode SCALE encoded bytes, all of the bytes should be consumed.
+fn decode_all_scale<T: Decode>(bytes: &[u8]) -> Result<T, String> {
+    let mut input = bytes;
+    let value = T::decode(&mut input).map_err(|err| format!("{}", err))?;
+    match input.is_empty() {
+        true => Ok(value),
+        false => Err(format!("Redundant bytes in SCALE encoded payload.")),
+    }
+}
+
+fn parse_account_id(account_id: String) -> Result<AccountId, String> {
+    AccountId::try_from(account_id.clone())
+        .map_err(|err| format!("Invalid account id '{}': {}", account_id, err))
+}
+
+impl AppchainMessagePayload for BurnAssetPayload {
+    //
+    fn decode_scale(bytes: &[u8]) -> Result<Self, String> {
+        let payload: ScaleBurnAssetPayload = decode_all_scale(bytes)?;
+        Ok(Self {
+            token_id: payload.token_id,
+            sender: payload.sender,
+            receiver_id: parse_account_id(payload.receiver_id)?,
+            amount: payload.amount,
+        })
+    }
+}
+
+impl AppchainMessagePayload for LockPayload {
+    //
+    fn decode_scale(bytes: &[u8]) -> Result<Self, String> {
+        let payload: ScaleLockPayload = decode_all_scale(bytes)?;
+        Ok(Self {
+            sender: payload.sender,
+            receiver_id: parse_account_id(payload.receiver_id)?,
+            amount: payload.amount,
+        })
+    }
+}
+
+impl AppchainMessagePayload for PlanNewEraPayload {
+    //
+    fn decode_scale(bytes: &[u8]) -> Result<Self, String> {
+        decode_all_scale(bytes)
+    }
+}
+
+impl AppchainMessagePayload for EraPayoutPayload {
+    //
+    fn decode_scale(bytes: &[u8]) -> Result<Self, String> {
+        decode_all_scale(bytes)
+    }
+}
+
+impl AppchainMessagePayload for LockNftPayload {
+    //
+    fn decode_scale(bytes: &[u8]) -> Result<Self, String> {
+        let payload: ScaleLockNftPayload = decode_all_scale(bytes)?;
+        let metadata = payload.metadata;
+        Ok(Self {
+            sender: payload.sender,
+            receiver_id: parse_account_id(payload.receiver_id)?,
+            class: payload.class,
+            instance: payload.instance,
+            metadata: TokenMetadata {
+                title: metadata.title,
+                description: metadata.description,
+                media: metadata.media,
+                media_hash: metadata.media_hash.map(|hash| hash.into()),
+                copies: metadata.copies,
+                issued_at: metadata.issued_at,
+                expires_at: metadata.expires_at,
+                starts_at: metadata.starts_at,
+                updated_at: metadata.updated_at,
+                extra: metadata.extra,
+                reference: metadata.reference,
+                reference_hash: metadata.reference_hash.map(|hash| hash.into()),
+            },
+        })
+    }
+}
+
+impl AppchainMessagePayload for ContractCallPayload {
+    //
+    fn decode_scale(bytes: &[u8]) -> Result<Self, String> {
+        let payload: ScaleContractCallPayload = decode_all_scale(bytes)?;
+        Ok(Self {
+            sender: payload.sender,
+            contract_id: parse_account_id(payload.contract_id)?,
+            method_name: payload.method_name,
+            args: payload.args,
+            deposit: payload.deposit,
+            gas: payload.gas,
+        })
+    }
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct AppchainMessage {
@@ -77,6 +270,7 @@ pub enum MessagePayload {
     PlanNewEra(PlanNewEraPayload),
     EraPayout(EraPayoutPayload),
     LockNft(LockNftPayload),
+    ContractCall(ContractCallPayload),
 }
 
 #[derive(Encode, Decode, Clone)]
@@ -193,6 +387,26 @@ impl AppchainMessages {
         }
         results
     }
+    /// Get the nonces (not greater than the max nonce) which have neither
+    /// a staged message nor a processing result.
+    pub fn get_missing_nonces(&self, start_nonce: &u32, quantity: Option<u32>) -> Vec<u32> {
+        let mut results = Vec::<u32>::new();
+        let end_nonce = start_nonce.saturating_add(match quantity {
+            Some(quantity) => match quantity > 50 {
+                true => 50,
+                false => quantity,
+            },
+            None => 50,
+        });
+        for nonce in *start_nonce..u32::min(end_nonce, self.max_nonce.saturating_add(1)) {
+            if !self.message_map.contains_key(&nonce)
+                && !self.processing_result_map.contains_key(&nonce)
+            {
+                results.push(nonce);
+            }
+        }
+        results
+    }
     ///
     pub fn get_processing_result(&self, nonce: &u32) -> Option<AppchainMessageProcessingResult> {
         self.processing_result_map.get(nonce)
@@ -275,8 +489,11 @@ impl AppchainAnchor {
     ) {
         match raw_message.payload_type {
             PayloadType::BurnAsset => {
-                let payload_result: Result<BurnAssetPayload, std::io::Error> =
-                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                let payload_result = BurnAssetPayload::decode_payload(
+                    &raw_message.payload,
+                    raw_message.nonce,
+                    &self.payload_codec,
+                );
                 match payload_result {
                     Ok(payload) => {
                         log!(
@@ -297,14 +514,20 @@ impl AppchainAnchor {
                         raw_message.nonce as u32,
                         &AppchainMessageProcessingResult::Error {
                             nonce: raw_message.nonce as u32,
+                            error: AppchainMessageError::InvalidPayload {
+                                reason: err.to_string(),
+                            },
                             message: format!("Failed to deserialize raw message payload: {}", err),
                         },
                     ),
                 }
             }
             PayloadType::Lock => {
-                let payload_result: Result<LockPayload, std::io::Error> =
-                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                let payload_result = LockPayload::decode_payload(
+                    &raw_message.payload,
+                    raw_message.nonce,
+                    &self.payload_codec,
+                );
                 match payload_result {
                     Ok(payload) => {
                         log!(
@@ -324,14 +547,20 @@ impl AppchainAnchor {
                         raw_message.nonce as u32,
                         &AppchainMessageProcessingResult::Error {
                             nonce: raw_message.nonce as u32,
+                            error: AppchainMessageError::InvalidPayload {
+                                reason: err.to_string(),
+                            },
                             message: format!("Failed to deserialize raw message payload: {}", err),
                         },
                     ),
                 }
             }
             PayloadType::PlanNewEra => {
-                let payload_result: Result<PlanNewEraPayload, std::io::Error> =
-                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                let payload_result = PlanNewEraPayload::decode_payload(
+                    &raw_message.payload,
+                    raw_message.nonce,
+                    &self.payload_codec,
+                );
                 match payload_result {
                     Ok(payload) => {
                         log!(
@@ -349,14 +578,20 @@ impl AppchainAnchor {
                         raw_message.nonce as u32,
                         &AppchainMessageProcessingResult::Error {
                             nonce: raw_message.nonce as u32,
+                            error: AppchainMessageError::InvalidPayload {
+                                reason: err.to_string(),
+                            },
                             message: format!("Failed to deserialize raw message payload: {}", err),
                         },
                     ),
                 }
             }
             PayloadType::EraPayout => {
-                let payload_result: Result<EraPayoutPayload, std::io::Error> =
-                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                let payload_result = EraPayoutPayload::decode_payload(
+                    &raw_message.payload,
+                    raw_message.nonce,
+                    &self.payload_codec,
+                );
                 match payload_result {
                     Ok(payload) => {
                         log!(
@@ -374,6 +609,9 @@ impl AppchainAnchor {
                                 raw_message.nonce as u32,
                                 &AppchainMessageProcessingResult::Error {
                                     nonce: raw_message.nonce as u32,
+                                    error: AppchainMessageError::EraPayoutTooOld {
+                                        era_number: payload.end_era,
+                                    },
                                     message: format!(
                                         "Era number of message 'EraPayout' is too old."
                                     ),
@@ -394,14 +632,20 @@ impl AppchainAnchor {
                         raw_message.nonce as u32,
                         &AppchainMessageProcessingResult::Error {
                             nonce: raw_message.nonce as u32,
+                            error: AppchainMessageError::InvalidPayload {
+                                reason: err.to_string(),
+                            },
                             message: format!("Failed to deserialize raw message payload: {}", err),
                         },
                     ),
                 }
             }
             PayloadType::LockNft => {
-                let payload_result: Result<LockNftPayload, std::io::Error> =
-                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                let payload_result = LockNftPayload::decode_payload(
+                    &raw_message.payload,
+                    raw_message.nonce,
+                    &self.payload_codec,
+                );
                 match payload_result {
                     Ok(payload) => {
                         log!(
@@ -423,6 +667,45 @@ impl AppchainAnchor {
                         raw_message.nonce as u32,
                         &AppchainMessageProcessingResult::Error {
                             nonce: raw_message.nonce as u32,
+                            error: AppchainMessageError::InvalidPayload {
+                                reason: err.to_string(),
+                            },
+                            message: format!("Failed to deserialize raw message payload: {}", err),
+                        },
+                    ),
+                }
+            }
+            PayloadType::ContractCall => {
+                let payload_result = ContractCallPayload::decode_payload(
+                    &raw_message.payload,
+                    raw_message.nonce,
+                    &self.payload_codec,
+                );
+                match payload_result {
+                    Ok(payload) => {
+                        log!(
+                            "Origin appchain message: '{}'",
+                            serde_json::to_string(&payload).unwrap()
+                        );
+                        appchain_messages.insert_message(&AppchainMessage {
+                            nonce: raw_message.nonce as u32,
+                            appchain_event: AppchainEvent::ContractCallRequested {
+                                sender_id_in_appchain: payload.sender,
+                                contract_id: payload.contract_id,
+                                method_name: payload.method_name,
+                                args: payload.args,
+                                deposit: U128::from(payload.deposit),
+                                gas: U64::from(payload.gas),
+                            },
+                        });
+                    }
+                    Err(err) => appchain_messages.insert_processing_result(
+                        raw_message.nonce as u32,
+                        &AppchainMessageProcessingResult::Error {
+                            nonce: raw_message.nonce as u32,
+                            error: AppchainMessageError::InvalidPayload {
+                                reason: err.to_string(),
+                            },
                             message: format!("Failed to deserialize raw message payload: {}", err),
                         },
                     ),
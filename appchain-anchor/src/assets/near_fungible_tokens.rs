@@ -1,4 +1,3 @@
-use near_contract_standards::fungible_token::core::ext_ft_core;
 use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 
 use crate::{
@@ -310,6 +309,9 @@ impl AppchainAnchor {
                 );
                 let result = AppchainMessageProcessingResult::Error {
                     nonce: appchain_message_nonce,
+                    error: AppchainMessageError::BridgingClosed {
+                        contract_account: contract_account.to_string(),
+                    },
                     message: message.clone(),
                 };
                 self.record_appchain_message_processing_result(&result);
@@ -321,26 +323,17 @@ impl AppchainAnchor {
                     None => U128::from(0),
                 };
             near_fungible_tokens.insert(&near_fungible_token);
-            ext_ft_core::ext(near_fungible_token.contract_account)
-                .with_attached_deposit(1)
-                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
-                .with_unused_gas_weight(0)
-                .ft_transfer(receiver_id_in_near.clone(), amount.clone(), None)
-                .then(
-                    ext_self::ext(env::current_account_id())
-                        .with_attached_deposit(0)
-                        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
-                        .with_unused_gas_weight(0)
-                        .resolve_fungible_token_transfer(
-                            near_fungible_token.metadata.symbol,
-                            sender_id_in_appchain.clone(),
-                            receiver_id_in_near.clone(),
-                            amount.clone(),
-                            appchain_message_nonce,
-                        ),
-                );
-            processing_context.add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER));
-            processing_context.add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION));
+            self.internal_transfer_asset_with_storage_check(
+                PendingAssetTransfer::UnlockNearFungibleToken {
+                    symbol: near_fungible_token.metadata.symbol,
+                    contract_account: near_fungible_token.contract_account,
+                    sender_id_in_appchain: sender_id_in_appchain.clone(),
+                    receiver_id_in_near: receiver_id_in_near.clone(),
+                    amount: amount.clone(),
+                    appchain_message_nonce,
+                },
+                processing_context,
+            );
             MultiTxsOperationProcessingResult::Ok
         } else {
             let message = format!(
@@ -349,6 +342,9 @@ impl AppchainAnchor {
             );
             let result = AppchainMessageProcessingResult::Error {
                 nonce: appchain_message_nonce,
+                error: AppchainMessageError::UnregisteredNearFungibleToken {
+                    contract_account: contract_account.to_string(),
+                },
                 message: message.clone(),
             };
             self.record_appchain_message_processing_result(&result);
@@ -395,6 +391,7 @@ impl FungibleTokenContractResolver for AppchainAnchor {
                 self.record_appchain_message_processing_result(
                     &AppchainMessageProcessingResult::Error {
                         nonce: appchain_message_nonce,
+                        error: AppchainMessageError::NearFungibleTokenTransferFailed,
                         message,
                     },
                 );
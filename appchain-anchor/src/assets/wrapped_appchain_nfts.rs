@@ -271,6 +271,9 @@ impl AppchainAnchor {
                 );
                 let result = AppchainMessageProcessingResult::Error {
                     nonce: appchain_message_nonce,
+                    error: AppchainMessageError::BridgingClosed {
+                        contract_account: wrapped_appchain_nft.contract_account.to_string(),
+                    },
                     message: message.clone(),
                 };
                 self.record_appchain_message_processing_result(&result);
@@ -364,6 +367,9 @@ impl AppchainAnchor {
             );
             let result = AppchainMessageProcessingResult::Error {
                 nonce: appchain_message_nonce,
+                error: AppchainMessageError::UnregisteredNftClass {
+                    class_id: class_id.clone(),
+                },
                 message: message.clone(),
             };
             self.record_appchain_message_processing_result(&result);
@@ -479,6 +485,7 @@ impl WrappedAppchainNFTContractResolver for AppchainAnchor {
                 self.record_appchain_message_processing_result(
                     &AppchainMessageProcessingResult::Error {
                         nonce: appchain_message_nonce,
+                        error: AppchainMessageError::WrappedAppchainNftTransferFailed,
                         message,
                     },
                 );
@@ -532,6 +539,7 @@ impl WrappedAppchainNFTContractResolver for AppchainAnchor {
                 self.record_appchain_message_processing_result(
                     &AppchainMessageProcessingResult::Error {
                         nonce: appchain_message_nonce,
+                        error: AppchainMessageError::WrappedAppchainNftMintFailed,
                         message,
                     },
                 );
@@ -164,6 +164,7 @@ impl AppchainAnchor {
             let message = format!("Contract account of wrapped appchain token is not set.");
             let result = AppchainMessageProcessingResult::Error {
                 nonce: appchain_message_nonce,
+                error: AppchainMessageError::ContractAccountOfWrappedAppchainTokenNotSet,
                 message: message.clone(),
             };
             self.record_appchain_message_processing_result(&result);
@@ -176,46 +177,25 @@ impl AppchainAnchor {
                 let message = format!("Invalid sender id in appchain: '{}'", sender_id);
                 let result = AppchainMessageProcessingResult::Error {
                     nonce: appchain_message_nonce,
+                    error: AppchainMessageError::InvalidSenderIdInAppchain {
+                        sender_id: sender_id.clone(),
+                    },
                     message: message.clone(),
                 };
                 self.record_appchain_message_processing_result(&result);
                 return MultiTxsOperationProcessingResult::Error(message);
             }
         }
-        // mint token in wrapped appchain token contract
-        #[derive(near_sdk::serde::Serialize)]
-        #[serde(crate = "near_sdk::serde")]
-        struct Args {
-            account_id: AccountId,
-            amount: U128,
-        }
-        let args = Args {
-            account_id: receiver_id.clone(),
+        let transfer = PendingAssetTransfer::MintWrappedAppchainToken {
+            sender_id_in_appchain: sender_id.map_or(None, |s| Some(s.clone())),
+            receiver_id_in_near: receiver_id.clone(),
             amount: amount.clone(),
+            appchain_message_nonce,
         };
-        let args = near_sdk::serde_json::to_vec(&args)
-            .expect("Failed to serialize the cross contract args using JSON.");
-        Promise::new(wrapped_appchain_token.contract_account.unwrap())
-            .function_call(
-                "mint".to_string(),
-                args,
-                STORAGE_DEPOSIT_FOR_NEP141_TOEKN,
-                Gas::ONE_TERA.mul(T_GAS_FOR_MINT_FUNGIBLE_TOKEN),
-            )
-            .then(
-                ext_self::ext(env::current_account_id())
-                    .with_attached_deposit(0)
-                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
-                    .with_unused_gas_weight(0)
-                    .resolve_wrapped_appchain_token_minting(
-                        sender_id.map_or(None, |s| Some(s.clone())),
-                        receiver_id.clone(),
-                        amount.clone(),
-                        appchain_message_nonce,
-                    ),
-            );
-        processing_context.add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_MINT_FUNGIBLE_TOKEN));
-        processing_context.add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION));
+        // The storage deposit attached to the minting covers the registration of receiver,
+        // so the storage fund is not needed here.
+        processing_context.add_prepaid_gas(transfer.gas_of_transfer());
+        self.internal_transfer_asset(transfer);
         MultiTxsOperationProcessingResult::Ok
     }
 }
@@ -305,6 +285,7 @@ impl WrappedAppchainTokenContractResolver for AppchainAnchor {
                 self.record_appchain_message_processing_result(
                     &AppchainMessageProcessingResult::Error {
                         nonce: appchain_message_nonce,
+                        error: AppchainMessageError::WrappedAppchainTokenMintFailed,
                         message,
                     },
                 );
@@ -0,0 +1,246 @@
+use crate::{permissionless_actions::AppchainMessagesProcessingContext, *};
+
+pub trait ContractCallResolver {
+    /// Resolver for the contract call requested by the appchain
+    fn resolve_contract_call(
+        &mut self,
+        appchain_message_nonce: u32,
+        contract_id: AccountId,
+        method_name: String,
+    );
+}
+
+impl AppchainAnchor {
+    /// Execute the contract call requested by an appchain message, if it is allowed by
+    /// the contract call settings. The prepaid gas and deposit of the call are debited
+    /// from the budget of contract calls. The processing result of the message will be
+    /// recorded in the resolver of the call.
+    pub fn internal_execute_contract_call(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        appchain_message_nonce: u32,
+        sender_id_in_appchain: &String,
+        contract_id: &AccountId,
+        method_name: &String,
+        args: &String,
+        deposit: &U128,
+        gas: &U64,
+    ) -> MultiTxsOperationProcessingResult {
+        if let Err((error, message)) =
+            self.check_contract_call(contract_id, method_name, deposit, gas)
+        {
+            let result = AppchainMessageProcessingResult::Error {
+                nonce: appchain_message_nonce,
+                error,
+                message: message.clone(),
+            };
+            self.record_appchain_message_processing_result(&result);
+            return MultiTxsOperationProcessingResult::Error(message);
+        }
+        let mut contract_call_settings = self.contract_call_settings.get().unwrap();
+        contract_call_settings.remaining_gas_budget =
+            U64::from(contract_call_settings.remaining_gas_budget.0 - gas.0);
+        contract_call_settings.remaining_deposit_budget =
+            U128::from(contract_call_settings.remaining_deposit_budget.0 - deposit.0);
+        self.contract_call_settings.set(&contract_call_settings);
+        Promise::new(contract_id.clone())
+            .function_call(
+                method_name.clone(),
+                args.as_bytes().to_vec(),
+                deposit.0,
+                Gas::from(gas.0),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_contract_call(
+                        appchain_message_nonce,
+                        contract_id.clone(),
+                        method_name.clone(),
+                    ),
+            );
+        processing_context.add_prepaid_gas(Gas::from(gas.0));
+        processing_context.add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION));
+        log!(
+            "Contract call '{}' of '{}' is requested by '{}' of appchain. Deposit: '{}', Gas: '{}'.",
+            method_name,
+            contract_id,
+            sender_id_in_appchain,
+            deposit.0,
+            gas.0
+        );
+        MultiTxsOperationProcessingResult::Ok
+    }
+    /// Whether the contract is this contract itself, or a token contract managed by
+    /// this contract, which can not be called by the appchain.
+    pub fn is_protected_contract(&self, contract_id: &AccountId) -> bool {
+        contract_id.eq(&env::current_account_id())
+            || contract_id.eq(&self.oct_token.get().unwrap().contract_account)
+            || self
+                .wrapped_appchain_token
+                .get()
+                .unwrap()
+                .contract_account
+                .map_or(false, |contract_account| contract_account.eq(contract_id))
+            || self
+                .near_fungible_tokens
+                .get()
+                .unwrap()
+                .get_by_contract_account(contract_id)
+                .is_some()
+            || self
+                .wrapped_appchain_nfts
+                .get()
+                .unwrap()
+                .get_by_contract_account(contract_id)
+                .is_some()
+    }
+    // Check the contract call against the contract call settings and the available balance.
+    fn check_contract_call(
+        &self,
+        contract_id: &AccountId,
+        method_name: &String,
+        deposit: &U128,
+        gas: &U64,
+    ) -> Result<(), (AppchainMessageError, String)> {
+        // The protected contracts may be added after the contract call is allowed.
+        if self.is_protected_contract(contract_id) {
+            return Err((
+                AppchainMessageError::ContractCallToProtectedContract {
+                    contract_id: contract_id.clone(),
+                },
+                format!(
+                    "Contract '{}' is protected from being called by appchain.",
+                    contract_id
+                ),
+            ));
+        }
+        let contract_call_settings = self.contract_call_settings.get().unwrap();
+        if !contract_call_settings
+            .allowed_methods
+            .get(contract_id)
+            .map_or(false, |methods| methods.contains(method_name))
+        {
+            return Err((
+                AppchainMessageError::ContractCallNotAllowed {
+                    contract_id: contract_id.clone(),
+                    method_name: method_name.clone(),
+                },
+                format!(
+                    "Method '{}' of contract '{}' is not allowed to be called by appchain.",
+                    method_name, contract_id
+                ),
+            ));
+        }
+        if gas.0 > contract_call_settings.maximum_gas.0 {
+            return Err((
+                AppchainMessageError::ContractCallGasExceeded {
+                    gas: gas.clone(),
+                    maximum_gas: contract_call_settings.maximum_gas,
+                },
+                format!(
+                    "The gas of contract call '{}' exceeds the maximum gas '{}'.",
+                    gas.0, contract_call_settings.maximum_gas.0
+                ),
+            ));
+        }
+        if deposit.0 > contract_call_settings.maximum_deposit.0 {
+            return Err((
+                AppchainMessageError::ContractCallDepositExceeded {
+                    deposit: deposit.clone(),
+                    maximum_deposit: contract_call_settings.maximum_deposit,
+                },
+                format!(
+                    "The deposit of contract call '{}' exceeds the maximum deposit '{}'.",
+                    deposit.0, contract_call_settings.maximum_deposit.0
+                ),
+            ));
+        }
+        if gas.0 > contract_call_settings.remaining_gas_budget.0 {
+            return Err((
+                AppchainMessageError::ContractCallGasBudgetExhausted {
+                    gas: gas.clone(),
+                    remaining_gas_budget: contract_call_settings.remaining_gas_budget,
+                },
+                format!(
+                    "The gas of contract call '{}' exceeds the remaining gas budget '{}'.",
+                    gas.0, contract_call_settings.remaining_gas_budget.0
+                ),
+            ));
+        }
+        if deposit.0 > contract_call_settings.remaining_deposit_budget.0 {
+            return Err((
+                AppchainMessageError::ContractCallDepositBudgetExhausted {
+                    deposit: deposit.clone(),
+                    remaining_deposit_budget: contract_call_settings.remaining_deposit_budget,
+                },
+                format!(
+                    "The deposit of contract call '{}' exceeds the remaining deposit budget '{}'.",
+                    deposit.0, contract_call_settings.remaining_deposit_budget.0
+                ),
+            ));
+        }
+        if deposit.0 > self.get_available_balance_for_contract_call() {
+            return Err((
+                AppchainMessageError::InsufficientBalanceForContractCall,
+                format!(
+                    "The available balance is not enough for the deposit of contract call '{}'.",
+                    deposit.0
+                ),
+            ));
+        }
+        Ok(())
+    }
+    // Get the balance of this contract which is not used for storage staking
+    // and storage fund.
+    fn get_available_balance_for_contract_call(&self) -> Balance {
+        env::account_balance()
+            .saturating_sub(Balance::from(env::storage_usage()) * env::storage_byte_cost())
+            .saturating_sub(self.storage_fund_balance)
+    }
+}
+
+#[near_bindgen]
+impl ContractCallResolver for AppchainAnchor {
+    //
+    fn resolve_contract_call(
+        &mut self,
+        appchain_message_nonce: u32,
+        contract_id: AccountId,
+        method_name: String,
+    ) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                let message = format!(
+                    "Contract call '{}' of '{}' is done. Return value: '{}'",
+                    method_name,
+                    contract_id,
+                    String::from_utf8_lossy(&value)
+                );
+                self.record_appchain_message_processing_result(
+                    &AppchainMessageProcessingResult::Ok {
+                        nonce: appchain_message_nonce,
+                        message: Some(message),
+                    },
+                );
+            }
+            PromiseResult::Failed => {
+                let message = format!(
+                    "Failed to call '{}' of contract '{}'.",
+                    method_name, contract_id
+                );
+                self.record_appchain_message_processing_result(
+                    &AppchainMessageProcessingResult::Error {
+                        nonce: appchain_message_nonce,
+                        error: AppchainMessageError::ContractCallFailed,
+                        message,
+                    },
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use crate::*;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+pub trait IncentiveRewardTransferResolver {
+    /// Resolver for transferring the incentive rewards to a validator or a delegator
+    fn resolve_incentive_reward_transfer(
+        &mut self,
+        token_contract: AccountId,
+        delegator_id: Option<AccountId>,
+        validator_id: AccountId,
+        amount: U128,
+    );
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct IncentivePools {
+    /// The set of contract accounts of incentive tokens.
+    token_contracts: UnorderedSet<AccountId>,
+    /// The incentive pools, mapped by the contract account of the incentive token.
+    pools: LookupMap<AccountId, IncentivePool>,
+}
+
+impl IncentivePools {
+    ///
+    pub fn new() -> Self {
+        Self {
+            token_contracts: UnorderedSet::new(
+                StorageKey::IncentivePoolTokenContracts.into_bytes(),
+            ),
+            pools: LookupMap::new(StorageKey::IncentivePoolsMap.into_bytes()),
+        }
+    }
+    ///
+    pub fn to_vec(&self) -> Vec<IncentivePool> {
+        let token_contracts = self.token_contracts.to_vec();
+        token_contracts
+            .iter()
+            .map(|token_contract| self.pools.get(token_contract).unwrap())
+            .collect::<Vec<IncentivePool>>()
+    }
+    ///
+    pub fn get(&self, token_contract: &AccountId) -> Option<IncentivePool> {
+        self.pools.get(token_contract)
+    }
+    ///
+    pub fn insert(&mut self, incentive_pool: &IncentivePool) {
+        self.token_contracts.insert(&incentive_pool.token_contract);
+        self.pools
+            .insert(&incentive_pool.token_contract, incentive_pool);
+    }
+    /// Allocate the per era amount of all unfinished pools to an era.
+    pub fn allocate_for_era(&mut self) -> Vec<IncentiveRewardOfEra> {
+        let mut incentive_rewards = Vec::<IncentiveRewardOfEra>::new();
+        for mut incentive_pool in self.to_vec() {
+            if incentive_pool.remaining_era_count.0 == 0 {
+                continue;
+            }
+            incentive_pool.remaining_era_count.0 -= 1;
+            incentive_pool.total_allocated_amount.0 += incentive_pool.per_era_amount.0;
+            self.insert(&incentive_pool);
+            incentive_rewards.push(IncentiveRewardOfEra {
+                token_contract: incentive_pool.token_contract,
+                amount: incentive_pool.per_era_amount,
+            });
+        }
+        incentive_rewards
+    }
+}
+
+impl AppchainAnchor {
+    //
+    pub fn internal_fund_incentive_pool(
+        &mut self,
+        predecessor_account_id: AccountId,
+        sender_id: AccountId,
+        amount: U128,
+        eras: u64,
+        per_era_amount: u128,
+    ) -> PromiseOrValue<U128> {
+        assert!(
+            predecessor_account_id.eq(&self.oct_token.get().unwrap().contract_account)
+                || self
+                    .near_fungible_tokens
+                    .get()
+                    .unwrap()
+                    .get_by_contract_account(&predecessor_account_id)
+                    .is_some(),
+            "Received invalid deposit '{}' in contract '{}' from '{}'. Return deposit.",
+            &amount.0,
+            &predecessor_account_id,
+            &sender_id,
+        );
+        assert!(
+            eras > 0 && per_era_amount > 0,
+            "Invalid parameters for funding incentive pool. Return deposit."
+        );
+        let funding_amount = match per_era_amount.checked_mul(u128::from(eras)) {
+            Some(value) if value <= amount.0 => value,
+            _ => panic!("Not enough deposit for funding incentive pool. Return deposit."),
+        };
+        let mut incentive_pools = self.incentive_pools.get().unwrap();
+        let incentive_pool = match incentive_pools.get(&predecessor_account_id) {
+            Some(mut incentive_pool) => {
+                assert!(
+                    incentive_pool.remaining_era_count.0 == 0
+                        || incentive_pool.per_era_amount.0 == per_era_amount,
+                    "The per era amount of an unfinished incentive pool can not be changed. Return deposit."
+                );
+                incentive_pool.per_era_amount = U128::from(per_era_amount);
+                incentive_pool.remaining_era_count.0 += eras;
+                incentive_pool.total_funded_amount.0 += funding_amount;
+                incentive_pool
+            }
+            None => IncentivePool {
+                token_contract: predecessor_account_id.clone(),
+                per_era_amount: U128::from(per_era_amount),
+                remaining_era_count: U64::from(eras),
+                total_funded_amount: U128::from(funding_amount),
+                total_allocated_amount: U128::from(0),
+            },
+        };
+        incentive_pools.insert(&incentive_pool);
+        self.incentive_pools.set(&incentive_pools);
+        log!(
+            "Incentive pool of token '{}' is funded by '{}'. Amount per era: '{}', Remaining eras: '{}'.",
+            &predecessor_account_id,
+            &sender_id,
+            &per_era_amount,
+            &incentive_pool.remaining_era_count.0
+        );
+        PromiseOrValue::Value(U128::from(amount.0 - funding_amount))
+    }
+    /// Allocate the incentive tokens to the stakes of an era, only once for each era.
+    pub fn internal_allocate_incentive_rewards_of(
+        &mut self,
+        validator_set: &mut ValidatorSetOfEra,
+    ) {
+        if validator_set.incentive_rewards_are_allocated() || validator_set.valid_total_stake() == 0
+        {
+            return;
+        }
+        let mut incentive_pools = self.incentive_pools.get().unwrap();
+        validator_set.set_incentive_rewards(incentive_pools.allocate_for_era());
+        self.incentive_pools.set(&incentive_pools);
+    }
+    /// Withdraw the incentive rewards of a validator (or a delegator of it) in a certain token.
+    ///
+    /// The checkpoint of the account only moves forward over the eras whose
+    /// reward distribution is completed. The rewards which are failed to be transferred
+    /// will be credited to the account and withdrawn together in the next withdrawal.
+    pub fn internal_withdraw_incentive_rewards(
+        &mut self,
+        token_contract: AccountId,
+        delegator_id: Option<AccountId>,
+        validator_id: AccountId,
+    ) {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let (start_era, end_era) = self.get_era_range_of_unwithdrawn_reward();
+        let checkpoint_key = (
+            token_contract.clone(),
+            delegator_id.clone(),
+            validator_id.clone(),
+        );
+        let old_checkpoint = self
+            .incentive_reward_checkpoints
+            .get(&checkpoint_key)
+            .unwrap_or(0);
+        let mut checkpoint = old_checkpoint.max(start_era);
+        let mut reward_to_withdraw = self
+            .incentive_reward_credits
+            .get(&checkpoint_key)
+            .unwrap_or(0);
+        while checkpoint <= end_era {
+            if let Some(validator_set) = validator_set_histories.get(&checkpoint) {
+                if validator_set.processing_status() != ValidatorSetProcessingStatus::Completed {
+                    break;
+                }
+                reward_to_withdraw += validator_set
+                    .get_incentive_reward_of(&token_contract, &delegator_id, &validator_id)
+                    .unwrap_or(0);
+            }
+            checkpoint += 1;
+        }
+        if checkpoint != old_checkpoint {
+            self.incentive_reward_checkpoints
+                .insert(&checkpoint_key, &checkpoint);
+        }
+        if reward_to_withdraw > 0 {
+            self.incentive_reward_credits.remove(&checkpoint_key);
+            ext_ft_core::ext(token_contract.clone())
+                .with_attached_deposit(1)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                .with_unused_gas_weight(0)
+                .ft_transfer(
+                    delegator_id.clone().unwrap_or(validator_id.clone()),
+                    reward_to_withdraw.into(),
+                    None,
+                )
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_attached_deposit(0)
+                        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                        .with_unused_gas_weight(0)
+                        .resolve_incentive_reward_transfer(
+                            token_contract,
+                            delegator_id,
+                            validator_id,
+                            U128::from(reward_to_withdraw),
+                        ),
+                );
+        }
+    }
+    /// Get the incentive reward histories of a validator (or a delegator of it)
+    /// in a certain token and a certain era range.
+    pub fn get_incentive_reward_histories_of(
+        &self,
+        token_contract: &AccountId,
+        start_era: u64,
+        end_era: u64,
+        delegator_id: &Option<AccountId>,
+        validator_id: &AccountId,
+    ) -> Vec<RewardHistory> {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let checkpoint = self
+            .incentive_reward_checkpoints
+            .get(&(
+                token_contract.clone(),
+                delegator_id.clone(),
+                validator_id.clone(),
+            ))
+            .unwrap_or(0)
+            .max(self.get_era_range_of_unwithdrawn_reward().0);
+        let mut reward_histories = Vec::<RewardHistory>::new();
+        for era_number in start_era..end_era + 1 {
+            if let Some(validator_set) = validator_set_histories.get(&era_number) {
+                if let Some(reward) = validator_set.get_incentive_reward_of(
+                    token_contract,
+                    delegator_id,
+                    validator_id,
+                ) {
+                    reward_histories.push(RewardHistory {
+                        era_number: U64::from(era_number),
+                        total_reward: U128::from(reward),
+                        unwithdrawn_reward: match era_number >= checkpoint {
+                            true => U128::from(reward),
+                            false => U128::from(0),
+                        },
+                    });
+                }
+            }
+        }
+        reward_histories
+    }
+    // Get the range of eras (both inclusive) in which the rewards can be withdrawn.
+    fn get_era_range_of_unwithdrawn_reward(&self) -> (u64, u64) {
+        let end_era = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let start_era = match end_era > protocol_settings.maximum_era_count_of_unwithdrawn_reward.0
+        {
+            true => end_era - protocol_settings.maximum_era_count_of_unwithdrawn_reward.0 + 1,
+            false => 0,
+        };
+        (start_era, end_era)
+    }
+}
+
+#[near_bindgen]
+impl IncentiveRewardTransferResolver for AppchainAnchor {
+    //
+    fn resolve_incentive_reward_transfer(
+        &mut self,
+        token_contract: AccountId,
+        delegator_id: Option<AccountId>,
+        validator_id: AccountId,
+        amount: U128,
+    ) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                log!(
+                    "Incentive rewards of token '{}' are withdrawn to '{}'. Amount: '{}'.",
+                    &token_contract,
+                    delegator_id.as_ref().unwrap_or(&validator_id),
+                    &amount.0
+                );
+            }
+            PromiseResult::Failed => {
+                let credit_key = (token_contract.clone(), delegator_id.clone(), validator_id);
+                let credit = self.incentive_reward_credits.get(&credit_key).unwrap_or(0);
+                self.incentive_reward_credits
+                    .insert(&credit_key, &(credit + amount.0));
+                log!(
+                    "Failed to transfer incentive rewards of token '{}' to '{}'. Amount: '{}'. They will be withdrawn in the next withdrawal.",
+                    &token_contract,
+                    delegator_id.as_ref().unwrap_or(&credit_key.2),
+                    &amount.0
+                );
+            }
+        }
+    }
+}
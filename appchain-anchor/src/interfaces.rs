@@ -20,6 +20,27 @@ pub trait AnchorViewer {
     fn get_appchain_settings(&self) -> AppchainSettings;
     /// Get protocol settings detail.
     fn get_protocol_settings(&self) -> ProtocolSettings;
+    /// Get slashing settings detail.
+    fn get_slashing_settings(&self) -> SlashingSettings;
+    /// Get the schedule for calculating the reward of each era.
+    fn get_reward_schedule(&self) -> RewardSchedule;
+    /// Get the codec of the payloads of appchain messages.
+    fn get_payload_codec(&self) -> PayloadCodec;
+    /// Get the settings for the contract calls requested by the appchain.
+    fn get_contract_call_settings(&self) -> ContractCallSettings;
+    /// Get the history of sweeping expired rewards of the given era.
+    fn get_expired_reward_sweep_history_of(
+        &self,
+        era_number: U64,
+    ) -> Option<ExpiredRewardSweepHistory>;
+    /// Get the reward settings of a staker.
+    fn get_reward_settings_of(&self, account_id: AccountId) -> Option<RewardSettings>;
+    /// Get the reward of a staker which failed to be paid to its reward destination.
+    fn get_reward_credit_of(&self, account_id: AccountId) -> U128;
+    /// Get the amount of slashed OCT token which failed to be transferred to the treasury account.
+    fn get_untransferred_slashed_amount(&self) -> U128;
+    /// Get the balance of storage fund (in yocto NEAR).
+    fn get_storage_fund_balance(&self) -> U128;
     /// Get info of OCT token.
     fn get_oct_token(&self) -> OctToken;
     /// Get info of wrapped appchain token.
@@ -66,6 +87,9 @@ pub trait AnchorViewer {
     ) -> Vec<AppchainNotificationHistory>;
     /// Get the validator list of a certain era.
     fn get_validator_list_of(&self, era_number: Option<U64>) -> Vec<AppchainValidator>;
+    /// Get the validator list of a certain era which is used as the validator set of appchain.
+    /// The jailed and standby validators are excluded.
+    fn get_appchain_validator_list_of(&self, era_number: U64) -> Vec<AppchainValidator>;
     /// Get the delegators of a validator of a certain era.
     /// If the param `era_number` is omitted, the latest validator set will be used.
     fn get_delegators_of_validator_in_era(
@@ -90,6 +114,25 @@ pub trait AnchorViewer {
         delegator_id: AccountId,
         validator_id: AccountId,
     ) -> Vec<RewardHistory>;
+    /// Get all incentive pools.
+    fn get_incentive_pools(&self) -> Vec<IncentivePool>;
+    /// Get validator incentive rewards in a certain token of a certain era range.
+    fn get_validator_incentive_rewards_of(
+        &self,
+        token_contract: AccountId,
+        start_era: U64,
+        end_era: U64,
+        validator_id: AccountId,
+    ) -> Vec<RewardHistory>;
+    /// Get delegator incentive rewards in a certain token of a certain era range.
+    fn get_delegator_incentive_rewards_of(
+        &self,
+        token_contract: AccountId,
+        start_era: U64,
+        end_era: U64,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    ) -> Vec<RewardHistory>;
     /// Get current storage balance needed by this contract account.
     fn get_storage_balance(&self) -> U128;
     /// Get deposit of a certain validator in a certain era.
@@ -116,6 +159,27 @@ pub trait AnchorViewer {
         &self,
         validator_id_in_appchain: String,
     ) -> Option<ValidatorProfile>;
+    /// Get commission settings of a certain validator.
+    fn get_validator_commission_of(&self, validator_id: AccountId) -> Option<ValidatorCommission>;
+    /// Get commission percent of a certain validator in a certain era.
+    fn get_validator_commission_percent_of(
+        &self,
+        validator_id: AccountId,
+        era_number: Option<U64>,
+    ) -> u16;
+    /// Get operator account of a certain validator.
+    fn get_validator_operator_of(&self, validator_id: AccountId) -> Option<AccountId>;
+    /// Get delegation policy of a certain validator.
+    fn get_delegation_policy_of(&self, validator_id: AccountId) -> Option<DelegationPolicy>;
+    /// Get the pending staking deposit funded by another account for a certain account.
+    fn get_pending_staking_deposit_of(
+        &self,
+        account_id: AccountId,
+    ) -> Option<PendingStakingDeposit>;
+    /// Get the validators which are jailed in the next validator set.
+    fn get_jailed_validators(&self) -> Vec<JailedValidator>;
+    /// Get the unbonding requests of validators which are waiting for the exit limit.
+    fn get_queued_unbonding_validators(&self) -> Vec<QueuedUnbondingValidator>;
     /// Get the latest commitment data of appchain state.
     fn get_latest_commitment_of_appchain(&self) -> Option<AppchainCommitment>;
     /// Get status of the beefy light client.
@@ -141,19 +205,27 @@ pub trait AnchorViewer {
         start_nonce: u32,
         quantity: Option<u32>,
     ) -> Vec<AppchainMessageProcessingResult>;
+    /// Get the nonces of appchain messages which are never staged and not skipped,
+    /// and not greater than the max nonce of staged appchain messages.
+    /// Up to 50 nonces in the range will be checked.
+    fn get_missing_appchain_message_nonces(
+        &self,
+        start_nonce: u32,
+        quantity: Option<u32>,
+    ) -> Vec<u32>;
     /// Get appchain challenge by index.
     /// If the param `index `is omitted, the latest challenge data will be returned.
     /// If the paran `index` is smaller than the start index, or bigger than the end index
     /// stored in anchor, or there is no challenge data in anchor yet,
     /// `Option::None` will be returned.
-    fn get_appchain_challenge(&self, index: Option<U64>) -> Option<AppchainChallenge>;
+    fn get_appchain_challenge(&self, index: Option<U64>) -> Option<AppchainChallengeInfo>;
     /// Get appchain challenge data by start index and quantity.
     /// If the param `quantity` is omitted, up to 50 records will be returned.
     fn get_appchain_challenges(
         &self,
         start_index: U64,
         quantity: Option<U64>,
-    ) -> Vec<AppchainChallenge>;
+    ) -> Vec<AppchainChallengeInfo>;
 }
 
 pub trait AppchainLifecycleManager {
@@ -192,12 +264,22 @@ pub trait NearFungibleTokenManager {
 }
 
 pub trait OwnerActions {
+    /// Remove the validator sets before a certain era.
     ///
+    /// The era number must not be in the range of eras with withdrawable rewards,
+    /// and it is rounded down to the nearest checkpoint era (a multiple of 16),
+    /// as the validator sets after a checkpoint are based on it.
     fn remove_validator_set_before(&mut self, era_number: U64);
     ///
     fn remove_staking_history_before(&mut self, index: U64);
     ///
     fn remove_appchain_notification_history_before(&mut self, index: U64);
+    /// Transfer the undistributed reward (the dust) of an era to the treasury account.
+    /// If the transfer fails, the dust can be swept again.
+    fn sweep_reward_dust_of_era(&mut self, era_number: U64);
+    /// Add the attached deposit to the storage fund, which is used for registering
+    /// the receivers of inbound asset transfers in token contracts.
+    fn deposit_to_storage_fund(&mut self);
 }
 
 pub trait PermissionlessActions {
@@ -225,6 +307,9 @@ pub trait PermissionlessActions {
     fn process_appchain_messages(&mut self) -> MultiTxsOperationProcessingResult;
     ///
     fn commit_appchain_challenge(&mut self, appchain_challenge: AppchainChallenge);
+    /// Re-send the bounty of an applied appchain challenge to its submitter,
+    /// if the last transfer of it failed.
+    fn resend_challenge_bounty(&mut self, challenge_index: U64);
     //
     fn process_appchain_messages_with_all_proofs(
         &mut self,
@@ -237,6 +322,10 @@ pub trait PermissionlessActions {
         mmr_leaf_for_header: Vec<u8>,
         mmr_proof_for_header: Vec<u8>,
     );
+    /// Sweep the unwithdrawn rewards of the given era, which can no longer be withdrawn,
+    /// to the treasury account.
+    /// If the transfer of the swept rewards failed, calling this function again will re-send them.
+    fn sweep_expired_rewards(&mut self, era_number: U64) -> MultiTxsOperationProcessingResult;
 }
 
 pub trait ProtocolSettingsManager {
@@ -274,6 +363,27 @@ pub trait ProtocolSettingsManager {
     fn change_validator_commission_percent(&mut self, value: u16);
     ///
     fn change_maximum_allowed_unprofitable_era_count(&mut self, value: u16);
+    ///
+    fn change_minimum_validator_commission_percent(&mut self, value: u16);
+    ///
+    fn change_maximum_validator_commission_percent(&mut self, value: u16);
+    ///
+    fn change_maximum_validator_commission_percent_change_per_era(&mut self, value: u16);
+    ///
+    fn change_validator_commission_change_notice_era_count(&mut self, value: U64);
+    /// Change the limit of validators entering the validator set in an era.
+    fn change_validator_entry_limit(&mut self, value: ValidatorChurnLimit);
+    /// Change the limit of validators leaving the validator set in an era.
+    fn change_validator_exit_limit(&mut self, value: ValidatorChurnLimit);
+    /// Change the account which receives the treasury share of era reward.
+    fn change_treasury_account(&mut self, value: AccountId);
+    /// Change the percent of era reward to be minted to the treasury account.
+    fn change_treasury_reward_percent(&mut self, value: u16);
+    /// Change the handling of the share of era reward of unprofitable validators.
+    fn change_unprofitable_validator_reward_policy(
+        &mut self,
+        value: UnprofitableValidatorRewardPolicy,
+    );
 }
 
 pub trait AppchainSettingsManager {
@@ -283,8 +393,12 @@ pub trait AppchainSettingsManager {
     fn set_subql_endpoint(&mut self, subql_endpoint: String);
     ///
     fn set_era_reward(&mut self, era_reward: U128);
+    /// Set the schedule for calculating the reward of each era.
+    fn set_reward_schedule(&mut self, reward_schedule: RewardSchedule);
     ///
     fn set_bonus_for_new_validator(&mut self, bonus_amount: U128);
+    /// Set the codec of the payloads of appchain messages.
+    fn set_payload_codec(&mut self, payload_codec: PayloadCodec);
 }
 
 pub trait AnchorSettingsManager {
@@ -298,6 +412,41 @@ pub trait AnchorSettingsManager {
     fn turn_off_beefy_light_client_witness_mode(&mut self);
 }
 
+pub trait SlashingSettingsManager {
+    /// Set the percent of deposit to be slashed for each offence of a certain kind.
+    fn set_slash_percent_of_offence_kind(&mut self, offence_kind: String, slash_percent: u16);
+    /// Stop slashing the offences of a certain kind.
+    fn remove_slash_percent_of_offence_kind(&mut self, offence_kind: String);
+    ///
+    fn turn_on_delegators_slashing(&mut self);
+    ///
+    fn turn_off_delegators_slashing(&mut self);
+    ///
+    fn set_slashed_token_destination(&mut self, destination: SlashedTokenDestination);
+    /// Set the number of eras for which a slashed offender will be jailed.
+    fn set_offender_jail_era_count(&mut self, value: U64);
+    /// Set the penalty for a validator which is proved to be equivocating.
+    fn set_equivocation_penalty(&mut self, penalty: EquivocationPenalty);
+    /// Set the percent of slashed token to be paid to the submitter of
+    /// an equivocation challenge.
+    fn set_equivocation_challenger_bounty_percent(&mut self, value: u16);
+}
+
+pub trait ContractCallSettingsManager {
+    /// Allow the appchain to call a method of a contract.
+    fn allow_contract_call(&mut self, contract_id: AccountId, method_name: String);
+    /// Disallow the appchain to call a method of a contract.
+    fn disallow_contract_call(&mut self, contract_id: AccountId, method_name: String);
+    /// Set the maximum prepaid gas of a contract call requested by the appchain.
+    fn set_maximum_gas_of_contract_call(&mut self, value: U64);
+    /// Set the maximum deposit (in yocto NEAR) attached to a contract call
+    /// requested by the appchain.
+    fn set_maximum_deposit_of_contract_call(&mut self, value: U128);
+    /// Set the remaining cumulative budget of prepaid gas and deposit (in yocto NEAR)
+    /// for all contract calls requested by the appchain.
+    fn set_contract_call_budget(&mut self, gas: U64, deposit: U128);
+}
+
 pub trait StakingManager {
     /// Decrease stake of an account (validator).
     /// This function can only be called by a validator.
@@ -317,6 +466,42 @@ pub trait StakingManager {
     /// Unbond delegation of an account (delegator) to a validator.
     /// This function can only be called by a delegator.
     fn unbond_delegation(&mut self, validator_id: AccountId);
+    /// Move delegation of an account (delegator) from a validator to another validator,
+    /// without waiting for the unlock period of delegator deposit.
+    /// This function can only be called by a delegator.
+    fn redelegate(
+        &mut self,
+        from_validator_id: AccountId,
+        to_validator_id: AccountId,
+        amount: U128,
+    );
+    /// Rebond the unbonded stake of an account (validator) which is referred by
+    /// the given staking history, before withdrawing it.
+    /// This function can only be called by a validator.
+    fn rebond_stake(&mut self, staking_history_index: U64);
+    /// Rebond the unbonded delegation of an account (delegator) to a validator which is
+    /// referred by the given staking history, before withdrawing it.
+    /// This function can only be called by a delegator.
+    fn rebond_delegation(&mut self, validator_id: AccountId, staking_history_index: U64);
+    /// Accept the validator registration funded by another account for the caller,
+    /// and register the caller as a validator with the deposit of the funder.
+    /// This function can only be called by the beneficiary of the registration.
+    fn accept_validator_registration(
+        &mut self,
+        validator_id_in_appchain: String,
+        can_be_delegated_to: bool,
+        profile: HashMap<String, String>,
+        commission_percent: Option<u16>,
+    );
+    /// Accept the staking deposit funded by another account for the caller, and apply
+    /// the staking action (other than validator registration) of it.
+    /// This function can only be called by the beneficiary of the deposit.
+    fn accept_staking_deposit(&mut self);
+    /// Cancel the pending staking deposit for a certain account,
+    /// and return the deposit to the funder. The pending deposit is removed
+    /// after the deposit is returned successfully.
+    /// This function can only be called by the funder or the beneficiary.
+    fn cancel_staking_deposit(&mut self, beneficiary_id: AccountId);
     /// Withdraw unbonded stake(s) of a certain account.
     /// This function can be called by any account.
     fn withdraw_stake(&mut self, account_id: AccountId);
@@ -326,6 +511,25 @@ pub trait StakingManager {
     /// Withdraw rewards of a certain delegator to a validator.
     /// This function can be called by any account.
     fn withdraw_delegator_rewards(&mut self, delegator_id: AccountId, validator_id: AccountId);
+    /// Withdraw incentive rewards in a certain token of a certain validator.
+    /// This function can be called by any account.
+    fn withdraw_validator_incentive_rewards(
+        &mut self,
+        token_contract: AccountId,
+        validator_id: AccountId,
+    );
+    /// Withdraw incentive rewards in a certain token of a certain delegator to a validator.
+    /// This function can be called by any account.
+    fn withdraw_delegator_incentive_rewards(
+        &mut self,
+        token_contract: AccountId,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    );
+    /// Set the destination of rewards of the caller, and whether the rewards are paid
+    /// automatically at the end of the reward distribution of each era.
+    /// This function can only be called by a validator or a delegator.
+    fn set_reward_settings(&mut self, destination: RewardDestination, auto_claim: bool);
 }
 
 pub trait SudoActions {
@@ -359,6 +563,9 @@ pub trait SudoActions {
     );
     ///
     fn set_latest_applied_appchain_message_nonce(&mut self, nonce: u32);
+    /// Mark the given nonces, which are never staged, as skipped with the given reason,
+    /// so that the processing of appchain messages can continue.
+    fn skip_appchain_messages(&mut self, nonces: Vec<u32>, reason: String);
     ///
     fn unlock_auto_unbonded_stake_of(
         &mut self,
@@ -366,6 +573,10 @@ pub trait SudoActions {
         validator_id: AccountId,
         staking_history_index: U64,
     );
+    /// Jail a validator for a number of eras, starting from the next era.
+    fn jail_validator(&mut self, validator_id: AccountId, era_count: U64);
+    /// Release a jailed validator regardless of its sentence.
+    fn unjail_validator(&mut self, validator_id: AccountId);
 }
 
 pub trait ValidatorActions {
@@ -373,6 +584,18 @@ pub trait ValidatorActions {
     fn set_validator_id_in_appchain(&mut self, account_id_in_appchain: String);
     ///
     fn set_validator_profile(&mut self, profile: HashMap<String, String>);
+    /// Change the commission percent of the validator, which will take effect
+    /// after the notice period defined in protocol settings.
+    fn set_validator_commission_percent(&mut self, commission_percent: u16);
+    /// Set the policy for delegators delegating to the validator.
+    fn set_delegation_policy(&mut self, policy: DelegationPolicy);
+    /// Set the operator account which can manage the profile, the account id in appchain
+    /// and the delegation flag on behalf of the validator.
+    fn set_validator_operator(&mut self, operator_id: AccountId);
+    /// Revoke the operator account of the validator.
+    fn revoke_validator_operator(&mut self);
+    /// Release the validator from jail after its sentence expires.
+    fn unjail(&mut self);
 }
 
 pub trait WrappedAppchainTokenManager {
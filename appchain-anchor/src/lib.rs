@@ -2,16 +2,21 @@ mod anchor_viewer;
 pub mod appchain_challenge;
 pub mod appchain_messages;
 mod assets;
+mod contract_calls;
+mod incentive_pools;
 pub mod interfaces;
 mod lookup_array;
 mod permissionless_actions;
 mod reward_distribution_records;
+mod reward_payments;
+mod storage_fund;
 mod storage_key;
 pub mod storage_migration;
 pub mod types;
 mod upgrade;
 mod user_actions;
 mod user_staking_histories;
+mod validator_commissions;
 mod validator_profiles;
 mod validator_set;
 
@@ -21,7 +26,7 @@ use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
 use near_contract_standards::non_fungible_token::TokenId;
 use near_contract_standards::upgrade::Ownable;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
+use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedSet};
 use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
@@ -33,18 +38,20 @@ use std::ops::Mul;
 pub use appchain_messages::AppchainMessage;
 pub use permissionless_actions::AppchainEvent;
 
-use appchain_challenge::AppchainChallenge;
+use appchain_challenge::{AppchainChallenge, AppchainChallengeInfo, AppchainChallengeStatuses};
 use appchain_messages::AppchainMessages;
 use assets::near_fungible_tokens::NearFungibleTokens;
 use assets::wrapped_appchain_nfts::WrappedAppchainNFTs;
 use beefy_light_client::Hash;
 use beefy_light_client::LightClient;
+use incentive_pools::IncentivePools;
 use lookup_array::{IndexedAndClearable, LookupArray};
 use reward_distribution_records::RewardDistributionRecords;
 use storage_key::StorageKey;
 use types::*;
 use user_actions::UnbondedStakeReference;
 use user_staking_histories::UserStakingHistories;
+use validator_commissions::ValidatorCommissions;
 use validator_profiles::ValidatorProfiles;
 use validator_set::next_validator_set::NextValidatorSet;
 use validator_set::validator_set_of_era::ValidatorSetOfEra;
@@ -56,21 +63,29 @@ register_custom_getrandom!(get_random_in_near);
 const ANCHOR_VERSION: &str = "v2.2.0";
 /// Constants for gas.
 const T_GAS_FOR_FT_TRANSFER: u64 = 10;
+const T_GAS_FOR_FT_TRANSFER_CALL: u64 = 50;
 const T_GAS_FOR_BURN_FUNGIBLE_TOKEN: u64 = 10;
 const T_GAS_FOR_MINT_FUNGIBLE_TOKEN: u64 = 20;
 const T_GAS_FOR_NFT_TRANSFER: u64 = 10;
 const T_GAS_FOR_MINT_NFT: u64 = 10;
 const T_GAS_FOR_RESOLVER_FUNCTION: u64 = 10;
+const T_GAS_FOR_STORAGE_BALANCE_OF: u64 = 5;
+const T_GAS_FOR_STORAGE_DEPOSIT: u64 = 10;
 const T_GAS_FOR_SYNC_STATE_TO_REGISTRY: u64 = 10;
 const T_GAS_CAP_FOR_MULTI_TXS_PROCESSING: u64 = 150;
 const T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES: u64 = 240;
 const T_GAS_FOR_NFT_CONTRACT_INITIALIZATION: u64 = 50;
 const T_GAS_FOR_REGISTER_VALIDATOR: u64 = 100;
 const T_GAS_FOR_BURN_WRAPPED_APPCHAIN_TOKEN: u64 = 50;
+const T_GAS_CAP_FOR_CONTRACT_CALL: u64 = 50;
 /// The value of decimals value of USD.
 const USD_DECIMALS_VALUE: Balance = 1_000_000;
 /// The value of decimals value of OCT token.
 const OCT_DECIMALS_VALUE: Balance = 1_000_000_000_000_000_000;
+/// The scale of the fixed-point ratios used in calculating rewards
+const RATIO_SCALE: u128 = 1_000_000_000_000_000_000_000_000;
+/// The interval of eras for storing a full copy of validator set.
+const VALIDATOR_SET_CHECKPOINT_INTERVAL: u64 = 16;
 /// The seconds of a day.
 const SECONDS_OF_A_DAY: u64 = 86400;
 /// Multiple of nano seconds for a second.
@@ -128,6 +143,42 @@ trait ResolverForSelfCallback {
         token_metadata: TokenMetadata,
         appchain_message_nonce: u32,
     );
+    /// Resolver for paying reward to the reward destination of a staker
+    fn resolve_reward_payment(
+        &mut self,
+        staker_id: AccountId,
+        amount: U128,
+        destination: RewardDestination,
+    );
+    /// Resolver for checking the storage balance of the receiver of an inbound asset transfer
+    fn resolve_receiver_storage_balance(&mut self, transfer: PendingAssetTransfer);
+    /// Resolver for registering the receiver of an inbound asset transfer by storage fund
+    fn resolve_storage_deposit_by_fund(&mut self, transfer: PendingAssetTransfer);
+    /// Resolver for transferring the swept expired rewards to the treasury account
+    fn resolve_expired_reward_transfer(&mut self, era_number: U64);
+    /// Resolver for transferring slashed OCT token to the treasury account
+    fn resolve_slashed_oct_token_transfer(&mut self, treasury_account: AccountId, amount: U128);
+    /// Resolver for transferring the bounty of an appchain challenge to its submitter
+    fn resolve_challenge_bounty_transfer(&mut self, challenge_index: U64);
+    /// Resolver for refunding a cancelled staking deposit to its funder
+    fn resolve_staking_deposit_refund(&mut self, beneficiary_id: AccountId);
+    /// Resolver for transferring the swept reward dust of an era to the treasury account
+    fn resolve_reward_dust_transfer(&mut self, era_number: U64, amount: U128);
+    /// Resolver for transferring the incentive rewards to a validator or a delegator
+    fn resolve_incentive_reward_transfer(
+        &mut self,
+        token_contract: AccountId,
+        delegator_id: Option<AccountId>,
+        validator_id: AccountId,
+        amount: U128,
+    );
+    /// Resolver for the contract call requested by the appchain
+    fn resolve_contract_call(
+        &mut self,
+        appchain_message_nonce: u32,
+        contract_id: AccountId,
+        method_name: String,
+    );
 }
 
 #[near_bindgen]
@@ -160,6 +211,12 @@ pub struct AppchainAnchor {
     /// The map of unwithdrawn delegator rewards in eras, in unit of wrapped appchain token.
     /// The key in map is `(era_number, account_id_of_delegator, account_id_of_validator)`
     unwithdrawn_delegator_rewards: LookupMap<(u64, AccountId, AccountId), Balance>,
+    /// The map of era number before which all rewards of a delegator have been withdrawn.
+    /// The key in map is `(account_id_of_delegator, account_id_of_validator)`
+    delegator_reward_checkpoints: LookupMap<(AccountId, AccountId), u64>,
+    /// The map of withdrawn delegator rewards in eras after the checkpoint of the delegator.
+    /// The key in map is `(era_number, account_id_of_delegator, account_id_of_validator)`
+    withdrawn_delegator_rewards: LookupMap<(u64, AccountId, AccountId), Balance>,
     /// The map of unbonded stakes in eras.
     unbonded_stakes: LookupMap<AccountId, Vec<UnbondedStakeReference>>,
     /// The validators' profiles data.
@@ -194,6 +251,54 @@ pub struct AppchainAnchor {
     appchain_challenges: LazyOption<LookupArray<AppchainChallenge>>,
     /// The wrapped appchain NFT data
     wrapped_appchain_nfts: LazyOption<WrappedAppchainNFTs>,
+    /// The validators' commission settings
+    validator_commissions: LazyOption<ValidatorCommissions>,
+    /// The delegation policies of validators
+    delegation_policies: LookupMap<AccountId, DelegationPolicy>,
+    /// The settings for slashing offending validators
+    slashing_settings: LazyOption<SlashingSettings>,
+    /// The schedule for calculating the reward of each era
+    reward_schedule: LazyOption<RewardSchedule>,
+    /// The histories of sweeping expired rewards of eras, mapped by era number
+    expired_reward_sweep_histories: LookupMap<u64, ExpiredRewardSweepHistory>,
+    /// The pools of incentive tokens distributed alongside era reward
+    incentive_pools: LazyOption<IncentivePools>,
+    /// The map of era number before which all incentive rewards of an account have been withdrawn.
+    /// The key in map is `(token_contract, account_id_of_delegator, account_id_of_validator)`,
+    /// the delegator is `None` for the rewards of the validator itself.
+    incentive_reward_checkpoints: LookupMap<(AccountId, Option<AccountId>, AccountId), u64>,
+    /// The incentive rewards which are failed to be transferred, mapped by
+    /// (token contract, delegator id, validator id).
+    incentive_reward_credits: LookupMap<(AccountId, Option<AccountId>, AccountId), Balance>,
+    /// The reward settings of stakers, mapped by their account id in NEAR protocol.
+    reward_settings: LookupMap<AccountId, RewardSettings>,
+    /// The set of stakers whose rewards are paid automatically. It is ordered by account id,
+    /// so that it can be walked through by the last visited account while it is changed.
+    auto_claim_accounts: TreeMap<AccountId, ()>,
+    /// The rewards of stakers which failed to be paid to their reward destination,
+    /// and will be paid in the next payment of their rewards.
+    reward_credits: LookupMap<AccountId, Balance>,
+    /// The balance (in yocto NEAR) for registering the receivers of inbound asset transfers
+    /// in token contracts.
+    storage_fund_balance: Balance,
+    /// The resolving statuses of appchain challenges
+    appchain_challenge_statuses: LazyOption<AppchainChallengeStatuses>,
+    /// The codec of the payloads of appchain messages
+    payload_codec: PayloadCodec,
+    /// The settings for the contract calls requested by the appchain
+    contract_call_settings: LazyOption<ContractCallSettings>,
+    /// The range of nonces (inclusive) of the appchain message processing results
+    /// which are recorded by the earlier version and not migrated yet.
+    unmigrated_processing_result_nonces: Option<(u32, u32)>,
+    /// The staking deposits funded by other accounts, which are waiting for
+    /// the acceptance of the beneficiaries.
+    pending_staking_deposits: LookupMap<AccountId, PendingStakingDeposit>,
+    /// The status of slashing the delegators of offenders reported by the appchain,
+    /// which is `None` if there is no slashing in progress.
+    delegator_slashing_status: Option<DelegatorSlashingStatus>,
+    /// The slashed OCT token which failed to be transferred to the treasury account,
+    /// and will be transferred together with the next transfer of slashed token.
+    untransferred_slashed_amount: Balance,
 }
 
 #[near_bindgen]
@@ -241,6 +346,12 @@ impl AppchainAnchor {
             unwithdrawn_delegator_rewards: LookupMap::new(
                 StorageKey::UnwithdrawnDelegatorRewards.into_bytes(),
             ),
+            delegator_reward_checkpoints: LookupMap::new(
+                StorageKey::DelegatorRewardCheckpoints.into_bytes(),
+            ),
+            withdrawn_delegator_rewards: LookupMap::new(
+                StorageKey::WithdrawnDelegatorRewards.into_bytes(),
+            ),
             unbonded_stakes: LookupMap::new(StorageKey::UnbondedStakes.into_bytes()),
             validator_profiles: LazyOption::new(
                 StorageKey::ValidatorProfiles.into_bytes(),
@@ -305,6 +416,51 @@ impl AppchainAnchor {
                 StorageKey::WrappedAppchainNFTs.into_bytes(),
                 Some(&WrappedAppchainNFTs::new()),
             ),
+            validator_commissions: LazyOption::new(
+                StorageKey::ValidatorCommissions.into_bytes(),
+                Some(&ValidatorCommissions::new()),
+            ),
+            delegation_policies: LookupMap::new(StorageKey::DelegationPolicies.into_bytes()),
+            slashing_settings: LazyOption::new(
+                StorageKey::SlashingSettings.into_bytes(),
+                Some(&SlashingSettings::default()),
+            ),
+            reward_schedule: LazyOption::new(
+                StorageKey::RewardSchedule.into_bytes(),
+                Some(&RewardSchedule::Fixed),
+            ),
+            expired_reward_sweep_histories: LookupMap::new(
+                StorageKey::ExpiredRewardSweepHistories.into_bytes(),
+            ),
+            incentive_pools: LazyOption::new(
+                StorageKey::IncentivePools.into_bytes(),
+                Some(&IncentivePools::new()),
+            ),
+            incentive_reward_checkpoints: LookupMap::new(
+                StorageKey::IncentiveRewardCheckpoints.into_bytes(),
+            ),
+            incentive_reward_credits: LookupMap::new(
+                StorageKey::IncentiveRewardCredits.into_bytes(),
+            ),
+            reward_settings: LookupMap::new(StorageKey::RewardSettingsMap.into_bytes()),
+            auto_claim_accounts: TreeMap::new(StorageKey::AutoClaimAccounts.into_bytes()),
+            reward_credits: LookupMap::new(StorageKey::RewardCredits.into_bytes()),
+            storage_fund_balance: 0,
+            appchain_challenge_statuses: LazyOption::new(
+                StorageKey::AppchainChallengeStatuses.into_bytes(),
+                Some(&AppchainChallengeStatuses::new()),
+            ),
+            payload_codec: PayloadCodec::Borsh,
+            contract_call_settings: LazyOption::new(
+                StorageKey::ContractCallSettings.into_bytes(),
+                Some(&ContractCallSettings::default()),
+            ),
+            unmigrated_processing_result_nonces: None,
+            pending_staking_deposits: LookupMap::new(
+                StorageKey::PendingStakingDeposits.into_bytes(),
+            ),
+            delegator_slashing_status: None,
+            untransferred_slashed_amount: 0,
         }
     }
     // Assert that the contract called by the owner.
@@ -493,7 +649,11 @@ impl AppchainAnchor {
             FTDepositMessage::RegisterValidator { .. }
             | FTDepositMessage::IncreaseStake
             | FTDepositMessage::RegisterDelegator { .. }
-            | FTDepositMessage::IncreaseDelegation { .. } => {
+            | FTDepositMessage::IncreaseDelegation { .. }
+            | FTDepositMessage::RegisterValidatorFor { .. }
+            | FTDepositMessage::IncreaseStakeFor { .. }
+            | FTDepositMessage::RegisterDelegatorFor { .. }
+            | FTDepositMessage::IncreaseDelegationFor { .. } => {
                 assert!(
                     predecessor_account_id.eq(&self.oct_token.get().unwrap().contract_account),
                     "Received invalid deposit '{}' in contract '{}' from '{}'. Return deposit.",
@@ -510,6 +670,16 @@ impl AppchainAnchor {
                     amount,
                     deposit_message,
                 ),
+            FTDepositMessage::FundIncentivePool {
+                eras,
+                per_era_amount,
+            } => self.internal_fund_incentive_pool(
+                predecessor_account_id,
+                sender_id,
+                amount,
+                eras.0,
+                per_era_amount.0,
+            ),
         }
     }
 }
@@ -607,6 +777,37 @@ pub fn get_random_in_near(buf: &mut [u8]) -> Result<(), Error> {
     Ok(())
 }
 
+#[allow(clippy::all)]
+mod u256 {
+    uint::construct_uint! {
+        /// 256-bit unsigned integer, for calculating rewards at full precision.
+        pub struct U256(4);
+    }
+}
+use u256::U256;
+
+/// Calculate `a * b / c` at full precision, rounding down.
+pub fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    (U256::from(a) * U256::from(b) / U256::from(c)).as_u128()
+}
+
+/// Calculate `a * (b / c) ^ exponent` by exponentiation by squaring on the ratio `b / c`,
+/// which is kept as a fixed-point value scaled by `RATIO_SCALE`. Each step rounds down.
+pub fn mul_pow_of_ratio(a: u128, b: u128, c: u128, mut exponent: u64) -> u128 {
+    let mut base = mul_div(RATIO_SCALE, b, c);
+    let mut result = RATIO_SCALE;
+    while exponent > 0 && result > 0 {
+        if exponent & 1 == 1 {
+            result = mul_div(result, base, RATIO_SCALE);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = mul_div(base, base, RATIO_SCALE);
+        }
+    }
+    mul_div(a, result, RATIO_SCALE)
+}
+
 impl IndexedAndClearable for AppchainNotificationHistory {
     //
     fn set_index(&mut self, index: &u64) {
@@ -5,6 +5,12 @@ pub trait IndexedAndClearable {
     fn set_index(&mut self, index: &u64);
     ///
     fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult;
+    /// Load the records which this record is based on, when it is read from the lookup array.
+    fn load_base_records(&mut self, _lookup_array: &LookupArray<Self>)
+    where
+        Self: Sized + BorshDeserialize + BorshSerialize,
+    {
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -39,7 +45,10 @@ where
     }
     ///
     pub fn get(&self, index: &u64) -> Option<T> {
-        self.lookup_map.get(index)
+        self.lookup_map.get(index).map(|mut record| {
+            record.load_base_records(self);
+            record
+        })
     }
     ///
     pub fn get_slice_of(&self, start_index: &u64, quantity: Option<u64>) -> Vec<T> {
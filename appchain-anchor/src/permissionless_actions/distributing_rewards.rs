@@ -1,4 +1,4 @@
-use super::{AppchainMessagesProcessingContext, ResultOfLoopingValidatorSet};
+use super::AppchainMessagesProcessingContext;
 use crate::*;
 use core::convert::{TryFrom, TryInto};
 
@@ -24,15 +24,32 @@ impl AppchainAnchor {
         unprofitable_validator_ids: &Vec<String>,
     ) -> MultiTxsOperationProcessingResult {
         if !validator_set_histories.contains(&era_number) {
-            return MultiTxsOperationProcessingResult::Error(format!(
-                "Validator set is not existed."
-            ));
+            let message = format!("Validator set of era '{}' is not existed.", era_number);
+            let result = AppchainMessageProcessingResult::Error {
+                nonce: appchain_message_nonce,
+                error: AppchainMessageError::ValidatorSetNotExisted {
+                    era_number: U64::from(era_number),
+                },
+                message: message.clone(),
+            };
+            self.record_appchain_message_processing_result(&result);
+            return MultiTxsOperationProcessingResult::Error(message);
         }
         let mut validator_set = validator_set_histories.get(&era_number).unwrap();
         if !validator_set.processing_status().can_distribute_reward() {
-            return MultiTxsOperationProcessingResult::Error(format!(
-                "Validator set is not ready for distributing reward."
-            ));
+            let message = format!(
+                "Validator set of era '{}' is not ready for distributing reward.",
+                era_number
+            );
+            let result = AppchainMessageProcessingResult::Error {
+                nonce: appchain_message_nonce,
+                error: AppchainMessageError::ValidatorSetNotReadyForDistributingReward {
+                    era_number: U64::from(era_number),
+                },
+                message: message.clone(),
+            };
+            self.record_appchain_message_processing_result(&result);
+            return MultiTxsOperationProcessingResult::Error(message);
         }
         let mut unprofitable_validator_ids_in_near = Vec::<AccountId>::new();
         let validator_profiles = self.validator_profiles.get().unwrap();
@@ -50,22 +67,96 @@ impl AppchainAnchor {
                             unprofitable_validator_ids_in_near.push(validator_profile.validator_id);
                         }
                     } else {
-                        return MultiTxsOperationProcessingResult::Error(format!(
+                        let message = format!(
                             "Validator id in appchain '{}' is not a valid validator in era '{}'.",
                             id_in_appchain, era_number
-                        ));
+                        );
+                        let result = AppchainMessageProcessingResult::Error {
+                            nonce: appchain_message_nonce,
+                            error: AppchainMessageError::InvalidUnprofitableValidatorId {
+                                era_number: U64::from(era_number),
+                                validator_id_in_appchain: id_in_appchain.clone(),
+                            },
+                            message: message.clone(),
+                        };
+                        self.record_appchain_message_processing_result(&result);
+                        return MultiTxsOperationProcessingResult::Error(message);
                     }
                 }
                 None => {
-                    return MultiTxsOperationProcessingResult::Error(format!(
-                        "Invalid validator id in appchain: '{}'",
-                        id_in_appchain
-                    ));
+                    let message = format!("Invalid validator id in appchain: '{}'", id_in_appchain);
+                    let result = AppchainMessageProcessingResult::Error {
+                        nonce: appchain_message_nonce,
+                        error: AppchainMessageError::InvalidUnprofitableValidatorId {
+                            era_number: U64::from(era_number),
+                            validator_id_in_appchain: id_in_appchain.clone(),
+                        },
+                        message: message.clone(),
+                    };
+                    self.record_appchain_message_processing_result(&result);
+                    return MultiTxsOperationProcessingResult::Error(message);
                 }
             }
         }
         validator_set.set_unprofitable_validator_ids(unprofitable_validator_ids_in_near);
         validator_set.calculate_valid_total_stake();
+        self.internal_allocate_incentive_rewards_of(&mut validator_set);
+        let era_reward = self.calculate_era_reward_of(&validator_set);
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let mut treasury_reward = match protocol_settings.treasury_account {
+            Some(_) => era_reward * u128::from(protocol_settings.treasury_reward_percent) / 100,
+            None => 0,
+        };
+        let validators_reward = era_reward - treasury_reward;
+        // Calculate the share of unprofitable validators by the policy in protocol settings.
+        let unprofitable_validators = validator_set.get_active_unprofitable_validators();
+        let unprofitable_total_stake: Balance =
+            unprofitable_validators.iter().map(|v| v.total_stake).sum();
+        let unprofitable_share = match protocol_settings.unprofitable_validator_reward_policy {
+            UnprofitableValidatorRewardPolicy::Redistribute => 0,
+            _ => match unprofitable_total_stake > 0 {
+                true => mul_div(
+                    validators_reward,
+                    unprofitable_total_stake,
+                    validator_set.valid_total_stake() + unprofitable_total_stake,
+                ),
+                false => 0,
+            },
+        };
+        let (unprofitable_validators_reward, withheld_reward) = match protocol_settings
+            .unprofitable_validator_reward_policy
+        {
+            UnprofitableValidatorRewardPolicy::Delegators if unprofitable_share > 0 => {
+                let delegators_reward: Balance = unprofitable_validators
+                    .iter()
+                    .filter(|v| v.total_stake > 0)
+                    .map(|v| {
+                        let reward =
+                            mul_div(unprofitable_share, v.total_stake, unprofitable_total_stake);
+                        reward - mul_div(reward, v.deposit_amount, v.total_stake)
+                    })
+                    .sum();
+                (unprofitable_share, unprofitable_share - delegators_reward)
+            }
+            _ => (0, unprofitable_share),
+        };
+        if protocol_settings.unprofitable_validator_reward_policy
+            == UnprofitableValidatorRewardPolicy::Treasury
+            && protocol_settings.treasury_account.is_some()
+        {
+            treasury_reward += withheld_reward;
+        }
+        validator_set.set_reward_shares(
+            validators_reward - unprofitable_share,
+            unprofitable_validators_reward,
+            withheld_reward,
+        );
+        validator_set.add_total_reward(validators_reward - withheld_reward);
+        validator_set.set_era_reward(
+            era_reward,
+            protocol_settings.treasury_account.clone(),
+            treasury_reward,
+        );
         validator_set.set_processing_status(ValidatorSetProcessingStatus::DistributingReward {
             appchain_message_nonce,
             distributing_validator_index: U64::from(0),
@@ -73,12 +164,24 @@ impl AppchainAnchor {
         });
         validator_set_histories.insert(&era_number, &validator_set);
         processing_context.set_distributing_reward_era_number(era_number);
-        // Mint `total_reward` in the contract of wrapped appchain token.
-        let appchain_settings = self.appchain_settings.get().unwrap();
+        // Mint the treasury share of `era_reward` to the treasury account.
+        if treasury_reward > 0 {
+            let result = self.internal_mint_wrapped_appchain_token(
+                None,
+                &protocol_settings.treasury_account.unwrap(),
+                &U128::from(treasury_reward),
+                appchain_message_nonce,
+                processing_context,
+            );
+            if !result.is_ok() {
+                return result;
+            }
+        }
+        // Mint the reward of validators and delegators in the contract of wrapped appchain token.
         let mut result = self.internal_mint_wrapped_appchain_token(
             None,
             &env::current_account_id(),
-            &appchain_settings.era_reward,
+            &U128::from(validators_reward - withheld_reward),
             appchain_message_nonce,
             processing_context,
         );
@@ -110,49 +213,45 @@ impl AppchainAnchor {
             ValidatorSetProcessingStatus::DistributingReward {
                 appchain_message_nonce,
                 distributing_validator_index,
-                distributing_delegator_index,
+                ..
             } => {
-                let validator_commission_percent = u128::from(
-                    self.protocol_settings
-                        .get()
-                        .unwrap()
-                        .validator_commission_percent,
-                );
                 let mut validator_index = distributing_validator_index.0;
-                let mut delegator_index = distributing_delegator_index.0;
-                let era_reward = self.appchain_settings.get().unwrap().era_reward;
+                let era_reward = validator_set.profitable_validators_reward();
+                let unprofitable_total_stake: Balance =
+                    match validator_set.unprofitable_validators_reward() > 0 {
+                        true => validator_set
+                            .get_active_unprofitable_validators()
+                            .iter()
+                            .map(|v| v.total_stake)
+                            .sum(),
+                        false => 0,
+                    };
                 while processing_context.used_gas_of_current_function_call()
                     < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
                 {
-                    match self.distribute_reward_in_validator_set(
+                    if validator_index >= validator_set.validator_count() {
+                        validator_set.set_processing_status(
+                            ValidatorSetProcessingStatus::CheckingForAutoUnbondingValidator {
+                                unprofitable_validator_index: U64::from(0),
+                            },
+                        );
+                        validator_set_histories.insert(&era_number, &validator_set);
+                        return MultiTxsOperationProcessingResult::NeedMoreGas;
+                    }
+                    self.distribute_reward_to_validator(
                         appchain_message_nonce,
                         &mut validator_set,
                         validator_index,
-                        delegator_index,
-                        era_reward.0,
-                        validator_commission_percent,
-                    ) {
-                        ResultOfLoopingValidatorSet::NoMoreDelegator => {
-                            validator_index += 1;
-                            delegator_index = 0;
-                        }
-                        ResultOfLoopingValidatorSet::NoMoreValidator => {
-                            validator_set.set_processing_status(
-                                ValidatorSetProcessingStatus::CheckingForAutoUnbondingValidator {
-                                    unprofitable_validator_index: U64::from(0),
-                                },
-                            );
-                            validator_set_histories.insert(&era_number, &validator_set);
-                            return MultiTxsOperationProcessingResult::NeedMoreGas;
-                        }
-                        ResultOfLoopingValidatorSet::NeedToContinue => delegator_index += 1,
-                    }
+                        era_reward,
+                        unprofitable_total_stake,
+                    );
+                    validator_index += 1;
                 }
                 validator_set.set_processing_status(
                     ValidatorSetProcessingStatus::DistributingReward {
                         appchain_message_nonce,
                         distributing_validator_index: U64::from(validator_index),
-                        distributing_delegator_index: U64::from(delegator_index),
+                        distributing_delegator_index: U64::from(0),
                     },
                 );
                 validator_set_histories.insert(&era_number, &validator_set);
@@ -170,8 +269,11 @@ impl AppchainAnchor {
                     if unprofitable_validator_index.0
                         >= unprofitable_validators.len().try_into().unwrap()
                     {
-                        validator_set
-                            .set_processing_status(ValidatorSetProcessingStatus::Completed);
+                        validator_set.set_processing_status(
+                            ValidatorSetProcessingStatus::AutoClaimingRewards {
+                                last_claimed_account_id: None,
+                            },
+                        );
                         validator_set_histories.insert(&era_number, &validator_set);
                         return MultiTxsOperationProcessingResult::NeedMoreGas;
                     }
@@ -225,6 +327,60 @@ impl AppchainAnchor {
                 validator_set_histories.insert(&era_number, &validator_set);
                 MultiTxsOperationProcessingResult::NeedMoreGas
             }
+            ValidatorSetProcessingStatus::AutoClaimingRewards {
+                mut last_claimed_account_id,
+            } => {
+                // Auto-claiming is skipped if the rewards can not be withdrawn for now,
+                // the rewards will stay withdrawable in the contract.
+                let auto_claim_is_available = !self.asset_transfer_is_paused
+                    && !self.rewards_withdrawal_is_paused
+                    && self
+                        .wrapped_appchain_token
+                        .get()
+                        .unwrap()
+                        .contract_account
+                        .is_some();
+                while processing_context.used_gas_of_current_function_call()
+                    < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
+                {
+                    // The accounts are walked through in the order of account id, so that
+                    // the changes of the accounts in the meantime do not make any account
+                    // skipped or claimed twice.
+                    let next_account_id = match &last_claimed_account_id {
+                        Some(account_id) => self.auto_claim_accounts.higher(account_id),
+                        None => self.auto_claim_accounts.min(),
+                    };
+                    if !auto_claim_is_available || next_account_id.is_none() {
+                        validator_set
+                            .set_processing_status(ValidatorSetProcessingStatus::Completed);
+                        validator_set_histories.insert(&era_number, &validator_set);
+                        return MultiTxsOperationProcessingResult::NeedMoreGas;
+                    }
+                    let account_id = next_account_id.unwrap();
+                    let mut reward: Balance = 0;
+                    if validator_set.contains_validator(&account_id) {
+                        reward += self.internal_collect_validator_rewards(&account_id);
+                    }
+                    for validator_id in validator_set.get_validator_ids_of(&account_id) {
+                        reward +=
+                            self.internal_collect_delegator_rewards(&account_id, &validator_id);
+                    }
+                    if self.internal_pay_reward(&account_id, reward) {
+                        processing_context
+                            .add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER_CALL));
+                        processing_context
+                            .add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION));
+                    }
+                    last_claimed_account_id = Some(account_id);
+                }
+                validator_set.set_processing_status(
+                    ValidatorSetProcessingStatus::AutoClaimingRewards {
+                        last_claimed_account_id,
+                    },
+                );
+                validator_set_histories.insert(&era_number, &validator_set);
+                MultiTxsOperationProcessingResult::NeedMoreGas
+            }
             ValidatorSetProcessingStatus::Completed => {
                 self.record_appchain_message_processing_result(
                     &AppchainMessageProcessingResult::Ok {
@@ -240,77 +396,185 @@ impl AppchainAnchor {
             }
         }
     }
-    //
-    fn distribute_reward_in_validator_set(
+    /// Calculate the reward of the era of the given validator set by the reward schedule.
+    fn calculate_era_reward_of(&self, validator_set: &ValidatorSetOfEra) -> Balance {
+        let base_era_reward = self.appchain_settings.get().unwrap().era_reward.0;
+        let era_number = validator_set.era_number();
+        match self.reward_schedule.get().unwrap() {
+            RewardSchedule::Fixed => base_era_reward,
+            RewardSchedule::StepTable { steps } => steps
+                .iter()
+                .rev()
+                .find(|step| step.start_era_number.0 <= era_number)
+                .map_or(base_era_reward, |step| step.era_reward.0),
+            RewardSchedule::Decaying {
+                decay_era_count,
+                decay_percent,
+            } => mul_pow_of_ratio(
+                base_era_reward,
+                u128::from(100 - decay_percent),
+                100,
+                era_number / decay_era_count.0,
+            ),
+            RewardSchedule::StakingRatioCurve {
+                minimum_annual_inflation,
+                maximum_annual_inflation,
+                ideal_staking_ratio,
+                era_count_per_year,
+            } => {
+                let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+                // The staking ratio and the inflation are in basis points.
+                // Both market values are multiplied by the decimals value of the other token,
+                // so that the ratio is calculated at full precision of the token amounts.
+                let market_value_of_stake = U256::from(validator_set.total_stake())
+                    * U256::from(self.oct_token.get().unwrap().price_in_usd.0)
+                    * U256::from(u128::pow(
+                        10,
+                        u32::from(wrapped_appchain_token.metadata.decimals),
+                    ));
+                let market_value_of_wrapped_appchain_token =
+                    U256::from(wrapped_appchain_token.total_supply.0)
+                        * U256::from(wrapped_appchain_token.price_in_usd.0)
+                        * U256::from(OCT_DECIMALS_VALUE);
+                let staking_ratio = match market_value_of_wrapped_appchain_token.is_zero() {
+                    true => 0,
+                    false => (market_value_of_stake * U256::from(10000)
+                        / market_value_of_wrapped_appchain_token)
+                        .min(U256::from(10000))
+                        .as_u128(),
+                };
+                let minimum_inflation = u128::from(minimum_annual_inflation);
+                let maximum_inflation = u128::from(maximum_annual_inflation);
+                let ideal_staking_ratio = u128::from(ideal_staking_ratio) * 100;
+                let annual_inflation = match staking_ratio <= ideal_staking_ratio {
+                    true => {
+                        minimum_inflation
+                            + mul_div(
+                                maximum_inflation - minimum_inflation,
+                                staking_ratio,
+                                ideal_staking_ratio,
+                            )
+                    }
+                    false => {
+                        maximum_inflation
+                            - mul_div(
+                                maximum_inflation - minimum_inflation,
+                                staking_ratio - ideal_staking_ratio,
+                                10000 - ideal_staking_ratio,
+                            )
+                    }
+                };
+                mul_div(
+                    wrapped_appchain_token.total_supply.0,
+                    annual_inflation,
+                    10000,
+                ) / u128::from(era_count_per_year.0)
+            }
+        }
+    }
+    /// Distribute the reward of a validator in the given validator set.
+    ///
+    /// The reward of delegators is only added to the delegation reward pool of the validator,
+    /// each delegator's reward is calculated on demand from the reward per share of the pool.
+    /// The rewards of the delegators are booked as distributed at the reward per share,
+    /// so the rounding remainder of the pool is left as the reward dust of the era.
+    fn distribute_reward_to_validator(
         &mut self,
         appchain_message_nonce: u32,
         validator_set: &mut ValidatorSetOfEra,
         validator_index: u64,
-        delegator_index: u64,
         era_reward: Balance,
-        validator_commission_percent: u128,
-    ) -> ResultOfLoopingValidatorSet {
-        if validator_index >= validator_set.validator_count() {
-            return ResultOfLoopingValidatorSet::NoMoreValidator;
-        }
+        unprofitable_total_stake: Balance,
+    ) {
         let validator = validator_set
             .get_validator_by_index(&validator_index)
             .unwrap();
-        if validator_set
+        if validator_set.is_jailed(&validator.validator_id)
+            || validator_set.is_standby(&validator.validator_id)
+            || validator.total_stake == 0
+        {
+            return;
+        }
+        let is_unprofitable = validator_set
             .unprofitable_validator_ids()
-            .contains(&validator.validator_id)
+            .contains(&validator.validator_id);
+        if (is_unprofitable && unprofitable_total_stake == 0)
+            || (!is_unprofitable && validator_set.valid_total_stake() == 0)
         {
-            return ResultOfLoopingValidatorSet::NoMoreDelegator;
+            return;
         }
-        let total_reward_of_validator = era_reward * (validator.total_stake / OCT_DECIMALS_VALUE)
-            / (validator_set.valid_total_stake() / OCT_DECIMALS_VALUE);
-        let validator_commission_reward =
-            total_reward_of_validator * validator_commission_percent / 100;
         let mut reward_distribution_records = self.reward_distribution_records.get().unwrap();
-        if delegator_index >= validator_set.get_delegator_count_of(&validator.validator_id) {
-            let validator_reward = validator_commission_reward
-                + (total_reward_of_validator - validator_commission_reward)
-                    * (validator.deposit_amount / OCT_DECIMALS_VALUE)
-                    / (validator.total_stake / OCT_DECIMALS_VALUE);
-            self.add_reward_for_validator(validator_set, &validator.validator_id, validator_reward);
-            reward_distribution_records.insert(
-                appchain_message_nonce,
-                validator_set.era_number(),
-                &None,
-                &validator.validator_id,
-            );
-            self.reward_distribution_records
-                .set(&reward_distribution_records);
-            return ResultOfLoopingValidatorSet::NoMoreDelegator;
-        }
-        let delegator = validator_set
-            .get_delegator_by_index(&delegator_index, &validator.validator_id)
-            .unwrap();
-        if !reward_distribution_records.contains_record(
+        if reward_distribution_records.contains_record(
             appchain_message_nonce,
             validator_set.era_number(),
-            &Some(delegator.delegator_id.clone()),
-            &delegator.validator_id,
+            &None,
+            &validator.validator_id,
         ) {
-            let delegator_reward = (total_reward_of_validator - validator_commission_reward)
-                * (delegator.deposit_amount / OCT_DECIMALS_VALUE)
-                / (validator.total_stake / OCT_DECIMALS_VALUE);
-            self.add_reward_for_delegator(
-                validator_set,
-                &delegator.delegator_id,
-                &delegator.validator_id,
-                delegator_reward,
+            return;
+        }
+        if is_unprofitable {
+            // Only the delegators share the reward of an unprofitable validator,
+            // the share of the validator's own deposit has been withheld.
+            let delegation_reward = mul_div(
+                validator_set.unprofitable_validators_reward(),
+                validator.total_stake,
+                unprofitable_total_stake,
             );
-            reward_distribution_records.insert(
-                appchain_message_nonce,
-                validator_set.era_number(),
-                &Some(delegator.delegator_id),
-                &delegator.validator_id,
+            validator_set.add_delegation_reward(&validator.validator_id, delegation_reward);
+        } else {
+            // The commission of validators is snapshotted when the validator set is generated.
+            // The validator sets generated by earlier versions take the default commission.
+            let validator_commission_percent = u128::from(
+                validator_set
+                    .get_validator_commission_percent_of(&validator.validator_id)
+                    .unwrap_or(
+                        self.protocol_settings
+                            .get()
+                            .unwrap()
+                            .validator_commission_percent,
+                    ),
+            );
+            let total_reward_of_validator = mul_div(
+                era_reward,
+                validator.total_stake,
+                validator_set.valid_total_stake(),
+            );
+            let validator_commission_reward =
+                total_reward_of_validator * validator_commission_percent / 100;
+            validator_set.add_delegation_reward(
+                &validator.validator_id,
+                total_reward_of_validator - validator_commission_reward,
+            );
+            let validator_delegation_reward = mul_div(
+                validator.deposit_amount,
+                validator_set
+                    .get_delegation_reward_per_share_of(&validator.validator_id)
+                    .unwrap(),
+                RATIO_SCALE,
+            );
+            self.add_reward_for_validator(
+                validator_set,
+                &validator.validator_id,
+                validator_commission_reward + validator_delegation_reward,
             );
-            self.reward_distribution_records
-                .set(&reward_distribution_records);
         }
-        return ResultOfLoopingValidatorSet::NeedToContinue;
+        // The rewards of all delegators of the validator, each of which is rounded down
+        // from the same reward per share, can not exceed this amount.
+        validator_set.add_distributed_reward(mul_div(
+            validator.total_stake - validator.deposit_amount,
+            validator_set
+                .get_delegation_reward_per_share_of(&validator.validator_id)
+                .unwrap(),
+            RATIO_SCALE,
+        ));
+        reward_distribution_records.insert(
+            appchain_message_nonce,
+            validator_set.era_number(),
+            &None,
+            &validator.validator_id,
+        );
+        self.reward_distribution_records
+            .set(&reward_distribution_records);
     }
     //
     fn add_reward_for_validator(
@@ -324,6 +588,7 @@ impl AppchainAnchor {
             None => amount,
         };
         validator_set.set_validator_reward(validator_id, validator_reward);
+        validator_set.add_distributed_reward(amount);
         let unwithdrawn_validator_reward = match self
             .unwithdrawn_validator_rewards
             .get(&(validator_set.era_number(), validator_id.clone()))
@@ -336,35 +601,4 @@ impl AppchainAnchor {
             &unwithdrawn_validator_reward,
         );
     }
-    //
-    fn add_reward_for_delegator(
-        &mut self,
-        validator_set: &mut ValidatorSetOfEra,
-        delegator_id: &AccountId,
-        validator_id: &AccountId,
-        amount: u128,
-    ) {
-        let delegator_reward =
-            match validator_set.get_delegator_rewards_of(&delegator_id, &validator_id) {
-                Some(reward) => reward + amount,
-                None => amount,
-            };
-        validator_set.set_delegator_reward(&delegator_id, &validator_id, delegator_reward);
-        let unwithdrawn_delegator_reward = match self.unwithdrawn_delegator_rewards.get(&(
-            validator_set.era_number(),
-            delegator_id.clone(),
-            validator_id.clone(),
-        )) {
-            Some(reward) => reward + amount,
-            None => amount,
-        };
-        self.unwithdrawn_delegator_rewards.insert(
-            &(
-                validator_set.era_number(),
-                delegator_id.clone(),
-                validator_id.clone(),
-            ),
-            &unwithdrawn_delegator_reward,
-        );
-    }
 }
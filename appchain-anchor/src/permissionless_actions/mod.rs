@@ -1,4 +1,6 @@
 mod distributing_rewards;
+mod slashing_offenders;
+mod sweeping_expired_rewards;
 mod switching_era;
 
 use crate::appchain_messages::Offender;
@@ -43,6 +45,16 @@ pub enum AppchainEvent {
         instance_id: String,
         token_metadata: TokenMetadata,
     },
+    /// The fact that a function call of a contract in NEAR protocol is requested
+    /// by the appchain.
+    ContractCallRequested {
+        sender_id_in_appchain: String,
+        contract_id: AccountId,
+        method_name: String,
+        args: String,
+        deposit: U128,
+        gas: U64,
+    },
 }
 
 pub struct AppchainMessagesProcessingContext {
@@ -231,6 +243,10 @@ impl PermissionlessActions for AppchainAnchor {
     }
     //
     fn process_appchain_messages(&mut self) -> MultiTxsOperationProcessingResult {
+        assert!(
+            self.unmigrated_processing_result_nonces.is_none(),
+            "The appchain message processing results are not migrated yet."
+        );
         let processing_status = self.permissionless_actions_status.get().unwrap();
         let appchain_messages = self.appchain_messages.get().unwrap();
         let mut processing_context = AppchainMessagesProcessingContext::new(processing_status);
@@ -297,18 +313,43 @@ impl PermissionlessActions for AppchainAnchor {
     }
     //
     fn commit_appchain_challenge(&mut self, appchain_challenge: AppchainChallenge) {
-        match &appchain_challenge {
+        let submitter_account = match &appchain_challenge {
             AppchainChallenge::EquivocationChallenge {
-                submitter_account: _,
+                submitter_account,
                 proof,
             } => {
                 assert!(proof.is_valid(), "Invalid equivocation challenge data.");
+                submitter_account
             }
-            AppchainChallenge::ConspiracyMmr { .. } => (),
-        }
+            AppchainChallenge::ConspiracyMmr {
+                submitter_account, ..
+            } => submitter_account,
+        };
+        assert_eq!(
+            submitter_account,
+            &env::predecessor_account_id(),
+            "The submitter of challenge must be the caller."
+        );
         let mut appchain_challenges = self.appchain_challenges.get().unwrap();
         appchain_challenges.append(&mut appchain_challenge.clone());
         self.appchain_challenges.set(&appchain_challenges);
+        //
+        let challenge_index = appchain_challenges.index_range().end_index.0;
+        if let AppchainChallenge::EquivocationChallenge {
+            submitter_account,
+            proof,
+        } = &appchain_challenge
+        {
+            let status = self.internal_resolve_equivocation_challenge(
+                challenge_index,
+                submitter_account,
+                proof,
+            );
+            let mut appchain_challenge_statuses = self.appchain_challenge_statuses.get().unwrap();
+            appchain_challenge_statuses.set_status(&challenge_index, &status);
+            self.appchain_challenge_statuses
+                .set(&appchain_challenge_statuses);
+        }
     }
     //
     fn process_appchain_messages_with_all_proofs(
@@ -371,6 +412,14 @@ impl PermissionlessActions for AppchainAnchor {
             }
         });
     }
+    //
+    fn resend_challenge_bounty(&mut self, challenge_index: U64) {
+        self.internal_resend_challenge_bounty(challenge_index.0);
+    }
+    //
+    fn sweep_expired_rewards(&mut self, era_number: U64) -> MultiTxsOperationProcessingResult {
+        self.internal_sweep_expired_rewards_of_era(era_number.0)
+    }
 }
 
 impl AppchainAnchor {
@@ -392,6 +441,7 @@ impl AppchainAnchor {
                     let message = format!("Asset transfer is now paused.");
                     let result = AppchainMessageProcessingResult::Error {
                         nonce: appchain_message.nonce,
+                        error: AppchainMessageError::AssetTransferPaused,
                         message: message.clone(),
                     };
                     self.record_appchain_message_processing_result(&result);
@@ -402,6 +452,9 @@ impl AppchainAnchor {
                     let message = format!("Invalid contract account: '{}'.", contract_account);
                     let result = AppchainMessageProcessingResult::Error {
                         nonce: appchain_message.nonce,
+                        error: AppchainMessageError::InvalidContractAccount {
+                            contract_account: contract_account.clone(),
+                        },
                         message: message.clone(),
                     };
                     self.record_appchain_message_processing_result(&result);
@@ -425,6 +478,7 @@ impl AppchainAnchor {
                     let message = format!("Asset transfer is now paused.");
                     let result = AppchainMessageProcessingResult::Error {
                         nonce: appchain_message.nonce,
+                        error: AppchainMessageError::AssetTransferPaused,
                         message: message.clone(),
                     };
                     self.record_appchain_message_processing_result(&result);
@@ -439,6 +493,7 @@ impl AppchainAnchor {
                     let message = format!("Too much wrapped appchain token to mint.");
                     let result = AppchainMessageProcessingResult::Error {
                         nonce: appchain_message.nonce,
+                        error: AppchainMessageError::TooMuchWrappedAppchainTokenToMint,
                         message: message.clone(),
                     };
                     self.record_appchain_message_processing_result(&result);
@@ -465,6 +520,9 @@ impl AppchainAnchor {
                         let message = format!("Switching era number '{}' is too old.", era_number);
                         let result = AppchainMessageProcessingResult::Error {
                             nonce: appchain_message.nonce,
+                            error: AppchainMessageError::SwitchingEraTooOld {
+                                era_number: *era_number,
+                            },
                             message: message.clone(),
                         };
                         self.record_appchain_message_processing_result(&result);
@@ -480,7 +538,7 @@ impl AppchainAnchor {
             AppchainEvent::EraRewardConcluded {
                 era_number,
                 unprofitable_validator_ids,
-                offenders: _,
+                offenders,
             } => {
                 if let Some(era_number) = processing_context.distributing_reward_era_number() {
                     self.complete_distributing_reward_of_era(
@@ -489,6 +547,11 @@ impl AppchainAnchor {
                         era_number,
                     )
                 } else {
+                    // The offenders are slashed regardless of the result of distributing reward.
+                    let result = self.internal_slash_offenders(processing_context, offenders);
+                    if !result.is_ok() {
+                        return result;
+                    }
                     self.internal_start_distributing_reward_of_era(
                         processing_context,
                         validator_set_histories,
@@ -509,6 +572,7 @@ impl AppchainAnchor {
                     let message = format!("Asset transfer is now paused.");
                     let result = AppchainMessageProcessingResult::Error {
                         nonce: appchain_message.nonce,
+                        error: AppchainMessageError::AssetTransferPaused,
                         message: message.clone(),
                     };
                     self.record_appchain_message_processing_result(&result);
@@ -524,6 +588,23 @@ impl AppchainAnchor {
                     token_metadata,
                 )
             }
+            AppchainEvent::ContractCallRequested {
+                sender_id_in_appchain,
+                contract_id,
+                method_name,
+                args,
+                deposit,
+                gas,
+            } => self.internal_execute_contract_call(
+                processing_context,
+                appchain_message.nonce,
+                sender_id_in_appchain,
+                contract_id,
+                method_name,
+                args,
+                deposit,
+                gas,
+            ),
         }
     }
     ///
@@ -563,7 +644,8 @@ impl AppchainMessageProcessingResult {
     pub fn nonce(&self) -> u32 {
         match self {
             AppchainMessageProcessingResult::Ok { nonce, .. }
-            | AppchainMessageProcessingResult::Error { nonce, .. } => *nonce,
+            | AppchainMessageProcessingResult::Error { nonce, .. }
+            | AppchainMessageProcessingResult::Skipped { nonce, .. } => *nonce,
         }
     }
 }
@@ -0,0 +1,501 @@
+use super::AppchainMessagesProcessingContext;
+use crate::appchain_challenge::{
+    equivocation_challenge::EquivocationProof, AppchainChallenge, AppchainChallengeStatus,
+    BountyTransferStatus,
+};
+use crate::appchain_messages::Offender;
+use crate::validator_set::Delegator;
+use crate::*;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use std::cmp::min;
+
+const OFFENCE_KIND_OF_EQUIVOCATION: &str = "grandpa-equivocation";
+
+pub trait SlashedTokenTransferResolver {
+    /// Resolver for transferring slashed OCT token to the treasury account
+    fn resolve_slashed_oct_token_transfer(&mut self, treasury_account: AccountId, amount: U128);
+    /// Resolver for transferring the bounty of an appchain challenge to its submitter
+    fn resolve_challenge_bounty_transfer(&mut self, challenge_index: U64);
+}
+
+impl AppchainAnchor {
+    /// Slash the deposit of offending validators (and their delegators, if configured)
+    /// in the next validator set.
+    ///
+    /// The offenders which are not validators in the next validator set, or whose
+    /// offence kind is not configured in slashing settings, are ignored. The delegators
+    /// of the offenders are slashed one by one, which may cost more than one transaction,
+    /// and the delegations of the offenders can not be changed until it is completed.
+    pub fn internal_slash_offenders(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        offenders: &Vec<Offender>,
+    ) -> MultiTxsOperationProcessingResult {
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let mut status = match self.delegator_slashing_status.clone() {
+            Some(status) => status,
+            None => self.internal_slash_offending_validators(&mut next_validator_set, offenders),
+        };
+        let mut validator_index = status.slashing_validator_index.0;
+        let mut delegator_index = status.slashing_delegator_index.0;
+        let mut total_slashed_amount = status.total_slashed_amount.0;
+        while processing_context.used_gas_of_current_function_call()
+            < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
+        {
+            if validator_index >= status.slashing_validators.len().try_into().unwrap() {
+                self.delegator_slashing_status = None;
+                if total_slashed_amount > 0 {
+                    self.next_validator_set.set(&next_validator_set);
+                    self.sync_state_to_registry();
+                    if self.internal_transfer_slashed_oct_token_to_treasury(total_slashed_amount) {
+                        processing_context.add_prepaid_gas(
+                            Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER + T_GAS_FOR_RESOLVER_FUNCTION),
+                        );
+                    }
+                }
+                return MultiTxsOperationProcessingResult::Ok;
+            }
+            let (validator_id, slash_percent) =
+                &status.slashing_validators[usize::try_from(validator_index).unwrap()];
+            match next_validator_set.get_delegator_by_index(&delegator_index, validator_id) {
+                Some(delegator) => {
+                    total_slashed_amount += self.internal_slash_delegator(
+                        &mut next_validator_set,
+                        &delegator,
+                        *slash_percent,
+                    );
+                    delegator_index += 1;
+                }
+                None => {
+                    validator_index += 1;
+                    delegator_index = 0;
+                }
+            }
+        }
+        status.slashing_validator_index = U64::from(validator_index);
+        status.slashing_delegator_index = U64::from(delegator_index);
+        status.total_slashed_amount = U128::from(total_slashed_amount);
+        self.delegator_slashing_status = Some(status);
+        self.next_validator_set.set(&next_validator_set);
+        MultiTxsOperationProcessingResult::NeedMoreGas
+    }
+    // Slash and jail the offending validators in the next validator set, and return
+    // the status for slashing the delegators of them.
+    fn internal_slash_offending_validators(
+        &mut self,
+        next_validator_set: &mut NextValidatorSet,
+        offenders: &Vec<Offender>,
+    ) -> DelegatorSlashingStatus {
+        let slashing_settings = self.slashing_settings.get().unwrap();
+        let mut total_slashed_amount: u128 = 0;
+        let mut slashing_validators = Vec::<(AccountId, u16)>::new();
+        for offender in offenders {
+            let slash_percent = match slashing_settings
+                .slash_percents_of_offence_kinds
+                .get(&offender.kind)
+            {
+                Some(slash_percent) => min(
+                    100,
+                    u64::from(*slash_percent) * u64::from(offender.offences),
+                ) as u16,
+                None => continue,
+            };
+            if slash_percent == 0 {
+                continue;
+            }
+            let validator_id = match self.get_validator_id_by_id_in_appchain(&offender.who) {
+                Some(validator_id) => validator_id,
+                None => continue,
+            };
+            let slashed_amount = self.internal_slash_validator(
+                next_validator_set,
+                &validator_id,
+                &offender.kind,
+                offender.offences,
+                slash_percent,
+            );
+            if slashed_amount > 0 && slashing_settings.offender_jail_era_count.0 > 0 {
+                self.internal_jail_validator(
+                    next_validator_set,
+                    &validator_id,
+                    JailReason::Offence {
+                        offence_kind: offender.kind.clone(),
+                    },
+                    slashing_settings.offender_jail_era_count.0,
+                );
+            }
+            if slashed_amount > 0 && slashing_settings.slash_delegators {
+                slashing_validators.push((validator_id, slash_percent));
+            }
+            total_slashed_amount += slashed_amount;
+        }
+        DelegatorSlashingStatus {
+            slashing_validators,
+            slashing_validator_index: U64::from(0),
+            slashing_delegator_index: U64::from(0),
+            total_slashed_amount: U128::from(total_slashed_amount),
+        }
+    }
+    /// Assert that the staking fact does not change the delegations of a validator
+    /// whose delegators are being slashed.
+    pub fn assert_delegators_are_not_being_slashed(&self, staking_fact: &StakingFact) {
+        let status = match &self.delegator_slashing_status {
+            Some(status) => status,
+            None => return,
+        };
+        let validator_ids = match staking_fact {
+            StakingFact::ValidatorUnbonded { validator_id, .. }
+            | StakingFact::ValidatorAutoUnbonded { validator_id, .. }
+            | StakingFact::DelegatorRegistered { validator_id, .. }
+            | StakingFact::DelegationIncreased { validator_id, .. }
+            | StakingFact::DelegationDecreased { validator_id, .. }
+            | StakingFact::DelegatorUnbonded { validator_id, .. }
+            | StakingFact::DelegatorAutoUnbonded { validator_id, .. }
+            | StakingFact::DelegationRebonded { validator_id, .. } => vec![validator_id],
+            StakingFact::DelegationRedelegated {
+                old_validator_id,
+                new_validator_id,
+                ..
+            } => vec![old_validator_id, new_validator_id],
+            _ => return,
+        };
+        for validator_id in validator_ids {
+            assert!(
+                !status
+                    .slashing_validators
+                    .iter()
+                    .any(|(slashing_validator_id, _)| slashing_validator_id.eq(validator_id)),
+                "The delegators of validator '{}' are being slashed.",
+                validator_id
+            );
+        }
+    }
+    /// Apply the equivocation penalty to the validator which is proved to be equivocating,
+    /// and pay the bounty to the submitter of the challenge.
+    pub fn internal_resolve_equivocation_challenge(
+        &mut self,
+        challenge_index: u64,
+        submitter_account: &AccountId,
+        proof: &EquivocationProof,
+    ) -> AppchainChallengeStatus {
+        let mut appchain_challenge_statuses = self.appchain_challenge_statuses.get().unwrap();
+        let equivocation_key = proof.equivocation_key();
+        if let Some(original_index) =
+            appchain_challenge_statuses.get_index_of_equivocation(&equivocation_key)
+        {
+            return AppchainChallengeStatus::Duplicate {
+                original_index: U64::from(original_index),
+            };
+        }
+        appchain_challenge_statuses.set_index_of_equivocation(&equivocation_key, &challenge_index);
+        self.appchain_challenge_statuses
+            .set(&appchain_challenge_statuses);
+        //
+        let identity = hex::encode(&proof.identity().0);
+        let validator_id = match self.get_validator_id_by_id_in_appchain(&identity) {
+            Some(validator_id) => validator_id,
+            None => {
+                return AppchainChallengeStatus::Rejected {
+                    reason: format!("Equivocating voter '0x{}' is not a validator.", identity),
+                }
+            }
+        };
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        if !next_validator_set.contains_validator(&validator_id) {
+            return AppchainChallengeStatus::Rejected {
+                reason: format!(
+                    "Validator '{}' is not in the next validator set.",
+                    validator_id
+                ),
+            };
+        }
+        let slashing_settings = self.slashing_settings.get().unwrap();
+        let penalty = &slashing_settings.equivocation_penalty;
+        let mut slashed_amount: u128 = 0;
+        if penalty.slash_percent > 0 {
+            slashed_amount += self.internal_slash_validator(
+                &mut next_validator_set,
+                &validator_id,
+                &OFFENCE_KIND_OF_EQUIVOCATION.to_string(),
+                1,
+                penalty.slash_percent,
+            );
+            if slashing_settings.slash_delegators {
+                for delegator_id in next_validator_set.get_delegator_ids_of(&validator_id) {
+                    let delegator = next_validator_set
+                        .get_delegator(&delegator_id, &validator_id)
+                        .unwrap();
+                    slashed_amount += self.internal_slash_delegator(
+                        &mut next_validator_set,
+                        &delegator,
+                        penalty.slash_percent,
+                    );
+                }
+            }
+        }
+        if penalty.jail_era_count.0 > 0 {
+            self.internal_jail_validator(
+                &mut next_validator_set,
+                &validator_id,
+                JailReason::Equivocation {
+                    challenge_index: U64::from(challenge_index),
+                },
+                penalty.jail_era_count.0,
+            );
+        }
+        if penalty.force_unbond {
+            let protocol_settings = self.protocol_settings.get().unwrap();
+            self.record_unbonding_validator(
+                &protocol_settings,
+                &mut next_validator_set,
+                &validator_id,
+                true,
+            );
+        }
+        self.next_validator_set.set(&next_validator_set);
+        self.sync_state_to_registry();
+        //
+        let bounty = slashed_amount
+            * u128::from(slashing_settings.equivocation_challenger_bounty_percent)
+            / 100;
+        let bounty_transfer_status = match bounty > 0 {
+            true => {
+                self.internal_transfer_challenge_bounty(challenge_index, submitter_account, bounty);
+                BountyTransferStatus::Transferring
+            }
+            false => BountyTransferStatus::Transferred,
+        };
+        if slashed_amount > bounty {
+            self.internal_transfer_slashed_oct_token_to_treasury(slashed_amount - bounty);
+        }
+        AppchainChallengeStatus::Applied {
+            validator_id,
+            slashed_amount: U128::from(slashed_amount),
+            bounty: U128::from(bounty),
+            bounty_transfer_status,
+        }
+    }
+    // Get the account id in NEAR protocol of a validator by its id in appchain.
+    fn get_validator_id_by_id_in_appchain(&self, id_in_appchain: &String) -> Option<AccountId> {
+        let account_id_in_appchain =
+            AccountIdInAppchain::new(Some(id_in_appchain.clone()), &self.appchain_template_type);
+        self.validator_profiles
+            .get()
+            .unwrap()
+            .get_by_id_in_appchain(&account_id_in_appchain.to_string())
+            .map(|validator_profile| validator_profile.validator_id)
+    }
+    // Slash the deposit of a validator in the next validator set,
+    // and return the slashed amount.
+    fn internal_slash_validator(
+        &mut self,
+        next_validator_set: &mut NextValidatorSet,
+        validator_id: &AccountId,
+        offence_kind: &String,
+        offences: u32,
+        slash_percent: u16,
+    ) -> u128 {
+        let validator = match next_validator_set.get_validator(validator_id) {
+            Some(validator) => validator,
+            None => return 0,
+        };
+        let amount = validator.deposit_amount * u128::from(slash_percent) / 100;
+        if amount > 0 {
+            let staking_history = self.record_staking_fact(StakingFact::ValidatorSlashed {
+                validator_id: validator_id.clone(),
+                offence_kind: offence_kind.clone(),
+                offences,
+                slash_percent,
+                amount: U128::from(amount),
+            });
+            next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        }
+        amount
+    }
+    // Slash the deposit of a delegator in the next validator set,
+    // and return the slashed amount.
+    fn internal_slash_delegator(
+        &mut self,
+        next_validator_set: &mut NextValidatorSet,
+        delegator: &Delegator,
+        slash_percent: u16,
+    ) -> u128 {
+        let amount = delegator.deposit_amount * u128::from(slash_percent) / 100;
+        if amount > 0 {
+            let staking_history = self.record_staking_fact(StakingFact::DelegatorSlashed {
+                delegator_id: delegator.delegator_id.clone(),
+                validator_id: delegator.validator_id.clone(),
+                slash_percent,
+                amount: U128::from(amount),
+            });
+            next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        }
+        amount
+    }
+    /// Jail a validator in the next validator set for `era_count` eras,
+    /// starting from the next era.
+    pub fn internal_jail_validator(
+        &self,
+        next_validator_set: &mut NextValidatorSet,
+        validator_id: &AccountId,
+        reason: JailReason,
+        era_count: u64,
+    ) {
+        let start_era_number = self.get_number_of_next_era();
+        next_validator_set.jail_validator(JailedValidator {
+            validator_id: validator_id.clone(),
+            reason,
+            start_era_number: U64::from(start_era_number),
+            release_era_number: U64::from(start_era_number + era_count),
+        });
+    }
+    // Transfer the slashed OCT token (together with the untransferred slashed amount)
+    // to the treasury account, if it is the configured destination of slashed token.
+    // Return whether the transfer is made.
+    //
+    // If the transfer fails, the amount will be added back to the untransferred slashed amount.
+    fn internal_transfer_slashed_oct_token_to_treasury(&mut self, amount: u128) -> bool {
+        let slashing_settings = self.slashing_settings.get().unwrap();
+        let treasury_account = match slashing_settings.slashed_token_destination {
+            SlashedTokenDestination::Treasury(treasury_account) => treasury_account,
+            SlashedTokenDestination::Lock => return false,
+        };
+        let amount = amount + self.untransferred_slashed_amount;
+        self.untransferred_slashed_amount = 0;
+        ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(treasury_account.clone(), U128::from(amount), None)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_slashed_oct_token_transfer(treasury_account, U128::from(amount)),
+            );
+        true
+    }
+    /// Re-send the bounty of an applied appchain challenge to its submitter,
+    /// if the last transfer failed.
+    pub fn internal_resend_challenge_bounty(&mut self, challenge_index: u64) {
+        let appchain_challenges = self.appchain_challenges.get().unwrap();
+        let submitter_account = match appchain_challenges.get(&challenge_index) {
+            Some(AppchainChallenge::EquivocationChallenge {
+                submitter_account, ..
+            }) => submitter_account,
+            _ => panic!(
+                "Invalid index of equivocation challenge: {}",
+                challenge_index
+            ),
+        };
+        let mut appchain_challenge_statuses = self.appchain_challenge_statuses.get().unwrap();
+        match appchain_challenge_statuses.get_status(&challenge_index) {
+            AppchainChallengeStatus::Applied {
+                validator_id,
+                slashed_amount,
+                bounty,
+                bounty_transfer_status,
+            } => {
+                assert!(
+                    bounty_transfer_status.eq(&BountyTransferStatus::Failed),
+                    "The bounty of challenge {} is not failed to be transferred.",
+                    challenge_index
+                );
+                self.internal_transfer_challenge_bounty(
+                    challenge_index,
+                    &submitter_account,
+                    bounty.0,
+                );
+                appchain_challenge_statuses.set_status(
+                    &challenge_index,
+                    &AppchainChallengeStatus::Applied {
+                        validator_id,
+                        slashed_amount,
+                        bounty,
+                        bounty_transfer_status: BountyTransferStatus::Transferring,
+                    },
+                );
+                self.appchain_challenge_statuses
+                    .set(&appchain_challenge_statuses);
+            }
+            _ => panic!("The challenge {} is not applied.", challenge_index),
+        }
+    }
+    //
+    fn internal_transfer_challenge_bounty(
+        &self,
+        challenge_index: u64,
+        submitter_account: &AccountId,
+        bounty: u128,
+    ) {
+        ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(submitter_account.clone(), U128::from(bounty), None)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_challenge_bounty_transfer(U64::from(challenge_index)),
+            );
+    }
+}
+
+#[near_bindgen]
+impl SlashedTokenTransferResolver for AppchainAnchor {
+    //
+    fn resolve_slashed_oct_token_transfer(&mut self, treasury_account: AccountId, amount: U128) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => (),
+            PromiseResult::Failed => {
+                self.untransferred_slashed_amount += amount.0;
+                log!(
+                    "Failed to transfer slashed OCT token to '{}'. Amount: '{}'. It will be transferred in the next transfer.",
+                    &treasury_account,
+                    &amount.0
+                );
+            }
+        }
+    }
+    //
+    fn resolve_challenge_bounty_transfer(&mut self, challenge_index: U64) {
+        assert_self();
+        let mut appchain_challenge_statuses = self.appchain_challenge_statuses.get().unwrap();
+        if let AppchainChallengeStatus::Applied {
+            validator_id,
+            slashed_amount,
+            bounty,
+            ..
+        } = appchain_challenge_statuses.get_status(&challenge_index.0)
+        {
+            let bounty_transfer_status = match env::promise_result(0) {
+                PromiseResult::NotReady => unreachable!(),
+                PromiseResult::Successful(_) => BountyTransferStatus::Transferred,
+                PromiseResult::Failed => {
+                    log!(
+                        "Failed to transfer the bounty of challenge {}. Amount: '{}'.",
+                        challenge_index.0,
+                        bounty.0
+                    );
+                    BountyTransferStatus::Failed
+                }
+            };
+            appchain_challenge_statuses.set_status(
+                &challenge_index.0,
+                &AppchainChallengeStatus::Applied {
+                    validator_id,
+                    slashed_amount,
+                    bounty,
+                    bounty_transfer_status,
+                },
+            );
+            self.appchain_challenge_statuses
+                .set(&appchain_challenge_statuses);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::*;
+use core::convert::{TryFrom, TryInto};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+pub trait ExpiredRewardSweepResolver {
+    /// Resolver for transferring the swept expired rewards to the treasury account
+    fn resolve_expired_reward_transfer(&mut self, era_number: U64);
+}
+
+impl AppchainAnchor {
+    /// Sweep the unwithdrawn rewards of validators and delegators in the given era,
+    /// which can no longer be withdrawn, to the treasury account.
+    pub fn internal_sweep_expired_rewards_of_era(
+        &mut self,
+        era_number: u64,
+    ) -> MultiTxsOperationProcessingResult {
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let end_era = validator_set_histories.index_range().end_index.0;
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let start_era = match end_era > protocol_settings.maximum_era_count_of_unwithdrawn_reward.0
+        {
+            true => end_era - protocol_settings.maximum_era_count_of_unwithdrawn_reward.0 + 1,
+            false => 0,
+        };
+        assert!(
+            era_number < start_era,
+            "The rewards of era {} are not expired.",
+            era_number
+        );
+        let mut validator_set = validator_set_histories
+            .get(&era_number)
+            .expect("The validator set of the given era is not found.");
+        assert!(
+            validator_set
+                .processing_status()
+                .eq(&ValidatorSetProcessingStatus::Completed),
+            "The reward of era {} is not completely distributed.",
+            era_number
+        );
+        let mut history = match self.expired_reward_sweep_histories.get(&era_number) {
+            Some(history) => {
+                if history.is_completed {
+                    // Re-send the swept rewards if the last transfer failed.
+                    assert!(
+                        history
+                            .transfer_status
+                            .eq(&SweptRewardTransferStatus::Failed),
+                        "The expired rewards of era {} have been swept.",
+                        era_number
+                    );
+                    self.transfer_swept_rewards(history);
+                    return MultiTxsOperationProcessingResult::Ok;
+                }
+                history
+            }
+            None => ExpiredRewardSweepHistory {
+                era_number: U64::from(era_number),
+                treasury_account: protocol_settings
+                    .treasury_account
+                    .expect("Treasury account is not set."),
+                validator_reward: U128::from(0),
+                delegator_reward: U128::from(0),
+                sweeping_validator_index: U64::from(0),
+                sweeping_delegator_index: U64::from(0),
+                is_completed: false,
+                transfer_status: SweptRewardTransferStatus::NotTransferred,
+            },
+        };
+        let mut validator_index = history.sweeping_validator_index.0;
+        let mut delegator_index = history.sweeping_delegator_index.0;
+        while env::used_gas() < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+            let validator = match validator_set.get_validator_by_index(&validator_index) {
+                Some(validator) => validator,
+                None => {
+                    history.sweeping_validator_index = U64::from(validator_index);
+                    history.sweeping_delegator_index = U64::from(0);
+                    history.is_completed = true;
+                    self.transfer_swept_rewards(history);
+                    return MultiTxsOperationProcessingResult::Ok;
+                }
+            };
+            let delegator_ids = validator_set.get_delegator_ids_of(&validator.validator_id);
+            let delegator_count: u64 = delegator_ids.len().try_into().unwrap();
+            while delegator_index < delegator_count
+                && env::used_gas() < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
+            {
+                let delegator_id = &delegator_ids[usize::try_from(delegator_index).unwrap()];
+                let reward = self.get_unwithdrawn_delegator_reward_of(
+                    &validator_set,
+                    delegator_id,
+                    &validator.validator_id,
+                );
+                history.delegator_reward = U128::from(history.delegator_reward.0 + reward);
+                let reward_key = (
+                    era_number,
+                    delegator_id.clone(),
+                    validator.validator_id.clone(),
+                );
+                self.unwithdrawn_delegator_rewards.remove(&reward_key);
+                self.withdrawn_delegator_rewards.remove(&reward_key);
+                delegator_index += 1;
+            }
+            if delegator_index < delegator_count {
+                break;
+            }
+            if let Some(reward) = self
+                .unwithdrawn_validator_rewards
+                .remove(&(era_number, validator.validator_id.clone()))
+            {
+                history.validator_reward = U128::from(history.validator_reward.0 + reward);
+            }
+            // The rewards of delegators are calculated from the pool,
+            // so they are all swept by removing the pool.
+            validator_set.remove_delegation_reward_pool(&validator.validator_id);
+            validator_index += 1;
+            delegator_index = 0;
+        }
+        history.sweeping_validator_index = U64::from(validator_index);
+        history.sweeping_delegator_index = U64::from(delegator_index);
+        self.expired_reward_sweep_histories
+            .insert(&era_number, &history);
+        MultiTxsOperationProcessingResult::NeedMoreGas
+    }
+    // Transfer the swept rewards in the given history to the treasury account.
+    // The history is stored with the status of the transfer.
+    fn transfer_swept_rewards(&mut self, mut history: ExpiredRewardSweepHistory) {
+        let total_reward = history.validator_reward.0 + history.delegator_reward.0;
+        if total_reward == 0 {
+            history.transfer_status = SweptRewardTransferStatus::Transferred;
+            self.expired_reward_sweep_histories
+                .insert(&history.era_number.0, &history);
+            return;
+        }
+        history.transfer_status = SweptRewardTransferStatus::Transferring;
+        self.expired_reward_sweep_histories
+            .insert(&history.era_number.0, &history);
+        ext_ft_core::ext(
+            self.wrapped_appchain_token
+                .get()
+                .unwrap()
+                .contract_account
+                .unwrap(),
+        )
+        .with_attached_deposit(1)
+        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+        .with_unused_gas_weight(0)
+        .ft_transfer(history.treasury_account, total_reward.into(), None)
+        .then(
+            ext_self::ext(env::current_account_id())
+                .with_attached_deposit(0)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                .with_unused_gas_weight(0)
+                .resolve_expired_reward_transfer(history.era_number),
+        );
+    }
+}
+
+#[near_bindgen]
+impl ExpiredRewardSweepResolver for AppchainAnchor {
+    //
+    fn resolve_expired_reward_transfer(&mut self, era_number: U64) {
+        assert_self();
+        let mut history = self
+            .expired_reward_sweep_histories
+            .get(&era_number.0)
+            .unwrap();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                history.transfer_status = SweptRewardTransferStatus::Transferred;
+            }
+            PromiseResult::Failed => {
+                log!(
+                    "Failed to transfer the expired rewards of era {} to '{}'. Amount: '{}'.",
+                    era_number.0,
+                    &history.treasury_account,
+                    history.validator_reward.0 + history.delegator_reward.0
+                );
+                history.transfer_status = SweptRewardTransferStatus::Failed;
+            }
+        }
+        self.expired_reward_sweep_histories
+            .insert(&era_number.0, &history);
+    }
+}
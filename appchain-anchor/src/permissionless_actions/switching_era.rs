@@ -12,6 +12,14 @@ impl AppchainAnchor {
         era_number: u64,
     ) -> MultiTxsOperationProcessingResult {
         if !validator_set_histories.contains(&era_number) {
+            // Only the changes based on the validator set of last era are stored,
+            // except for the checkpoints which hold a full copy of validator set.
+            let parent_era_number = match era_number % VALIDATOR_SET_CHECKPOINT_INTERVAL > 0
+                && validator_set_histories.contains(&(era_number - 1))
+            {
+                true => Some(era_number - 1),
+                false => None,
+            };
             validator_set_histories.insert(
                 &era_number,
                 &ValidatorSetOfEra::new(
@@ -22,6 +30,7 @@ impl AppchainAnchor {
                         .index_range()
                         .end_index
                         .0,
+                    parent_era_number,
                 ),
             );
         }
@@ -48,8 +57,20 @@ impl AppchainAnchor {
                     );
                     let last_validator_set =
                         validator_set_histories.get(&(era_number - 1)).unwrap();
+                    if validator_set.parent_era_number().is_some() {
+                        validator_set.copy_validator_ids_from(&last_validator_set);
+                        validator_set.set_processing_status(
+                            ValidatorSetProcessingStatus::UnbondingValidator {
+                                unbonding_validator_index: U64::from(0),
+                                unbonding_delegator_index: U64::from(0),
+                            },
+                        );
+                        validator_set_histories.insert(&era_number, &validator_set);
+                        return MultiTxsOperationProcessingResult::NeedMoreGas;
+                    }
                     let mut validator_index = copying_validator_index.0;
                     let mut delegator_index = copying_delegator_index.0;
+                    let mut delegator_ids_of_validator: Option<(u64, Vec<AccountId>)> = None;
                     while processing_context.used_gas_of_current_function_call()
                         < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
                     {
@@ -58,6 +79,7 @@ impl AppchainAnchor {
                             &mut validator_set,
                             validator_index,
                             delegator_index,
+                            &mut delegator_ids_of_validator,
                         ) {
                             ResultOfLoopingValidatorSet::NoMoreDelegator => {
                                 validator_index += 1;
@@ -203,10 +225,33 @@ impl AppchainAnchor {
                     applying_index.0 += 1;
                 }
                 if applying_index.0 > validator_set.staking_history_index() {
+                    validator_set.set_jailed_validator_ids(
+                        self.next_validator_set
+                            .get()
+                            .unwrap()
+                            .get_jailed_validator_ids(),
+                    );
+                    validator_set.select_standby_validators(
+                        self.protocol_settings
+                            .get()
+                            .unwrap()
+                            .maximum_validator_count
+                            .0,
+                    );
                     processing_context.clear_switching_era_number();
                     validator_set.set_processing_status(
                         ValidatorSetProcessingStatus::ReadyForDistributingReward,
                     );
+                    // Snapshot the commission of validators which takes effect in this era,
+                    // so that the later changes of commission or protocol settings
+                    // will not affect the reward distribution of this era.
+                    for validator_id in validator_set.get_validator_ids() {
+                        let commission_percent = self
+                            .get_commission_percent_of_validator_in_era(&validator_id, era_number);
+                        validator_set
+                            .set_validator_commission_percent(&validator_id, commission_percent);
+                    }
+                    self.dequeue_unbonding_validators(&validator_set);
                     self.record_appchain_message_processing_result(
                         &AppchainMessageProcessingResult::Ok {
                             nonce: processing_context.processing_nonce().unwrap_or(0),
@@ -240,6 +285,7 @@ impl AppchainAnchor {
         target_validator_set: &mut ValidatorSetOfEra,
         validator_index: u64,
         delegator_index: u64,
+        delegator_ids_of_validator: &mut Option<(u64, Vec<AccountId>)>,
     ) -> ResultOfLoopingValidatorSet {
         if validator_index >= source_validator_set.validator_count() {
             return ResultOfLoopingValidatorSet::NoMoreValidator;
@@ -247,12 +293,24 @@ impl AppchainAnchor {
         let validator = source_validator_set
             .get_validator_by_index(&validator_index)
             .unwrap();
-        if delegator_index >= source_validator_set.get_delegator_count_of(&validator.validator_id) {
+        // Cache the delegator ids of the validator, as the source validator set may only
+        // hold the changes based on its parent set, which is costly to look up by index.
+        if !matches!(delegator_ids_of_validator, Some((index, _)) if *index == validator_index) {
+            *delegator_ids_of_validator = Some((
+                validator_index,
+                source_validator_set.get_delegator_ids_of(&validator.validator_id),
+            ));
+        }
+        let delegator_ids = &delegator_ids_of_validator.as_ref().unwrap().1;
+        if delegator_index >= delegator_ids.len().try_into().unwrap() {
             target_validator_set.insert_validator(&validator);
             return ResultOfLoopingValidatorSet::NoMoreDelegator;
         }
         let delegator = source_validator_set
-            .get_delegator_by_index(&delegator_index, &validator.validator_id)
+            .get_delegator(
+                &delegator_ids[usize::try_from(delegator_index).unwrap()],
+                &validator.validator_id,
+            )
             .unwrap();
         target_validator_set.insert_delegator(&delegator);
         return ResultOfLoopingValidatorSet::NeedToContinue;
@@ -0,0 +1,144 @@
+use crate::*;
+use core::convert::TryFrom;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_sdk::json_types::I128;
+
+pub trait RewardPaymentResolver {
+    /// Resolver for paying reward to the reward destination of a staker
+    fn resolve_reward_payment(
+        &mut self,
+        staker_id: AccountId,
+        amount: U128,
+        destination: RewardDestination,
+    );
+}
+
+impl AppchainAnchor {
+    /// Pay the reward of a staker to its reward destination, together with
+    /// the reward credit of the staker (if any). Return whether the payment is made.
+    ///
+    /// If the payment fails, the unpaid amount will be added to the reward credit of the staker.
+    pub fn internal_pay_reward(&mut self, staker_id: &AccountId, reward: Balance) -> bool {
+        let amount = reward + self.reward_credits.get(staker_id).unwrap_or(0);
+        if amount == 0 {
+            return false;
+        }
+        self.reward_credits.remove(staker_id);
+        let destination = self
+            .reward_settings
+            .get(staker_id)
+            .map_or(RewardDestination::Staker, |settings| settings.destination);
+        let token_contract = self
+            .wrapped_appchain_token
+            .get()
+            .unwrap()
+            .contract_account
+            .unwrap();
+        let promise = match &destination {
+            RewardDestination::Staker => ext_ft_core::ext(token_contract)
+                .with_attached_deposit(1)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                .with_unused_gas_weight(0)
+                .ft_transfer(staker_id.clone(), amount.into(), None),
+            RewardDestination::Account { account_id } => ext_ft_core::ext(token_contract)
+                .with_attached_deposit(1)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                .with_unused_gas_weight(0)
+                .ft_transfer(account_id.clone(), amount.into(), None),
+            RewardDestination::Contract { contract_id, msg } => ext_ft_core::ext(token_contract)
+                .with_attached_deposit(1)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER_CALL))
+                .with_unused_gas_weight(0)
+                .ft_transfer_call(contract_id.clone(), amount.into(), None, msg.clone()),
+            RewardDestination::Appchain { .. } => {
+                #[derive(near_sdk::serde::Serialize)]
+                #[serde(crate = "near_sdk::serde")]
+                struct Args {
+                    account_id: AccountId,
+                    amount: U128,
+                }
+                let args = Args {
+                    account_id: env::current_account_id(),
+                    amount: U128::from(amount),
+                };
+                let args = near_sdk::serde_json::to_vec(&args)
+                    .expect("Failed to serialize the cross contract args using JSON.");
+                Promise::new(token_contract).function_call(
+                    "burn".to_string(),
+                    args,
+                    1,
+                    Gas::ONE_TERA.mul(T_GAS_FOR_BURN_FUNGIBLE_TOKEN),
+                )
+            }
+        };
+        promise.then(
+            ext_self::ext(env::current_account_id())
+                .with_attached_deposit(0)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                .with_unused_gas_weight(0)
+                .resolve_reward_payment(staker_id.clone(), U128::from(amount), destination),
+        );
+        true
+    }
+}
+
+#[near_bindgen]
+impl RewardPaymentResolver for AppchainAnchor {
+    //
+    fn resolve_reward_payment(
+        &mut self,
+        staker_id: AccountId,
+        amount: U128,
+        destination: RewardDestination,
+    ) {
+        assert_self();
+        let unpaid_amount = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => match destination {
+                RewardDestination::Contract { .. } => {
+                    // The unused amount of `ft_transfer_call` is refunded to this contract.
+                    let used_amount = serde_json::from_slice::<U128>(&value)
+                        .map_or(amount.0, |used_amount| used_amount.0);
+                    amount.0 - u128::min(used_amount, amount.0)
+                }
+                RewardDestination::Appchain {
+                    receiver_id_in_appchain,
+                } => {
+                    let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+                    wrapped_appchain_token.changed_balance = I128::from(
+                        wrapped_appchain_token.changed_balance.0
+                            - i128::try_from(amount.0).unwrap(),
+                    );
+                    self.wrapped_appchain_token.set(&wrapped_appchain_token);
+                    let appchain_notification_history = self.internal_append_appchain_notification(
+                        AppchainNotification::WrappedAppchainTokenBurnt {
+                            sender_id_in_near: staker_id.clone(),
+                            receiver_id_in_appchain: receiver_id_in_appchain.clone(),
+                            amount,
+                        },
+                    );
+                    log!(
+                        "Reward of '{}' is paid to '{}' of appchain. Amount: '{}', Crosschain notification index: '{}'.",
+                        &staker_id,
+                        &receiver_id_in_appchain,
+                        &amount.0,
+                        &appchain_notification_history.index.0
+                    );
+                    0
+                }
+                _ => 0,
+            },
+            PromiseResult::Failed => amount.0,
+        };
+        if unpaid_amount > 0 {
+            let reward_credit = self.reward_credits.get(&staker_id).unwrap_or(0);
+            self.reward_credits
+                .insert(&staker_id, &(reward_credit + unpaid_amount));
+            log!(
+                "Failed to pay reward of '{}' to its reward destination. Amount: '{}'. It will be paid in the next payment.",
+                &staker_id,
+                &unpaid_amount
+            );
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{permissionless_actions::AppchainMessagesProcessingContext, *};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_contract_standards::storage_management::StorageBalance;
+
+pub trait StorageFundResolver {
+    /// Resolver for checking the storage balance of the receiver of an inbound asset transfer
+    fn resolve_receiver_storage_balance(&mut self, transfer: PendingAssetTransfer);
+    /// Resolver for registering the receiver of an inbound asset transfer by storage fund
+    fn resolve_storage_deposit_by_fund(&mut self, transfer: PendingAssetTransfer);
+}
+
+impl PendingAssetTransfer {
+    ///
+    pub fn receiver_id_in_near(&self) -> &AccountId {
+        match self {
+            PendingAssetTransfer::UnlockNearFungibleToken {
+                receiver_id_in_near,
+                ..
+            }
+            | PendingAssetTransfer::MintWrappedAppchainToken {
+                receiver_id_in_near,
+                ..
+            } => receiver_id_in_near,
+        }
+    }
+    /// The gas for the cross-contract call of the transfer and its resolver.
+    pub fn gas_of_transfer(&self) -> Gas {
+        match self {
+            PendingAssetTransfer::UnlockNearFungibleToken { .. } => {
+                Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER + T_GAS_FOR_RESOLVER_FUNCTION)
+            }
+            PendingAssetTransfer::MintWrappedAppchainToken { .. } => {
+                Gas::ONE_TERA.mul(T_GAS_FOR_MINT_FUNGIBLE_TOKEN + T_GAS_FOR_RESOLVER_FUNCTION)
+            }
+        }
+    }
+}
+
+impl AppchainAnchor {
+    //
+    fn get_token_contract_of(&self, transfer: &PendingAssetTransfer) -> AccountId {
+        match transfer {
+            PendingAssetTransfer::UnlockNearFungibleToken {
+                contract_account, ..
+            } => contract_account.clone(),
+            PendingAssetTransfer::MintWrappedAppchainToken { .. } => self
+                .wrapped_appchain_token
+                .get()
+                .unwrap()
+                .contract_account
+                .unwrap(),
+        }
+    }
+    /// Check the storage balance of the receiver in the token contract, and register
+    /// the receiver by the storage fund if necessary, before applying the transfer.
+    pub fn internal_transfer_asset_with_storage_check(
+        &mut self,
+        transfer: PendingAssetTransfer,
+        processing_context: &mut AppchainMessagesProcessingContext,
+    ) {
+        #[derive(near_sdk::serde::Serialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Args {
+            account_id: AccountId,
+        }
+        let args = Args {
+            account_id: transfer.receiver_id_in_near().clone(),
+        };
+        let args = near_sdk::serde_json::to_vec(&args)
+            .expect("Failed to serialize the cross contract args using JSON.");
+        let gas_for_resolver = Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION * 2)
+            + Gas::ONE_TERA.mul(T_GAS_FOR_STORAGE_DEPOSIT)
+            + transfer.gas_of_transfer();
+        Promise::new(self.get_token_contract_of(&transfer))
+            .function_call(
+                "storage_balance_of".to_string(),
+                args,
+                0,
+                Gas::ONE_TERA.mul(T_GAS_FOR_STORAGE_BALANCE_OF),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(gas_for_resolver)
+                    .with_unused_gas_weight(0)
+                    .resolve_receiver_storage_balance(transfer),
+            );
+        processing_context.add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_STORAGE_BALANCE_OF));
+        processing_context.add_prepaid_gas(gas_for_resolver);
+    }
+    /// Apply the cross-contract call of an inbound asset transfer with its resolver.
+    pub fn internal_transfer_asset(&self, transfer: PendingAssetTransfer) -> Promise {
+        let token_contract = self.get_token_contract_of(&transfer);
+        let (transfer_promise, resolver_promise) = match transfer {
+            PendingAssetTransfer::UnlockNearFungibleToken {
+                symbol,
+                sender_id_in_appchain,
+                receiver_id_in_near,
+                amount,
+                appchain_message_nonce,
+                ..
+            } => (
+                ext_ft_core::ext(token_contract)
+                    .with_attached_deposit(1)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                    .with_unused_gas_weight(0)
+                    .ft_transfer(receiver_id_in_near.clone(), amount.clone(), None),
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_fungible_token_transfer(
+                        symbol,
+                        sender_id_in_appchain,
+                        receiver_id_in_near,
+                        amount,
+                        appchain_message_nonce,
+                    ),
+            ),
+            PendingAssetTransfer::MintWrappedAppchainToken {
+                sender_id_in_appchain,
+                receiver_id_in_near,
+                amount,
+                appchain_message_nonce,
+            } => {
+                #[derive(near_sdk::serde::Serialize)]
+                #[serde(crate = "near_sdk::serde")]
+                struct Args {
+                    account_id: AccountId,
+                    amount: U128,
+                }
+                let args = Args {
+                    account_id: receiver_id_in_near.clone(),
+                    amount: amount.clone(),
+                };
+                let args = near_sdk::serde_json::to_vec(&args)
+                    .expect("Failed to serialize the cross contract args using JSON.");
+                (
+                    Promise::new(token_contract).function_call(
+                        "mint".to_string(),
+                        args,
+                        STORAGE_DEPOSIT_FOR_NEP141_TOEKN,
+                        Gas::ONE_TERA.mul(T_GAS_FOR_MINT_FUNGIBLE_TOKEN),
+                    ),
+                    ext_self::ext(env::current_account_id())
+                        .with_attached_deposit(0)
+                        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                        .with_unused_gas_weight(0)
+                        .resolve_wrapped_appchain_token_minting(
+                            sender_id_in_appchain,
+                            receiver_id_in_near,
+                            amount,
+                            appchain_message_nonce,
+                        ),
+                )
+            }
+        };
+        transfer_promise.then(resolver_promise)
+    }
+}
+
+#[near_bindgen]
+impl StorageFundResolver for AppchainAnchor {
+    //
+    fn resolve_receiver_storage_balance(&mut self, transfer: PendingAssetTransfer) {
+        assert_self();
+        // If the storage balance can not be queried, apply the transfer directly.
+        let receiver_is_registered = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                serde_json::from_slice::<Option<StorageBalance>>(&value)
+                    .map_or(true, |storage_balance| storage_balance.is_some())
+            }
+            PromiseResult::Failed => true,
+        };
+        if receiver_is_registered {
+            self.internal_transfer_asset(transfer);
+            return;
+        }
+        if self.storage_fund_balance < STORAGE_DEPOSIT_FOR_NEP141_TOEKN {
+            log!(
+                "Storage fund is not enough for registering '{}' in token contract '{}'.",
+                transfer.receiver_id_in_near(),
+                self.get_token_contract_of(&transfer)
+            );
+            self.internal_transfer_asset(transfer);
+            return;
+        }
+        #[derive(near_sdk::serde::Serialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Args {
+            account_id: AccountId,
+            registration_only: bool,
+        }
+        let args = Args {
+            account_id: transfer.receiver_id_in_near().clone(),
+            registration_only: true,
+        };
+        let args = near_sdk::serde_json::to_vec(&args)
+            .expect("Failed to serialize the cross contract args using JSON.");
+        log!(
+            "Registering '{}' in token contract '{}' by storage fund.",
+            transfer.receiver_id_in_near(),
+            self.get_token_contract_of(&transfer)
+        );
+        // The deposit is reserved from the storage fund, and will be given back
+        // if the registration fails.
+        self.storage_fund_balance -= STORAGE_DEPOSIT_FOR_NEP141_TOEKN;
+        let gas_for_resolver =
+            Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION) + transfer.gas_of_transfer();
+        Promise::new(self.get_token_contract_of(&transfer))
+            .function_call(
+                "storage_deposit".to_string(),
+                args,
+                STORAGE_DEPOSIT_FOR_NEP141_TOEKN,
+                Gas::ONE_TERA.mul(T_GAS_FOR_STORAGE_DEPOSIT),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(gas_for_resolver)
+                    .with_unused_gas_weight(0)
+                    .resolve_storage_deposit_by_fund(transfer),
+            );
+    }
+    //
+    fn resolve_storage_deposit_by_fund(&mut self, transfer: PendingAssetTransfer) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => (),
+            PromiseResult::Failed => {
+                self.storage_fund_balance += STORAGE_DEPOSIT_FOR_NEP141_TOEKN;
+                log!(
+                    "Failed to register '{}' in token contract '{}' by storage fund.",
+                    transfer.receiver_id_in_near(),
+                    self.get_token_contract_of(&transfer)
+                );
+            }
+        }
+        self.internal_transfer_asset(transfer);
+    }
+}
@@ -13,6 +13,8 @@ pub enum StorageKey {
     ValidatorSetHistoriesMap,
     UnwithdrawnValidatorRewards,
     UnwithdrawnDelegatorRewards,
+    DelegatorRewardCheckpoints,
+    WithdrawnDelegatorRewards,
     UnbondedStakes,
     AppchainSettings,
     AnchorSettings,
@@ -29,6 +31,8 @@ pub enum StorageKey {
     ValidatorProfilesIdSet,
     ValidatorProfilesMap,
     ValidatorProfilesIdMap,
+    ValidatorOperatorsMap,
+    ValidatorIdsByOperatorMap,
     RewardDistributionRecords,
     RewardDistributionEraNumberSet,
     RewardDistributionEraNumberToNoncesMap,
@@ -49,14 +53,38 @@ pub enum StorageKey {
     WrappedAppchainNFTsClassIds,
     WrappedAppchainNFTsNFTs,
     WrappedAppchainNFTContractWasm,
+    ValidatorCommissions,
+    ValidatorCommissionsMap,
+    DelegationPolicies,
+    SlashingSettings,
+    RewardSchedule,
+    ExpiredRewardSweepHistories,
+    IncentivePools,
+    IncentivePoolTokenContracts,
+    IncentivePoolsMap,
+    IncentiveRewardCheckpoints,
+    IncentiveRewardCredits,
+    RewardSettingsMap,
+    AutoClaimAccounts,
+    RewardCredits,
+    AppchainChallengeStatuses,
+    AppchainChallengeStatusesMap,
+    EquivocationChallengeIndexesMap,
+    ContractCallSettings,
+    PendingStakingDeposits,
     ValidatorIdsOfEra(u64),
     ValidatorToDelegatorsMapOfEra(u64),
     DelegatorToValidatorsMapOfEra(u64),
     ValidatorsOfEra(u64),
     DelegatorsOfEra(u64),
     UnprofitableValidatorIdsOfEra(u64),
+    JailedValidatorIdsOfEra(u64),
+    StandbyValidatorIdsOfEra(u64),
     ValidatorRewardsOfEra(u64),
     DelegatorRewardsOfEra(u64),
+    DelegationRewardPoolsOfEra(u64),
+    RemovedDelegatorsOfEra(u64),
+    ValidatorCommissionPercentsOfEra(u64),
     DelegatorIdsInMapOfVToDOfEra {
         era_number: u64,
         validator_id: AccountId,
@@ -82,6 +110,8 @@ impl StorageKey {
             StorageKey::ValidatorSetHistoriesMap => "vshm".to_string(),
             StorageKey::UnwithdrawnValidatorRewards => "uwvrs".to_string(),
             StorageKey::UnwithdrawnDelegatorRewards => "uwdrs".to_string(),
+            StorageKey::DelegatorRewardCheckpoints => "drcps".to_string(),
+            StorageKey::WithdrawnDelegatorRewards => "wdrs".to_string(),
             StorageKey::UnbondedStakes => "ubss".to_string(),
             StorageKey::AppchainSettings => "ass".to_string(),
             StorageKey::AnchorSettings => "ans".to_string(),
@@ -98,6 +128,8 @@ impl StorageKey {
             StorageKey::ValidatorProfilesIdSet => "vpsis".to_string(),
             StorageKey::ValidatorProfilesMap => "vpsm".to_string(),
             StorageKey::ValidatorProfilesIdMap => "vpsim".to_string(),
+            StorageKey::ValidatorOperatorsMap => "vpsom".to_string(),
+            StorageKey::ValidatorIdsByOperatorMap => "vpsvbom".to_string(),
             StorageKey::RewardDistributionRecords => "rdr".to_string(),
             StorageKey::RewardDistributionEraNumberSet => "rdens".to_string(),
             StorageKey::RewardDistributionEraNumberToNoncesMap => "rdentnm".to_string(),
@@ -118,16 +150,42 @@ impl StorageKey {
             StorageKey::WrappedAppchainNFTsClassIds => "wanftscis".to_string(),
             StorageKey::WrappedAppchainNFTsNFTs => "wanftsnfts".to_string(),
             StorageKey::WrappedAppchainNFTContractWasm => "wanftwasm".to_string(),
+            StorageKey::ValidatorCommissions => "vcs".to_string(),
+            StorageKey::ValidatorCommissionsMap => "vcsm".to_string(),
+            StorageKey::DelegationPolicies => "dps".to_string(),
+            StorageKey::SlashingSettings => "sls".to_string(),
+            StorageKey::RewardSchedule => "rws".to_string(),
+            StorageKey::ExpiredRewardSweepHistories => "ersh".to_string(),
+            StorageKey::IncentivePools => "ips".to_string(),
+            StorageKey::IncentivePoolTokenContracts => "iptcs".to_string(),
+            StorageKey::IncentivePoolsMap => "ipsm".to_string(),
+            StorageKey::IncentiveRewardCheckpoints => "ircs".to_string(),
+            StorageKey::IncentiveRewardCredits => "ircrs".to_string(),
+            StorageKey::RewardSettingsMap => "rsm".to_string(),
+            StorageKey::AutoClaimAccounts => "acas".to_string(),
+            StorageKey::RewardCredits => "rcs".to_string(),
+            StorageKey::AppchainChallengeStatuses => "acss".to_string(),
+            StorageKey::AppchainChallengeStatusesMap => "acssm".to_string(),
+            StorageKey::EquivocationChallengeIndexesMap => "ecim".to_string(),
+            StorageKey::ContractCallSettings => "ccs".to_string(),
+            StorageKey::PendingStakingDeposits => "psds".to_string(),
             StorageKey::ValidatorIdsOfEra(era_number) => format!("{}vis", era_number),
             StorageKey::ValidatorToDelegatorsMapOfEra(era_number) => format!("{}lmvtd", era_number),
             StorageKey::DelegatorToValidatorsMapOfEra(era_number) => format!("{}lmdtv", era_number),
             StorageKey::ValidatorsOfEra(era_number) => format!("{}vs", era_number),
             StorageKey::DelegatorsOfEra(era_number) => format!("{}ds", era_number),
+            StorageKey::JailedValidatorIdsOfEra(era_number) => format!("{}jvis", era_number),
+            StorageKey::StandbyValidatorIdsOfEra(era_number) => format!("{}svis", era_number),
             StorageKey::UnprofitableValidatorIdsOfEra(era_number) => {
                 format!("{}upvis", era_number)
             }
             StorageKey::ValidatorRewardsOfEra(era_number) => format!("{}vrs", era_number),
             StorageKey::DelegatorRewardsOfEra(era_number) => format!("{}drs", era_number),
+            StorageKey::DelegationRewardPoolsOfEra(era_number) => format!("{}drps", era_number),
+            StorageKey::RemovedDelegatorsOfEra(era_number) => format!("{}rmds", era_number),
+            StorageKey::ValidatorCommissionPercentsOfEra(era_number) => {
+                format!("{}vcps", era_number)
+            }
             StorageKey::DelegatorIdsInMapOfVToDOfEra {
                 era_number,
                 validator_id,
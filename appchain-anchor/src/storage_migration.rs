@@ -1,6 +1,7 @@
+use crate::validator_set::ValidatorSet;
 use crate::*;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
 use near_sdk::{env, near_bindgen, AccountId, Balance, BlockHeight};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -46,6 +47,13 @@ pub struct OldAppchainMessage {
     pub nonce: u32,
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum OldAppchainMessageProcessingResult {
+    Ok { nonce: u32, message: Option<String> },
+    Error { nonce: u32, message: String },
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct OldAnchorEventHistory {
@@ -73,6 +81,55 @@ pub struct OldStakingHistory {
     pub index: U64,
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OldProtocolSettings {
+    pub minimum_validator_deposit: U128,
+    pub minimum_validator_deposit_changing_amount: U128,
+    pub maximum_validator_stake_percent: u16,
+    pub minimum_delegator_deposit: U128,
+    pub minimum_delegator_deposit_changing_amount: U128,
+    pub minimum_total_stake_price_for_booting: U128,
+    pub maximum_market_value_percent_of_near_fungible_tokens: u16,
+    pub maximum_market_value_percent_of_wrapped_appchain_token: u16,
+    pub minimum_validator_count: U64,
+    pub maximum_validator_count: U64,
+    pub maximum_validators_per_delegator: U64,
+    pub unlock_period_of_validator_deposit: U64,
+    pub unlock_period_of_delegator_deposit: U64,
+    pub maximum_era_count_of_unwithdrawn_reward: U64,
+    pub maximum_era_count_of_valid_appchain_message: U64,
+    pub validator_commission_percent: u16,
+    pub maximum_allowed_unprofitable_era_count: u16,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldValidatorSetOfEra {
+    pub validator_set: ValidatorSet,
+    pub start_block_height: BlockHeight,
+    pub start_timestamp: Timestamp,
+    pub staking_history_index: u64,
+    pub unprofitable_validator_id_set: UnorderedSet<AccountId>,
+    pub valid_total_stake: Balance,
+    pub validator_rewards: LookupMap<AccountId, Balance>,
+    pub delegator_rewards: LookupMap<(AccountId, AccountId), Balance>,
+    pub processing_status: ValidatorSetProcessingStatus,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldNextValidatorSet {
+    pub validator_set: ValidatorSet,
+    pub unbonding_validator_ids: Vec<AccountId>,
+    pub auto_unbonding_validator_ids: Vec<AccountId>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldValidatorProfiles {
+    pub validator_id_set: UnorderedSet<AccountId>,
+    pub profiles: LookupMap<AccountId, ValidatorProfile>,
+    pub map_by_id_in_appchain: LookupMap<String, AccountId>,
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct OldAppchainAnchor {
     /// The id of corresponding appchain.
@@ -93,7 +150,7 @@ pub struct OldAppchainAnchor {
     validator_set_histories: LazyOption<LookupArray<ValidatorSetOfEra>>,
     /// The validator set of the next era in appchain.
     /// This validator set is only for checking staking rules.
-    next_validator_set: LazyOption<NextValidatorSet>,
+    next_validator_set: LazyOption<OldNextValidatorSet>,
     /// The map of unwithdrawn validator rewards in eras, in unit of wrapped appchain token.
     /// The key in map is `(era_number, account_id_of_validator)`
     unwithdrawn_validator_rewards: LookupMap<(u64, AccountId), Balance>,
@@ -103,13 +160,13 @@ pub struct OldAppchainAnchor {
     /// The map of unbonded stakes in eras.
     unbonded_stakes: LookupMap<AccountId, Vec<UnbondedStakeReference>>,
     /// The validators' profiles data.
-    validator_profiles: LazyOption<ValidatorProfiles>,
+    validator_profiles: LazyOption<OldValidatorProfiles>,
     /// The custom settings for appchain.
     appchain_settings: LazyOption<AppchainSettings>,
     /// The anchor settings for appchain.
     anchor_settings: LazyOption<AnchorSettings>,
     /// The protocol settings for appchain anchor.
-    protocol_settings: LazyOption<ProtocolSettings>,
+    protocol_settings: LazyOption<OldProtocolSettings>,
     /// The state of the corresponding appchain.
     appchain_state: AppchainState,
     /// The staking history data happened in this contract.
@@ -149,6 +206,7 @@ impl AppchainAnchor {
         near_sdk::assert_self();
         //
         old_contract.clear_anchor_events();
+        let old_appchain_messages = old_contract.appchain_messages.get().unwrap();
         // Create the new contract using the data from the old contract.
         let new_contract = AppchainAnchor {
             appchain_id: old_contract.appchain_id,
@@ -160,14 +218,38 @@ impl AppchainAnchor {
             wrapped_appchain_token: old_contract.wrapped_appchain_token,
             near_fungible_tokens: old_contract.near_fungible_tokens,
             validator_set_histories: old_contract.validator_set_histories,
-            next_validator_set: old_contract.next_validator_set,
+            next_validator_set: LazyOption::new(
+                StorageKey::NextValidatorSet.into_bytes(),
+                Some(&NextValidatorSet::from_old_version(
+                    old_contract.next_validator_set.get().unwrap(),
+                )),
+            ),
             unwithdrawn_validator_rewards: old_contract.unwithdrawn_validator_rewards,
+            // The rewards of delegators recorded by the earlier versions are kept
+            // for querying and withdrawing, the rewards of following eras are
+            // calculated from the delegation reward pools of validators.
             unwithdrawn_delegator_rewards: old_contract.unwithdrawn_delegator_rewards,
+            delegator_reward_checkpoints: LookupMap::new(
+                StorageKey::DelegatorRewardCheckpoints.into_bytes(),
+            ),
+            withdrawn_delegator_rewards: LookupMap::new(
+                StorageKey::WithdrawnDelegatorRewards.into_bytes(),
+            ),
             unbonded_stakes: old_contract.unbonded_stakes,
-            validator_profiles: old_contract.validator_profiles,
+            validator_profiles: LazyOption::new(
+                StorageKey::ValidatorProfiles.into_bytes(),
+                Some(&ValidatorProfiles::from_old_version(
+                    old_contract.validator_profiles.get().unwrap(),
+                )),
+            ),
             appchain_settings: old_contract.appchain_settings,
             anchor_settings: old_contract.anchor_settings,
-            protocol_settings: old_contract.protocol_settings,
+            protocol_settings: LazyOption::new(
+                StorageKey::ProtocolSettings.into_bytes(),
+                Some(&ProtocolSettings::from_old_version(
+                    old_contract.protocol_settings.get().unwrap(),
+                )),
+            ),
             appchain_state: old_contract.appchain_state,
             staking_histories: old_contract.staking_histories,
             appchain_notification_histories: old_contract.appchain_notification_histories,
@@ -180,6 +262,54 @@ impl AppchainAnchor {
             appchain_messages: old_contract.appchain_messages,
             appchain_challenges: old_contract.appchain_challenges,
             wrapped_appchain_nfts: old_contract.wrapped_appchain_nfts,
+            validator_commissions: LazyOption::new(
+                StorageKey::ValidatorCommissions.into_bytes(),
+                Some(&ValidatorCommissions::new()),
+            ),
+            delegation_policies: LookupMap::new(StorageKey::DelegationPolicies.into_bytes()),
+            slashing_settings: LazyOption::new(
+                StorageKey::SlashingSettings.into_bytes(),
+                Some(&SlashingSettings::default()),
+            ),
+            reward_schedule: LazyOption::new(
+                StorageKey::RewardSchedule.into_bytes(),
+                Some(&RewardSchedule::Fixed),
+            ),
+            expired_reward_sweep_histories: LookupMap::new(
+                StorageKey::ExpiredRewardSweepHistories.into_bytes(),
+            ),
+            incentive_pools: LazyOption::new(
+                StorageKey::IncentivePools.into_bytes(),
+                Some(&IncentivePools::new()),
+            ),
+            incentive_reward_checkpoints: LookupMap::new(
+                StorageKey::IncentiveRewardCheckpoints.into_bytes(),
+            ),
+            incentive_reward_credits: LookupMap::new(
+                StorageKey::IncentiveRewardCredits.into_bytes(),
+            ),
+            reward_settings: LookupMap::new(StorageKey::RewardSettingsMap.into_bytes()),
+            auto_claim_accounts: TreeMap::new(StorageKey::AutoClaimAccounts.into_bytes()),
+            reward_credits: LookupMap::new(StorageKey::RewardCredits.into_bytes()),
+            storage_fund_balance: 0,
+            appchain_challenge_statuses: LazyOption::new(
+                StorageKey::AppchainChallengeStatuses.into_bytes(),
+                Some(&AppchainChallengeStatuses::new()),
+            ),
+            payload_codec: PayloadCodec::Borsh,
+            contract_call_settings: LazyOption::new(
+                StorageKey::ContractCallSettings.into_bytes(),
+                Some(&ContractCallSettings::default()),
+            ),
+            unmigrated_processing_result_nonces: Some((
+                old_appchain_messages.min_nonce(),
+                old_appchain_messages.max_nonce(),
+            )),
+            pending_staking_deposits: LookupMap::new(
+                StorageKey::PendingStakingDeposits.into_bytes(),
+            ),
+            delegator_slashing_status: None,
+            untransferred_slashed_amount: 0,
         };
         //
         //
@@ -217,6 +347,40 @@ impl AppchainAnchor {
         MultiTxsOperationProcessingResult::Ok
     }
     ///
+    pub fn migrate_validator_set_histories(
+        &mut self,
+        start_index: U64,
+    ) -> MultiTxsOperationProcessingResult {
+        near_sdk::assert_self();
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let index_range = validator_set_histories.index_range();
+        for index in start_index.0..index_range.end_index.0 + 1 {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Not all records are migrated. Call this function again with start_index '{}'.",
+                    index
+                ));
+            }
+            if let Some(old_data) = env::storage_read(&get_storage_key_in_lookup_array(
+                &StorageKey::ValidatorSetHistoriesMap,
+                &index,
+            )) {
+                if let Ok(old_version) = OldValidatorSetOfEra::try_from_slice(&old_data) {
+                    env::storage_write(
+                        &get_storage_key_in_lookup_array(
+                            &StorageKey::ValidatorSetHistoriesMap,
+                            &index,
+                        ),
+                        &ValidatorSetOfEra::from_old_version(old_version)
+                            .try_to_vec()
+                            .unwrap(),
+                    );
+                }
+            }
+        }
+        MultiTxsOperationProcessingResult::Ok
+    }
+    ///
     pub fn migrate_appchain_notification_histories(
         &mut self,
         start_index: U64,
@@ -280,6 +444,46 @@ impl AppchainAnchor {
         }
         MultiTxsOperationProcessingResult::Ok
     }
+    /// Migrate the appchain message processing results recorded by the earlier version.
+    ///
+    /// The range of records to migrate is fixed in `migrate_state`, and the progress is saved
+    /// in state, so that each record is migrated exactly once. Call this function again
+    /// if it returns `NeedMoreGas`.
+    pub fn migrate_appchain_message_processing_results(
+        &mut self,
+    ) -> MultiTxsOperationProcessingResult {
+        near_sdk::assert_self();
+        let (start_nonce, end_nonce) = self
+            .unmigrated_processing_result_nonces
+            .expect("The appchain message processing results are already migrated.");
+        for nonce in start_nonce..end_nonce + 1 {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                self.unmigrated_processing_result_nonces = Some((nonce, end_nonce));
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+            let storage_key = get_storage_key_in_lookup_array(
+                &StorageKey::AppchainMessageProcessingResultMap,
+                &nonce,
+            );
+            if let Some(old_data) = env::storage_read(&storage_key) {
+                let old_version = OldAppchainMessageProcessingResult::try_from_slice(&old_data)
+                    .unwrap_or_else(|_| {
+                        panic!(
+                            "Failed to decode the processing result of appchain message '{}'.",
+                            nonce
+                        )
+                    });
+                env::storage_write(
+                    &storage_key,
+                    &AppchainMessageProcessingResult::from_old_version(old_version)
+                        .try_to_vec()
+                        .unwrap(),
+                );
+            }
+        }
+        self.unmigrated_processing_result_nonces = None;
+        MultiTxsOperationProcessingResult::Ok
+    }
 }
 
 fn get_storage_key_in_lookup_array<T: BorshSerialize>(prefix: &StorageKey, index: &T) -> Vec<u8> {
@@ -345,6 +549,54 @@ impl IndexedAndClearable for OldStakingHistory {
     }
 }
 
+impl ProtocolSettings {
+    //
+    pub fn from_old_version(old_version: OldProtocolSettings) -> Self {
+        let default_settings = ProtocolSettings::default();
+        Self {
+            minimum_validator_deposit: old_version.minimum_validator_deposit,
+            minimum_validator_deposit_changing_amount: old_version
+                .minimum_validator_deposit_changing_amount,
+            maximum_validator_stake_percent: old_version.maximum_validator_stake_percent,
+            minimum_delegator_deposit: old_version.minimum_delegator_deposit,
+            minimum_delegator_deposit_changing_amount: old_version
+                .minimum_delegator_deposit_changing_amount,
+            minimum_total_stake_price_for_booting: old_version
+                .minimum_total_stake_price_for_booting,
+            maximum_market_value_percent_of_near_fungible_tokens: old_version
+                .maximum_market_value_percent_of_near_fungible_tokens,
+            maximum_market_value_percent_of_wrapped_appchain_token: old_version
+                .maximum_market_value_percent_of_wrapped_appchain_token,
+            minimum_validator_count: old_version.minimum_validator_count,
+            maximum_validator_count: old_version.maximum_validator_count,
+            maximum_validators_per_delegator: old_version.maximum_validators_per_delegator,
+            unlock_period_of_validator_deposit: old_version.unlock_period_of_validator_deposit,
+            unlock_period_of_delegator_deposit: old_version.unlock_period_of_delegator_deposit,
+            maximum_era_count_of_unwithdrawn_reward: old_version
+                .maximum_era_count_of_unwithdrawn_reward,
+            maximum_era_count_of_valid_appchain_message: old_version
+                .maximum_era_count_of_valid_appchain_message,
+            validator_commission_percent: old_version.validator_commission_percent,
+            maximum_allowed_unprofitable_era_count: old_version
+                .maximum_allowed_unprofitable_era_count,
+            minimum_validator_commission_percent: default_settings
+                .minimum_validator_commission_percent,
+            maximum_validator_commission_percent: default_settings
+                .maximum_validator_commission_percent,
+            maximum_validator_commission_percent_change_per_era: default_settings
+                .maximum_validator_commission_percent_change_per_era,
+            validator_commission_change_notice_era_count: default_settings
+                .validator_commission_change_notice_era_count,
+            validator_entry_limit: default_settings.validator_entry_limit,
+            validator_exit_limit: default_settings.validator_exit_limit,
+            treasury_account: default_settings.treasury_account,
+            treasury_reward_percent: default_settings.treasury_reward_percent,
+            unprofitable_validator_reward_policy: default_settings
+                .unprofitable_validator_reward_policy,
+        }
+    }
+}
+
 impl StakingHistory {
     //
     pub fn from_old_version(old_version: OldStakingHistory) -> Self {
@@ -430,3 +682,21 @@ impl AppchainMessage {
         }
     }
 }
+
+impl AppchainMessageProcessingResult {
+    //
+    pub fn from_old_version(old_version: OldAppchainMessageProcessingResult) -> Self {
+        match old_version {
+            OldAppchainMessageProcessingResult::Ok { nonce, message } => {
+                AppchainMessageProcessingResult::Ok { nonce, message }
+            }
+            OldAppchainMessageProcessingResult::Error { nonce, message } => {
+                AppchainMessageProcessingResult::Error {
+                    nonce,
+                    error: AppchainMessageError::Unclassified,
+                    message,
+                }
+            }
+        }
+    }
+}
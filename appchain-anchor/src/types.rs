@@ -111,6 +111,39 @@ pub struct AppchainSettings {
     pub bonus_for_new_validator: U128,
 }
 
+/// The settings for the contract calls requested by the appchain.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractCallSettings {
+    /// The allowed methods, mapped by the contract account.
+    /// The calls to a contract or a method which is not in this map will be rejected.
+    pub allowed_methods: HashMap<AccountId, Vec<String>>,
+    /// The maximum prepaid gas of a contract call.
+    pub maximum_gas: U64,
+    /// The maximum deposit (in yocto NEAR) attached to a contract call.
+    pub maximum_deposit: U128,
+    /// The remaining cumulative prepaid gas for all contract calls.
+    /// It is debited by each contract call and can be refilled by the owner.
+    pub remaining_gas_budget: U64,
+    /// The remaining cumulative deposit (in yocto NEAR) for all contract calls.
+    /// It is debited by each contract call and can be refilled by the owner.
+    pub remaining_deposit_budget: U128,
+}
+
+/// The codec of the payloads of appchain messages.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PayloadCodec {
+    Borsh,
+    Scale,
+    /// The payloads of messages with nonce less than `nonce` are Borsh encoded,
+    /// and the others are SCALE encoded.
+    /// Used in the transition period of the payload codec of the appchain.
+    ScaleFromNonce {
+        nonce: u32,
+    },
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct AnchorSettings {
@@ -119,6 +152,143 @@ pub struct AnchorSettings {
     pub beefy_light_client_witness_mode: bool,
 }
 
+/// A step of the era reward schedule.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardScheduleStep {
+    /// The number of the era from which this step takes effect.
+    pub start_era_number: U64,
+    /// The amount of reward of each era in this step.
+    pub era_reward: U128,
+}
+
+/// The schedule for calculating the total reward of an era.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RewardSchedule {
+    /// The `era_reward` in appchain settings is used for all eras.
+    Fixed,
+    /// The reward of an era is the `era_reward` of the last step which starts
+    /// at or before the era. The `era_reward` in appchain settings is used for
+    /// the eras before the first step.
+    StepTable { steps: Vec<RewardScheduleStep> },
+    /// The `era_reward` in appchain settings is decreased by `decay_percent`
+    /// every `decay_era_count` eras. A halving schedule has `decay_percent` of 50.
+    Decaying {
+        decay_era_count: U64,
+        decay_percent: u16,
+    },
+    /// The reward of an era is based on the annual inflation of the total supply of
+    /// wrapped appchain token, which depends on the staking ratio of the era.
+    ///
+    /// The staking ratio is the percent of the market value of total stake of the era
+    /// to the market value of wrapped appchain token. The inflation increases linearly
+    /// from the minimum to the maximum while the staking ratio increases from zero to
+    /// the ideal ratio, and decreases linearly back to the minimum while the staking ratio
+    /// increases from the ideal ratio to 100 percent.
+    StakingRatioCurve {
+        /// The minimum annual inflation, in basis points (1/10000).
+        minimum_annual_inflation: u16,
+        /// The maximum annual inflation, in basis points (1/10000).
+        maximum_annual_inflation: u16,
+        /// The staking ratio (in percent) on which the inflation is maximum.
+        ideal_staking_ratio: u16,
+        /// The number of eras in a year.
+        era_count_per_year: U64,
+    },
+}
+
+/// The reason why a validator is jailed.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum JailReason {
+    /// The validator is reported as an offender by the appchain.
+    Offence { offence_kind: String },
+    /// The validator is proved to be equivocating by an appchain challenge.
+    Equivocation { challenge_index: U64 },
+    /// The validator is jailed by the owner of this contract.
+    ByOwner,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JailedValidator {
+    pub validator_id: AccountId,
+    pub reason: JailReason,
+    /// The number of the first era in which the validator is excluded from the validator set.
+    pub start_era_number: U64,
+    /// The number of the era from which the validator can be unjailed.
+    pub release_era_number: U64,
+}
+
+/// The unbonding request of a validator which exceeds the exit limit of an era,
+/// and is waiting for taking effect in later eras.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QueuedUnbondingValidator {
+    pub validator_id: AccountId,
+    /// Whether the validator is unbonded by this contract automatically.
+    pub auto_unbond: bool,
+    /// The number of the era in which the request was expected to take effect.
+    pub queued_era_number: U64,
+}
+
+/// The destination of the OCT token slashed from offending validators and their delegators.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SlashedTokenDestination {
+    /// The slashed token will be transferred to the treasury account.
+    Treasury(AccountId),
+    /// The slashed token will be locked in this contract forever.
+    Lock,
+}
+
+/// The penalty for a validator which is proved to be equivocating in the appchain.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EquivocationPenalty {
+    /// The percent of deposit to be slashed.
+    pub slash_percent: u16,
+    /// Whether the validator will be unbonded in the next era.
+    pub force_unbond: bool,
+    /// The number of eras for which the validator will be jailed.
+    /// The validator will not be jailed if this is zero.
+    pub jail_era_count: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlashingSettings {
+    /// The percent of deposit to be slashed for each offence, mapped by offence kind.
+    /// The offences of a kind which is not in this map will not be slashed.
+    pub slash_percents_of_offence_kinds: HashMap<String, u16>,
+    /// Whether the delegations of an offending validator will be slashed pro rata.
+    pub slash_delegators: bool,
+    /// The number of eras for which a slashed offender will be jailed.
+    /// The offenders will not be jailed if this is zero.
+    pub offender_jail_era_count: U64,
+    ///
+    pub slashed_token_destination: SlashedTokenDestination,
+    ///
+    pub equivocation_penalty: EquivocationPenalty,
+    /// The percent of slashed token to be paid to the submitter of
+    /// an equivocation challenge.
+    pub equivocation_challenger_bounty_percent: u16,
+}
+
+/// The status of slashing the delegators of the offenders reported in an appchain message,
+/// which may cost more than one transaction.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct DelegatorSlashingStatus {
+    /// The offending validators whose delegators are to be slashed,
+    /// together with the slash percent of each of them.
+    pub slashing_validators: Vec<(AccountId, u16)>,
+    pub slashing_validator_index: U64,
+    pub slashing_delegator_index: U64,
+    /// The total amount slashed from the offenders so far.
+    pub total_slashed_amount: U128,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct ProtocolSettings {
@@ -147,8 +317,9 @@ pub struct ProtocolSettings {
     /// The minimum number of validator(s) registered in this contract for
     /// booting the corresponding appchain and keep it alive.
     pub minimum_validator_count: U64,
-    /// The maximum number of validator(s) registered in this contract for
-    /// the corresponding appchain.
+    /// The maximum number of active validator(s) in an era of the corresponding appchain.
+    /// The validators out of the top `maximum_validator_count` ranked by total stake
+    /// will be standby validators.
     pub maximum_validator_count: U64,
     /// The maximum number of validator(s) which a delegator can delegate to.
     pub maximum_validators_per_delegator: U64,
@@ -165,10 +336,108 @@ pub struct ProtocolSettings {
     /// If the era number of appchain message is smaller than the latest era number minus
     /// this value, the message will be considered as `invalid`.
     pub maximum_era_count_of_valid_appchain_message: U64,
-    /// The percent of commission fees of a validator's reward in an era
+    /// The percent of commission fees of a validator's reward in an era.
+    /// This is the default value for validators which have not set their own commission.
     pub validator_commission_percent: u16,
     /// The maximum unprofitable era count for auto-unbonding a validator
     pub maximum_allowed_unprofitable_era_count: u16,
+    /// The minimum commission percent that a validator can set
+    pub minimum_validator_commission_percent: u16,
+    /// The maximum commission percent that a validator can set
+    pub maximum_validator_commission_percent: u16,
+    /// The maximum difference between the new commission percent and the current one
+    /// when a validator changes his/her commission
+    pub maximum_validator_commission_percent_change_per_era: u16,
+    /// The number of eras that a changed commission of a validator will wait for
+    /// before it takes effect
+    pub validator_commission_change_notice_era_count: U64,
+    /// The limit of validators entering the validator set in an era.
+    pub validator_entry_limit: ValidatorChurnLimit,
+    /// The limit of validators leaving the validator set in an era.
+    /// The unbonding requests exceeding the limit will be queued for later eras.
+    pub validator_exit_limit: ValidatorChurnLimit,
+    /// The account which receives the treasury share of era reward.
+    pub treasury_account: Option<AccountId>,
+    /// The percent of era reward to be minted to the treasury account
+    /// before distributing to validators and delegators.
+    pub treasury_reward_percent: u16,
+    /// The handling of the share of era reward of unprofitable validators.
+    pub unprofitable_validator_reward_policy: UnprofitableValidatorRewardPolicy,
+}
+
+/// The handling of the share of era reward of unprofitable validators.
+///
+/// The share of a validator is in proportion to its total stake, like the profitable ones.
+/// The unprofitable validators which are jailed or standby have no share.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum UnprofitableValidatorRewardPolicy {
+    /// The share is redistributed to the profitable validators and their delegators.
+    Redistribute,
+    /// The share is withheld (not minted).
+    Withhold,
+    /// The share is minted to the treasury account.
+    Treasury,
+    /// The share is distributed to the delegators of the unprofitable validators,
+    /// the share of the validators' own deposit is withheld.
+    Delegators,
+}
+
+/// The limit of validators (and their stake) entering or leaving the validator set in an era.
+///
+/// The limits are relative to the validator set of the latest era. If both the absolute value
+/// and the percent are set, the greater one is applied. If neither is set, there is no limit.
+/// At least one validator can always enter or leave the validator set in an era.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorChurnLimit {
+    /// The maximum number of validators.
+    pub maximum_validator_count: Option<U64>,
+    /// The maximum percent of the number of validators in the latest era.
+    pub maximum_validator_count_percent: Option<u16>,
+    /// The maximum amount of total stake of validators.
+    pub maximum_stake: Option<U128>,
+    /// The maximum percent of the total stake in the latest era.
+    pub maximum_stake_percent: Option<u16>,
+}
+
+impl ValidatorChurnLimit {
+    ///
+    pub fn assert_valid(&self) {
+        if let Some(percent) = self.maximum_validator_count_percent {
+            assert!(percent <= 100, "Invalid percent value.");
+        }
+        if let Some(percent) = self.maximum_stake_percent {
+            assert!(percent <= 100, "Invalid percent value.");
+        }
+    }
+    /// Get the maximum number of validators that can enter or leave the validator set
+    /// in an era.
+    pub fn get_validator_count_budget(&self, latest_validator_count: u64) -> u64 {
+        match (
+            self.maximum_validator_count.as_ref(),
+            self.maximum_validator_count_percent,
+        ) {
+            (None, None) => u64::MAX,
+            (count, percent) => std::cmp::max(
+                count.map_or(0, |count| count.0),
+                percent.map_or(0, |percent| {
+                    latest_validator_count * u64::from(percent) / 100
+                }),
+            ),
+        }
+    }
+    /// Get the maximum amount of total stake of validators that can enter or leave
+    /// the validator set in an era.
+    pub fn get_stake_budget(&self, latest_total_stake: u128) -> u128 {
+        match (self.maximum_stake.as_ref(), self.maximum_stake_percent) {
+            (None, None) => u128::MAX,
+            (stake, percent) => std::cmp::max(
+                stake.map_or(0, |stake| stake.0),
+                percent.map_or(0, |percent| latest_total_stake * u128::from(percent) / 100),
+            ),
+        }
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -280,6 +549,44 @@ pub enum StakingFact {
         validator_id: AccountId,
         validator_id_in_appchain: String,
     },
+    /// A delegator moves his delegation from a validator to another validator directly
+    DelegationRedelegated {
+        delegator_id: AccountId,
+        old_validator_id: AccountId,
+        new_validator_id: AccountId,
+        amount: U128,
+    },
+    /// A validator rebonds the stake which was decreased or unbonded before.
+    /// If the validator is not in the validator set, it will be registered again
+    /// with delegation disabled.
+    StakeRebonded {
+        validator_id: AccountId,
+        validator_id_in_appchain: String,
+        amount: U128,
+        staking_history_index: U64,
+    },
+    /// A delegator rebonds the delegation which was decreased or unbonded before
+    DelegationRebonded {
+        delegator_id: AccountId,
+        validator_id: AccountId,
+        amount: U128,
+        staking_history_index: U64,
+    },
+    /// A validator is slashed for the offences reported by the appchain
+    ValidatorSlashed {
+        validator_id: AccountId,
+        offence_kind: String,
+        offences: u32,
+        slash_percent: u16,
+        amount: U128,
+    },
+    /// A delegator is slashed for the offences of the validator he delegated to
+    DelegatorSlashed {
+        delegator_id: AccountId,
+        validator_id: AccountId,
+        slash_percent: u16,
+        amount: U128,
+    },
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -370,6 +677,9 @@ pub struct AppchainValidator {
     pub delegators_count: U64,
     pub can_be_delegated_to: bool,
     pub is_unbonding: bool,
+    /// Whether the validator is out of the active validators (ranked by total stake),
+    /// and will not be profited.
+    pub is_standby: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -389,6 +699,8 @@ pub struct UnbondedStake {
     pub account_id: AccountId,
     /// The amount of unbonded stake.
     pub amount: U128,
+    /// The index of staking history which the unbonded stake refers to.
+    pub staking_history_index: U64,
     /// The unlock time of the stake.
     /// If the unlock time is not determined at the time, the value will be `None`.
     pub unlock_time: U64,
@@ -397,7 +709,7 @@ pub struct UnbondedStake {
 /// The actual processing order is:
 /// `CopyingFromLastEra` -> `UnbondingValidator`-> `AutoUnbondingValidator`
 /// -> `ApplyingStakingHistory` -> `ReadyForDistributingReward` -> `DistributingReward`
-/// -> `CheckingForAutoUnbondingValidator` -> `Completed`
+/// -> `CheckingForAutoUnbondingValidator` -> `AutoClaimingRewards` -> `Completed`
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub enum ValidatorSetProcessingStatus {
@@ -412,6 +724,7 @@ pub enum ValidatorSetProcessingStatus {
     DistributingReward {
         appchain_message_nonce: u32,
         distributing_validator_index: U64,
+        /// Not used any more, as the rewards of delegators are not distributed one by one.
         distributing_delegator_index: U64,
     },
     Completed,
@@ -426,6 +739,11 @@ pub enum ValidatorSetProcessingStatus {
     CheckingForAutoUnbondingValidator {
         unprofitable_validator_index: U64,
     },
+    AutoClaimingRewards {
+        /// The last account whose rewards are claimed. The accounts are claimed
+        /// in the order of account id.
+        last_claimed_account_id: Option<AccountId>,
+    },
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -458,6 +776,105 @@ pub struct RewardHistory {
     pub unwithdrawn_reward: U128,
 }
 
+/// The pool of an additional token which is distributed to stakes alongside era reward.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IncentivePool {
+    /// The contract account of the incentive token.
+    pub token_contract: AccountId,
+    /// The amount of incentive token distributed in each era.
+    pub per_era_amount: U128,
+    /// The count of eras in which the incentive token is still to be distributed.
+    pub remaining_era_count: U64,
+    /// The total amount of incentive token funded to the pool.
+    pub total_funded_amount: U128,
+    /// The total amount of incentive token allocated to eras.
+    pub total_allocated_amount: U128,
+}
+
+/// The amount of an incentive token allocated to the stakes of an era.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IncentiveRewardOfEra {
+    pub token_contract: AccountId,
+    pub amount: U128,
+}
+
+/// The destination which the rewards of a staker are paid to.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RewardDestination {
+    /// The account of the staker itself.
+    Staker,
+    /// Another account in NEAR protocol.
+    Account { account_id: AccountId },
+    /// A contract in NEAR protocol, which is paid by `ft_transfer_call` with the message.
+    Contract { contract_id: AccountId, msg: String },
+    /// An account in the appchain, which is paid by burning the wrapped appchain token.
+    Appchain { receiver_id_in_appchain: String },
+}
+
+/// The reward settings of a staker (a validator or a delegator).
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardSettings {
+    pub destination: RewardDestination,
+    /// Whether the rewards are paid automatically at the end of
+    /// the reward distribution of each era.
+    pub auto_claim: bool,
+}
+
+/// An inbound asset transfer which is waiting for the storage check of its receiver
+/// in the token contract.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PendingAssetTransfer {
+    UnlockNearFungibleToken {
+        symbol: String,
+        contract_account: AccountId,
+        sender_id_in_appchain: String,
+        receiver_id_in_near: AccountId,
+        amount: U128,
+        appchain_message_nonce: u32,
+    },
+    MintWrappedAppchainToken {
+        sender_id_in_appchain: Option<String>,
+        receiver_id_in_near: AccountId,
+        amount: U128,
+        appchain_message_nonce: u32,
+    },
+}
+
+/// The record of sweeping the expired unwithdrawn rewards of an era to the treasury account.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ExpiredRewardSweepHistory {
+    pub era_number: U64,
+    /// The account which receives the swept rewards.
+    pub treasury_account: AccountId,
+    /// The total amount of swept rewards of validators.
+    pub validator_reward: U128,
+    /// The total amount of swept rewards of delegators.
+    pub delegator_reward: U128,
+    /// The index of the validator whose rewards are being swept.
+    pub sweeping_validator_index: U64,
+    /// The index of the delegator (of the validator) whose rewards are being swept.
+    pub sweeping_delegator_index: U64,
+    /// Whether all expired rewards of the era are swept.
+    pub is_completed: bool,
+    /// The status of transferring the swept rewards to the treasury account.
+    pub transfer_status: SweptRewardTransferStatus,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SweptRewardTransferStatus {
+    NotTransferred,
+    Transferring,
+    Transferred,
+    Failed,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct AnchorStatus {
@@ -491,8 +908,33 @@ pub struct ValidatorSetInfo {
     pub staking_history_index: U64,
     /// The set of validator id which will not be profited.
     pub unprofitable_validator_ids: Vec<AccountId>,
-    /// Total stake excluding all unprofitable validators' stake.
+    /// The set of validator id which are jailed in this era.
+    pub jailed_validator_ids: Vec<AccountId>,
+    /// The set of validator id which are standby (not active) in this era.
+    pub standby_validator_ids: Vec<AccountId>,
+    /// Total stake excluding all unprofitable, jailed and standby validators' stake.
     pub valid_total_stake: U128,
+    /// The amount of era reward calculated by the reward schedule for this era.
+    pub era_reward: U128,
+    /// The account which received the treasury share of era reward in this era.
+    pub treasury_account: Option<AccountId>,
+    /// The amount of era reward minted to the treasury account in this era.
+    pub treasury_reward: U128,
+    /// The share of era reward of unprofitable validators which is not distributed
+    /// to validators and delegators in this era.
+    pub withheld_reward: U128,
+    /// The total amount of era reward to be distributed in this era.
+    pub total_reward: U128,
+    /// The amount of reward which has been distributed to validators and delegators.
+    pub distributed_reward: U128,
+    /// The amount of undistributed reward which has not been swept.
+    pub reward_dust: U128,
+    /// The amount of undistributed reward which has been swept.
+    pub swept_reward_dust: U128,
+    /// The status of transferring the swept reward dust to the treasury account.
+    pub reward_dust_transfer_status: SweptRewardTransferStatus,
+    /// The incentive tokens allocated to the stakes of this era.
+    pub incentive_rewards: Vec<IncentiveRewardOfEra>,
     /// The status of creation of this set
     pub processing_status: ValidatorSetProcessingStatus,
 }
@@ -508,6 +950,41 @@ pub struct ValidatorProfile {
     pub profile: HashMap<String, String>,
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorCommission {
+    /// The commission percent which is currently effective.
+    pub commission_percent: u16,
+    /// The commission percent which will take effect in a later era.
+    pub pending_commission_percent: Option<u16>,
+    /// The era number from which the pending commission percent takes effect.
+    pub pending_effective_era_number: Option<U64>,
+}
+
+/// The upper limit of total delegated stake of a validator.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum DelegationCap {
+    /// The limit is an absolute amount of OCT token.
+    Absolute(U128),
+    /// The limit is a multiple of the deposit of the validator.
+    MultipleOfDeposit(u16),
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegationPolicy {
+    /// The accounts which are allowed to delegate to the validator.
+    /// If this is `None`, any account can delegate to the validator.
+    pub allowed_delegators: Option<Vec<AccountId>>,
+    /// The upper limit of total delegated stake of the validator.
+    pub maximum_delegated_stake: Option<DelegationCap>,
+    /// The maximum number of delegators of the validator.
+    pub maximum_delegator_count: Option<U64>,
+    /// The minimum amount of delegation of a delegator to the validator.
+    pub minimum_delegation: Option<U128>,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum AppchainNotification {
@@ -550,11 +1027,111 @@ pub struct AppchainNotificationHistory {
     pub index: U64,
 }
 
+/// The error code of a failed appchain message.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainMessageError {
+    /// The payload of the raw message can not be deserialized.
+    InvalidPayload {
+        reason: String,
+    },
+    /// The era number of message `EraPayout` is too old.
+    EraPayoutTooOld {
+        era_number: u32,
+    },
+    /// The era number of message `PlanNewEra` is too old.
+    SwitchingEraTooOld {
+        era_number: u32,
+    },
+    AssetTransferPaused,
+    InvalidContractAccount {
+        contract_account: String,
+    },
+    UnregisteredNearFungibleToken {
+        contract_account: String,
+    },
+    BridgingClosed {
+        contract_account: String,
+    },
+    ContractAccountOfWrappedAppchainTokenNotSet,
+    InvalidSenderIdInAppchain {
+        sender_id: String,
+    },
+    TooMuchWrappedAppchainTokenToMint,
+    UnregisteredNftClass {
+        class_id: String,
+    },
+    NearFungibleTokenTransferFailed,
+    WrappedAppchainTokenMintFailed,
+    WrappedAppchainNftTransferFailed,
+    WrappedAppchainNftMintFailed,
+    /// The contract call is not in the allowlist of contract calls.
+    ContractCallNotAllowed {
+        contract_id: AccountId,
+        method_name: String,
+    },
+    /// The contract is a token contract managed by this contract, or this contract itself,
+    /// which can not be called by the appchain.
+    ContractCallToProtectedContract {
+        contract_id: AccountId,
+    },
+    /// The prepaid gas of the contract call exceeds the gas budget.
+    ContractCallGasExceeded {
+        gas: U64,
+        maximum_gas: U64,
+    },
+    /// The attached deposit of the contract call exceeds the limit.
+    ContractCallDepositExceeded {
+        deposit: U128,
+        maximum_deposit: U128,
+    },
+    /// The available balance of this contract is not enough for the attached deposit.
+    InsufficientBalanceForContractCall,
+    /// The remaining gas budget of contract calls is not enough for the prepaid gas.
+    ContractCallGasBudgetExhausted {
+        gas: U64,
+        remaining_gas_budget: U64,
+    },
+    /// The remaining deposit budget of contract calls is not enough for the attached deposit.
+    ContractCallDepositBudgetExhausted {
+        deposit: U128,
+        remaining_deposit_budget: U128,
+    },
+    ContractCallFailed,
+    /// The validator set of the era in message `EraPayout` does not exist.
+    ValidatorSetNotExisted {
+        era_number: U64,
+    },
+    /// The validator set of the era in message `EraPayout` is not ready for distributing reward.
+    ValidatorSetNotReadyForDistributingReward {
+        era_number: U64,
+    },
+    /// An unprofitable validator in message `EraPayout` is not a validator of the era.
+    InvalidUnprofitableValidatorId {
+        era_number: U64,
+        validator_id_in_appchain: String,
+    },
+    /// The error recorded before the error codes are introduced.
+    Unclassified,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum AppchainMessageProcessingResult {
-    Ok { nonce: u32, message: Option<String> },
-    Error { nonce: u32, message: String },
+    Ok {
+        nonce: u32,
+        message: Option<String>,
+    },
+    Error {
+        nonce: u32,
+        error: AppchainMessageError,
+        message: String,
+    },
+    /// The message with the nonce is never staged, and is skipped by the owner.
+    Skipped {
+        nonce: u32,
+        reason: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -632,6 +1209,29 @@ pub struct UserStakingHistory {
     pub has_taken_effect: bool,
 }
 
+/// The staking action funded by another account on behalf of the beneficiary.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum FundedStakingAction {
+    RegisterValidator,
+    IncreaseStake,
+    RegisterDelegator { validator_id: AccountId },
+    IncreaseDelegation { validator_id: AccountId },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingStakingDeposit {
+    /// The account which deposited the OCT token for the staking action
+    pub funder_id: AccountId,
+    /// The staking action to be applied for the beneficiary
+    pub action: FundedStakingAction,
+    /// The amount of OCT token deposited for the staking action
+    pub deposit_amount: U128,
+    /// Whether the deposit is being refunded to the funder.
+    pub is_being_refunded: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum FTDepositMessage {
@@ -639,6 +1239,7 @@ pub enum FTDepositMessage {
         validator_id_in_appchain: String,
         can_be_delegated_to: bool,
         profile: HashMap<String, String>,
+        commission_percent: Option<u16>,
     },
     IncreaseStake,
     RegisterDelegator {
@@ -647,9 +1248,27 @@ pub enum FTDepositMessage {
     IncreaseDelegation {
         validator_id: AccountId,
     },
+    RegisterValidatorFor {
+        validator_id: AccountId,
+    },
+    IncreaseStakeFor {
+        validator_id: AccountId,
+    },
+    RegisterDelegatorFor {
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    },
+    IncreaseDelegationFor {
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    },
     BridgeToAppchain {
         receiver_id_in_appchain: String,
     },
+    FundIncentivePool {
+        eras: U64,
+        per_era_amount: U128,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone)]
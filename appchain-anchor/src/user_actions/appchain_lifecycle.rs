@@ -65,9 +65,13 @@ impl AppchainLifecycleManager for AppchainAnchor {
             "Missing settings of wrapped appchain token."
         );
         let appchain_settings = self.appchain_settings.get().unwrap();
+        let era_reward_is_required = match self.reward_schedule.get().unwrap() {
+            RewardSchedule::Fixed | RewardSchedule::Decaying { .. } => true,
+            RewardSchedule::StepTable { .. } | RewardSchedule::StakingRatioCurve { .. } => false,
+        };
         assert!(
             !(appchain_settings.rpc_endpoint.trim().is_empty()
-                || appchain_settings.era_reward.0 == 0),
+                || (era_reward_is_required && appchain_settings.era_reward.0 == 0)),
             "Missing appchain settings."
         );
         self.assert_light_client_initialized();
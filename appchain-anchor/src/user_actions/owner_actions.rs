@@ -1,4 +1,10 @@
 use crate::{interfaces::OwnerActions, *};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+pub trait RewardDustTransferResolver {
+    /// Resolver for transferring the swept reward dust of an era to the treasury account
+    fn resolve_reward_dust_transfer(&mut self, era_number: U64, amount: U128);
+}
 
 #[near_bindgen]
 impl OwnerActions for AppchainAnchor {
@@ -6,7 +12,33 @@ impl OwnerActions for AppchainAnchor {
     fn remove_validator_set_before(&mut self, era_number: U64) {
         self.assert_owner();
         let mut validator_set_histories = self.validator_set_histories.get().unwrap();
-        validator_set_histories.remove_before(&era_number.0);
+        // The rewards of delegators are calculated from the validator sets,
+        // so the validator sets in the range of withdrawable eras must be kept.
+        let end_era = validator_set_histories.index_range().end_index.0;
+        let maximum_era_count = self
+            .protocol_settings
+            .get()
+            .unwrap()
+            .maximum_era_count_of_unwithdrawn_reward
+            .0;
+        let start_era = match end_era > maximum_era_count {
+            true => end_era - maximum_era_count + 1,
+            false => 0,
+        };
+        assert!(
+            era_number.0 <= start_era,
+            "The validator sets of eras with withdrawable rewards can not be removed."
+        );
+        // The validator sets after a checkpoint are based on the checkpoint,
+        // so only the validator sets before a checkpoint can be removed.
+        let checkpoint_era = era_number.0 - era_number.0 % VALIDATOR_SET_CHECKPOINT_INTERVAL;
+        if checkpoint_era < era_number.0 {
+            log!(
+                "Only the validator sets before era '{}' are removed, as the validator sets after it are based on it.",
+                checkpoint_era
+            );
+        }
+        validator_set_histories.remove_before(&checkpoint_era);
         self.validator_set_histories.set(&validator_set_histories);
     }
     //
@@ -25,4 +57,97 @@ impl OwnerActions for AppchainAnchor {
         self.appchain_notification_histories
             .set(&appchain_notification_histories);
     }
+    //
+    fn sweep_reward_dust_of_era(&mut self, era_number: U64) {
+        self.assert_owner();
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let treasury_account = self
+            .protocol_settings
+            .get()
+            .unwrap()
+            .treasury_account
+            .expect("Treasury account is not set.");
+        let mut validator_set_histories = self.validator_set_histories.get().unwrap();
+        let mut validator_set = validator_set_histories
+            .get(&era_number.0)
+            .expect("The validator set of the given era is not found.");
+        assert!(
+            validator_set
+                .processing_status()
+                .eq(&ValidatorSetProcessingStatus::Completed),
+            "The reward of era {} is not completely distributed.",
+            era_number.0
+        );
+        assert!(
+            validator_set
+                .reward_dust_transfer_status()
+                .ne(&SweptRewardTransferStatus::Transferring),
+            "The reward dust of era {} is being transferred.",
+            era_number.0
+        );
+        let reward_dust = validator_set.sweep_reward_dust();
+        assert!(
+            reward_dust > 0,
+            "There is no reward dust in era {}.",
+            era_number.0
+        );
+        validator_set_histories.insert(&era_number.0, &validator_set);
+        ext_ft_core::ext(
+            self.wrapped_appchain_token
+                .get()
+                .unwrap()
+                .contract_account
+                .unwrap(),
+        )
+        .with_attached_deposit(1)
+        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+        .with_unused_gas_weight(0)
+        .ft_transfer(treasury_account, reward_dust.into(), None)
+        .then(
+            ext_self::ext(env::current_account_id())
+                .with_attached_deposit(0)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                .with_unused_gas_weight(0)
+                .resolve_reward_dust_transfer(era_number, U128::from(reward_dust)),
+        );
+    }
+    //
+    #[payable]
+    fn deposit_to_storage_fund(&mut self) {
+        self.assert_owner();
+        let amount = env::attached_deposit();
+        assert!(amount > 0, "Attached deposit must be greater than 0.");
+        self.storage_fund_balance += amount;
+        log!(
+            "Storage fund is topped up by '{}'. Balance: '{}'.",
+            amount,
+            self.storage_fund_balance
+        );
+    }
+}
+
+#[near_bindgen]
+impl RewardDustTransferResolver for AppchainAnchor {
+    //
+    fn resolve_reward_dust_transfer(&mut self, era_number: U64, amount: U128) {
+        assert_self();
+        let succeeded = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                log!(
+                    "Failed to transfer the reward dust of era {}. Amount: '{}'.",
+                    era_number.0,
+                    amount.0
+                );
+                false
+            }
+        };
+        let mut validator_set_histories = self.validator_set_histories.get().unwrap();
+        if let Some(mut validator_set) = validator_set_histories.get(&era_number.0) {
+            validator_set.resolve_reward_dust_transfer(amount.0, succeeded);
+            validator_set_histories.insert(&era_number.0, &validator_set);
+        }
+    }
 }
@@ -1,8 +1,12 @@
 use crate::{
-    interfaces::{AnchorSettingsManager, AppchainSettingsManager, ProtocolSettingsManager},
+    interfaces::{
+        AnchorSettingsManager, AppchainSettingsManager, ContractCallSettingsManager,
+        ProtocolSettingsManager, SlashingSettingsManager,
+    },
     *,
 };
 use core::convert::From;
+use near_sdk::borsh::maybestd::collections::HashMap;
 
 impl Default for ProtocolSettings {
     fn default() -> Self {
@@ -24,6 +28,25 @@ impl Default for ProtocolSettings {
             maximum_era_count_of_valid_appchain_message: U64::from(7),
             validator_commission_percent: 20,
             maximum_allowed_unprofitable_era_count: 3,
+            minimum_validator_commission_percent: 0,
+            maximum_validator_commission_percent: 100,
+            maximum_validator_commission_percent_change_per_era: 5,
+            validator_commission_change_notice_era_count: U64::from(2),
+            validator_entry_limit: ValidatorChurnLimit {
+                maximum_validator_count: Some(U64::from(3)),
+                maximum_validator_count_percent: Some(25),
+                maximum_stake: None,
+                maximum_stake_percent: None,
+            },
+            validator_exit_limit: ValidatorChurnLimit {
+                maximum_validator_count: Some(U64::from(1)),
+                maximum_validator_count_percent: Some(10),
+                maximum_stake: None,
+                maximum_stake_percent: Some(10),
+            },
+            treasury_account: None,
+            treasury_reward_percent: 0,
+            unprofitable_validator_reward_policy: UnprofitableValidatorRewardPolicy::Redistribute,
         }
     }
 }
@@ -38,6 +61,35 @@ impl Default for AnchorSettings {
     }
 }
 
+impl Default for SlashingSettings {
+    fn default() -> Self {
+        Self {
+            slash_percents_of_offence_kinds: HashMap::new(),
+            slash_delegators: false,
+            offender_jail_era_count: U64::from(0),
+            slashed_token_destination: SlashedTokenDestination::Lock,
+            equivocation_penalty: EquivocationPenalty {
+                slash_percent: 10,
+                force_unbond: true,
+                jail_era_count: U64::from(0),
+            },
+            equivocation_challenger_bounty_percent: 10,
+        }
+    }
+}
+
+impl Default for ContractCallSettings {
+    fn default() -> Self {
+        Self {
+            allowed_methods: HashMap::new(),
+            maximum_gas: U64::from(Gas::ONE_TERA.mul(20).0),
+            maximum_deposit: U128::from(0),
+            remaining_gas_budget: U64::from(0),
+            remaining_deposit_budget: U128::from(0),
+        }
+    }
+}
+
 impl Default for AppchainSettings {
     fn default() -> Self {
         Self {
@@ -265,6 +317,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             value != protocol_settings.validator_commission_percent,
             "The value is not changed."
         );
+        assert!(
+            value >= protocol_settings.minimum_validator_commission_percent
+                && value <= protocol_settings.maximum_validator_commission_percent,
+            "The value should be between `minimum_validator_commission_percent` and `maximum_validator_commission_percent`."
+        );
         protocol_settings.validator_commission_percent = value;
         self.protocol_settings.set(&protocol_settings);
     }
@@ -283,6 +340,133 @@ impl ProtocolSettingsManager for AppchainAnchor {
         protocol_settings.maximum_allowed_unprofitable_era_count = value;
         self.protocol_settings.set(&protocol_settings);
     }
+    //
+    fn change_minimum_validator_commission_percent(&mut self, value: u16) {
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.minimum_validator_commission_percent,
+            "The value is not changed."
+        );
+        assert!(
+            value <= protocol_settings.validator_commission_percent,
+            "The value should not be greater than `validator_commission_percent`."
+        );
+        protocol_settings.minimum_validator_commission_percent = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_maximum_validator_commission_percent(&mut self, value: u16) {
+        self.assert_owner();
+        assert!(value <= 100, "Invalid percent value.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.maximum_validator_commission_percent,
+            "The value is not changed."
+        );
+        assert!(
+            value >= protocol_settings.validator_commission_percent,
+            "The value should not be less than `validator_commission_percent`."
+        );
+        protocol_settings.maximum_validator_commission_percent = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_maximum_validator_commission_percent_change_per_era(&mut self, value: u16) {
+        self.assert_owner();
+        assert!(value <= 100, "Invalid percent value.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.maximum_validator_commission_percent_change_per_era,
+            "The value is not changed."
+        );
+        protocol_settings.maximum_validator_commission_percent_change_per_era = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_validator_commission_change_notice_era_count(&mut self, value: U64) {
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0
+                != protocol_settings
+                    .validator_commission_change_notice_era_count
+                    .0,
+            "The value is not changed."
+        );
+        protocol_settings.validator_commission_change_notice_era_count = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_validator_entry_limit(&mut self, value: ValidatorChurnLimit) {
+        self.assert_owner();
+        value.assert_valid();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.validator_entry_limit,
+            "The value is not changed."
+        );
+        protocol_settings.validator_entry_limit = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_validator_exit_limit(&mut self, value: ValidatorChurnLimit) {
+        self.assert_owner();
+        value.assert_valid();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.validator_exit_limit,
+            "The value is not changed."
+        );
+        protocol_settings.validator_exit_limit = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_treasury_account(&mut self, value: AccountId) {
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            protocol_settings
+                .treasury_account
+                .as_ref()
+                .map_or(true, |account| *account != value),
+            "The value is not changed."
+        );
+        protocol_settings.treasury_account = Some(value);
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_treasury_reward_percent(&mut self, value: u16) {
+        self.assert_owner();
+        assert!(value < 100, "Invalid percent value.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.treasury_reward_percent,
+            "The value is not changed."
+        );
+        protocol_settings.treasury_reward_percent = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
+    //
+    fn change_unprofitable_validator_reward_policy(
+        &mut self,
+        value: UnprofitableValidatorRewardPolicy,
+    ) {
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.unprofitable_validator_reward_policy,
+            "The value is not changed."
+        );
+        if value == UnprofitableValidatorRewardPolicy::Treasury {
+            assert!(
+                protocol_settings.treasury_account.is_some(),
+                "Treasury account is not set."
+            );
+        }
+        protocol_settings.unprofitable_validator_reward_policy = value;
+        self.protocol_settings.set(&protocol_settings);
+    }
 }
 
 #[near_bindgen]
@@ -309,12 +493,69 @@ impl AppchainSettingsManager for AppchainAnchor {
         self.appchain_settings.set(&appchain_settings);
     }
     //
+    fn set_reward_schedule(&mut self, reward_schedule: RewardSchedule) {
+        self.assert_owner();
+        match &reward_schedule {
+            RewardSchedule::Fixed => (),
+            RewardSchedule::StepTable { steps } => {
+                assert!(!steps.is_empty(), "The steps can not be empty.");
+                assert!(
+                    steps
+                        .windows(2)
+                        .all(|pair| pair[0].start_era_number.0 < pair[1].start_era_number.0),
+                    "The start era numbers of steps must be in ascending order."
+                );
+            }
+            RewardSchedule::Decaying {
+                decay_era_count,
+                decay_percent,
+            } => {
+                assert!(decay_era_count.0 > 0, "Invalid decay era count.");
+                assert!(
+                    *decay_percent > 0 && *decay_percent <= 100,
+                    "Invalid percent value."
+                );
+            }
+            RewardSchedule::StakingRatioCurve {
+                minimum_annual_inflation,
+                maximum_annual_inflation,
+                ideal_staking_ratio,
+                era_count_per_year,
+            } => {
+                assert!(
+                    minimum_annual_inflation <= maximum_annual_inflation
+                        && *maximum_annual_inflation <= 10000,
+                    "Invalid annual inflation."
+                );
+                assert!(
+                    *ideal_staking_ratio > 0 && *ideal_staking_ratio <= 100,
+                    "Invalid percent value."
+                );
+                assert!(era_count_per_year.0 > 0, "Invalid era count per year.");
+            }
+        }
+        assert!(
+            self.reward_schedule.get().unwrap() != reward_schedule,
+            "The value is not changed."
+        );
+        self.reward_schedule.set(&reward_schedule);
+    }
+    //
     fn set_bonus_for_new_validator(&mut self, bonus_amount: U128) {
         self.assert_owner();
         let mut appchain_settings = self.appchain_settings.get().unwrap();
         appchain_settings.bonus_for_new_validator = bonus_amount;
         self.appchain_settings.set(&appchain_settings);
     }
+    //
+    fn set_payload_codec(&mut self, payload_codec: PayloadCodec) {
+        self.assert_owner();
+        assert!(
+            self.payload_codec != payload_codec,
+            "The value is not changed."
+        );
+        self.payload_codec = payload_codec;
+    }
 }
 
 #[near_bindgen]
@@ -364,3 +605,190 @@ impl AnchorSettingsManager for AppchainAnchor {
         self.anchor_settings.set(&anchor_settings);
     }
 }
+
+#[near_bindgen]
+impl SlashingSettingsManager for AppchainAnchor {
+    //
+    fn set_slash_percent_of_offence_kind(&mut self, offence_kind: String, slash_percent: u16) {
+        self.assert_owner();
+        assert!(
+            slash_percent > 0 && slash_percent <= 100,
+            "Invalid percent value."
+        );
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            slashing_settings
+                .slash_percents_of_offence_kinds
+                .get(&offence_kind)
+                .map_or(true, |value| *value != slash_percent),
+            "The value is not changed."
+        );
+        slashing_settings
+            .slash_percents_of_offence_kinds
+            .insert(offence_kind, slash_percent);
+        self.slashing_settings.set(&slashing_settings);
+    }
+    //
+    fn remove_slash_percent_of_offence_kind(&mut self, offence_kind: String) {
+        self.assert_owner();
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            slashing_settings
+                .slash_percents_of_offence_kinds
+                .remove(&offence_kind)
+                .is_some(),
+            "Offence kind '{}' is not slashed.",
+            offence_kind
+        );
+        self.slashing_settings.set(&slashing_settings);
+    }
+    //
+    fn turn_on_delegators_slashing(&mut self) {
+        self.assert_owner();
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            !slashing_settings.slash_delegators,
+            "Delegators slashing is already turned on."
+        );
+        slashing_settings.slash_delegators = true;
+        self.slashing_settings.set(&slashing_settings);
+    }
+    //
+    fn turn_off_delegators_slashing(&mut self) {
+        self.assert_owner();
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            slashing_settings.slash_delegators,
+            "Delegators slashing is already turned off."
+        );
+        slashing_settings.slash_delegators = false;
+        self.slashing_settings.set(&slashing_settings);
+    }
+    //
+    fn set_slashed_token_destination(&mut self, destination: SlashedTokenDestination) {
+        self.assert_owner();
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            slashing_settings.slashed_token_destination != destination,
+            "The value is not changed."
+        );
+        slashing_settings.slashed_token_destination = destination;
+        self.slashing_settings.set(&slashing_settings);
+    }
+    //
+    fn set_offender_jail_era_count(&mut self, value: U64) {
+        self.assert_owner();
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            value.0 != slashing_settings.offender_jail_era_count.0,
+            "The value is not changed."
+        );
+        slashing_settings.offender_jail_era_count = value;
+        self.slashing_settings.set(&slashing_settings);
+    }
+    //
+    fn set_equivocation_penalty(&mut self, penalty: EquivocationPenalty) {
+        self.assert_owner();
+        assert!(penalty.slash_percent <= 100, "Invalid percent value.");
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            slashing_settings.equivocation_penalty != penalty,
+            "The value is not changed."
+        );
+        slashing_settings.equivocation_penalty = penalty;
+        self.slashing_settings.set(&slashing_settings);
+    }
+    //
+    fn set_equivocation_challenger_bounty_percent(&mut self, value: u16) {
+        self.assert_owner();
+        assert!(value <= 100, "Invalid percent value.");
+        let mut slashing_settings = self.slashing_settings.get().unwrap();
+        assert!(
+            value != slashing_settings.equivocation_challenger_bounty_percent,
+            "The value is not changed."
+        );
+        slashing_settings.equivocation_challenger_bounty_percent = value;
+        self.slashing_settings.set(&slashing_settings);
+    }
+}
+
+#[near_bindgen]
+impl ContractCallSettingsManager for AppchainAnchor {
+    //
+    fn allow_contract_call(&mut self, contract_id: AccountId, method_name: String) {
+        self.assert_owner();
+        assert!(!method_name.is_empty(), "Method name can not be empty.");
+        assert!(
+            !self.is_protected_contract(&contract_id),
+            "Contract '{}' is protected from being called by appchain.",
+            contract_id
+        );
+        let mut contract_call_settings = self.contract_call_settings.get().unwrap();
+        let methods = contract_call_settings
+            .allowed_methods
+            .entry(contract_id.clone())
+            .or_insert(Vec::new());
+        assert!(
+            !methods.contains(&method_name),
+            "Method '{}' of contract '{}' is already allowed.",
+            method_name,
+            contract_id
+        );
+        methods.push(method_name);
+        self.contract_call_settings.set(&contract_call_settings);
+    }
+    //
+    fn disallow_contract_call(&mut self, contract_id: AccountId, method_name: String) {
+        self.assert_owner();
+        let mut contract_call_settings = self.contract_call_settings.get().unwrap();
+        let methods = contract_call_settings
+            .allowed_methods
+            .get_mut(&contract_id)
+            .filter(|methods| methods.contains(&method_name))
+            .unwrap_or_else(|| {
+                panic!(
+                    "Method '{}' of contract '{}' is not allowed.",
+                    method_name, contract_id
+                )
+            });
+        methods.retain(|method| !method.eq(&method_name));
+        if methods.is_empty() {
+            contract_call_settings.allowed_methods.remove(&contract_id);
+        }
+        self.contract_call_settings.set(&contract_call_settings);
+    }
+    //
+    fn set_maximum_gas_of_contract_call(&mut self, value: U64) {
+        self.assert_owner();
+        assert!(
+            value.0 > 0 && value.0 <= Gas::ONE_TERA.mul(T_GAS_CAP_FOR_CONTRACT_CALL).0,
+            "Invalid gas value."
+        );
+        let mut contract_call_settings = self.contract_call_settings.get().unwrap();
+        assert!(
+            value.0 != contract_call_settings.maximum_gas.0,
+            "The value is not changed."
+        );
+        contract_call_settings.maximum_gas = value;
+        self.contract_call_settings.set(&contract_call_settings);
+    }
+    //
+    fn set_maximum_deposit_of_contract_call(&mut self, value: U128) {
+        self.assert_owner();
+        let mut contract_call_settings = self.contract_call_settings.get().unwrap();
+        assert!(
+            value.0 != contract_call_settings.maximum_deposit.0,
+            "The value is not changed."
+        );
+        contract_call_settings.maximum_deposit = value;
+        self.contract_call_settings.set(&contract_call_settings);
+    }
+    //
+    fn set_contract_call_budget(&mut self, gas: U64, deposit: U128) {
+        self.assert_owner();
+        let mut contract_call_settings = self.contract_call_settings.get().unwrap();
+        contract_call_settings.remaining_gas_budget = gas;
+        contract_call_settings.remaining_deposit_budget = deposit;
+        self.contract_call_settings.set(&contract_call_settings);
+    }
+}
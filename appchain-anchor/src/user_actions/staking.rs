@@ -6,6 +6,11 @@ use std::str::FromStr;
 
 const SUB_ACCOUNT_ID_OF_WAT_FAUCET: &str = "wat-faucet";
 
+pub trait StakingDepositRefundResolver {
+    /// Resolver for refunding a cancelled staking deposit to its funder
+    fn resolve_staking_deposit_refund(&mut self, beneficiary_id: AccountId);
+}
+
 impl AppchainAnchor {
     //
     pub fn internal_process_oct_deposit(
@@ -19,6 +24,7 @@ impl AppchainAnchor {
                 validator_id_in_appchain,
                 can_be_delegated_to,
                 profile,
+                commission_percent,
             } => {
                 self.register_validator(
                     sender_id,
@@ -26,6 +32,7 @@ impl AppchainAnchor {
                     profile,
                     amount,
                     can_be_delegated_to,
+                    commission_percent,
                 );
                 PromiseOrValue::Value(0.into())
             }
@@ -41,6 +48,48 @@ impl AppchainAnchor {
                 self.increase_delegation(sender_id, validator_id, amount);
                 PromiseOrValue::Value(0.into())
             }
+            FTDepositMessage::RegisterValidatorFor { validator_id } => {
+                self.add_pending_staking_deposit(
+                    sender_id,
+                    validator_id,
+                    FundedStakingAction::RegisterValidator,
+                    amount,
+                );
+                PromiseOrValue::Value(0.into())
+            }
+            FTDepositMessage::IncreaseStakeFor { validator_id } => {
+                self.add_pending_staking_deposit(
+                    sender_id,
+                    validator_id,
+                    FundedStakingAction::IncreaseStake,
+                    amount,
+                );
+                PromiseOrValue::Value(0.into())
+            }
+            FTDepositMessage::RegisterDelegatorFor {
+                delegator_id,
+                validator_id,
+            } => {
+                self.add_pending_staking_deposit(
+                    sender_id,
+                    delegator_id,
+                    FundedStakingAction::RegisterDelegator { validator_id },
+                    amount,
+                );
+                PromiseOrValue::Value(0.into())
+            }
+            FTDepositMessage::IncreaseDelegationFor {
+                delegator_id,
+                validator_id,
+            } => {
+                self.add_pending_staking_deposit(
+                    sender_id,
+                    delegator_id,
+                    FundedStakingAction::IncreaseDelegation { validator_id },
+                    amount,
+                );
+                PromiseOrValue::Value(0.into())
+            }
             _ => panic!(
                 "Internal error: misuse of internal function 'internal_process_oct_deposit'."
             ),
@@ -54,7 +103,8 @@ impl AppchainAnchor {
         profile: HashMap<String, String>,
         deposit_amount: U128,
         can_be_delegated_to: bool,
-    ) {
+        commission_percent: Option<u16>,
+    ) -> StakingHistory {
         match self.appchain_state {
             AppchainState::Staging | AppchainState::Active => (),
             _ => panic!(
@@ -73,6 +123,14 @@ impl AppchainAnchor {
             &validator_id
         );
         let mut validator_profiles = self.validator_profiles.get().unwrap();
+        if let Some(operated_validator_id) =
+            validator_profiles.get_validator_id_by_operator(&validator_id)
+        {
+            panic!(
+                "The account '{}' is the operator of validator '{}'.",
+                &validator_id, &operated_validator_id
+            );
+        }
         let formatted_validator_id_in_appchain = AccountIdInAppchain::new(
             Some(validator_id_in_appchain.clone()),
             &self.appchain_template_type,
@@ -89,23 +147,22 @@ impl AppchainAnchor {
         }
         self.assert_validator_stake_is_valid(deposit_amount.0, None);
         let protocol_settings = self.protocol_settings.get().unwrap();
-        assert!(
-            next_validator_set.validator_count() < protocol_settings.maximum_validator_count.0,
-            "The count of registered validators exceeds the upper limit."
-        );
+        if let Some(commission_percent) = commission_percent {
+            assert!(
+                commission_percent >= protocol_settings.minimum_validator_commission_percent
+                    && commission_percent <= protocol_settings.maximum_validator_commission_percent,
+                "The commission percent should be between {} and {}.",
+                protocol_settings.minimum_validator_commission_percent,
+                protocol_settings.maximum_validator_commission_percent
+            );
+        }
         //
         if self.appchain_state.eq(&AppchainState::Active) {
-            let validator_set_histories = self.validator_set_histories.get().unwrap();
-            let latest_validator_set = validator_set_histories
-                .get(&validator_set_histories.end_index)
-                .unwrap();
-            if latest_validator_set.validator_count() >= 12 {
-                assert!(
-                    next_validator_set.validator_count()
-                        <= latest_validator_set.validator_count() * 5 / 4,
-                    "Too many new validators in this era. Please try again in the next era."
-                );
-            }
+            self.assert_validator_entry_is_allowed(
+                &protocol_settings,
+                &next_validator_set,
+                deposit_amount.0,
+            );
         }
         //
         let staking_history = self.record_staking_fact(StakingFact::ValidatorRegistered {
@@ -119,6 +176,15 @@ impl AppchainAnchor {
         //
         self.sync_state_to_registry();
         //
+        let mut validator_commissions = self.validator_commissions.get().unwrap();
+        match commission_percent {
+            Some(commission_percent) => {
+                validator_commissions.set_commission_percent(&validator_id, commission_percent)
+            }
+            None => validator_commissions.remove(&validator_id),
+        }
+        self.validator_commissions.set(&validator_commissions);
+        //
         validator_profiles.insert(ValidatorProfile {
             validator_id,
             validator_id_in_appchain: formatted_validator_id_in_appchain.to_string(),
@@ -156,9 +222,10 @@ impl AppchainAnchor {
                 Gas::ONE_TERA.mul(T_GAS_FOR_BURN_WRAPPED_APPCHAIN_TOKEN),
             );
         }
+        staking_history
     }
     //
-    fn increase_stake(&mut self, validator_id: AccountId, amount: U128) {
+    fn increase_stake(&mut self, validator_id: AccountId, amount: U128) -> StakingHistory {
         match self.appchain_state {
             AppchainState::Staging | AppchainState::Active => (),
             _ => panic!(
@@ -194,6 +261,8 @@ impl AppchainAnchor {
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
+        //
+        staking_history
     }
     //
     fn register_delegator(
@@ -201,7 +270,7 @@ impl AppchainAnchor {
         delegator_id: AccountId,
         validator_id: AccountId,
         deposit_amount: U128,
-    ) {
+    ) -> StakingHistory {
         match self.appchain_state {
             AppchainState::Staging | AppchainState::Active => (),
             _ => panic!(
@@ -233,6 +302,12 @@ impl AppchainAnchor {
             deposit_amount.0 >= protocol_settings.minimum_delegator_deposit.0,
             "The deposit for registering delegator is too few."
         );
+        self.assert_delegation_policy_is_satisfied(
+            &next_validator_set,
+            &delegator_id,
+            &validator_id,
+            deposit_amount.0,
+        );
         self.assert_validator_stake_is_valid(
             validator.deposit_amount,
             Some(validator.total_stake + deposit_amount.0),
@@ -247,9 +322,72 @@ impl AppchainAnchor {
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
+        //
+        staking_history
+    }
+    // Assert the delegation of the given amount is allowed by
+    // the delegation policy of the given validator.
+    fn assert_delegation_policy_is_satisfied(
+        &self,
+        next_validator_set: &NextValidatorSet,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+        amount: u128,
+    ) {
+        let policy = match self.delegation_policies.get(validator_id) {
+            Some(policy) => policy,
+            None => return,
+        };
+        if let Some(allowed_delegators) = &policy.allowed_delegators {
+            assert!(
+                allowed_delegators.contains(delegator_id),
+                "The account '{}' is not allowed to delegate to validator '{}'.",
+                delegator_id,
+                validator_id
+            );
+        }
+        let deposit_amount_of_delegator =
+            match next_validator_set.get_delegator(delegator_id, validator_id) {
+                Some(delegator) => delegator.deposit_amount + amount,
+                None => {
+                    if let Some(maximum_delegator_count) = &policy.maximum_delegator_count {
+                        assert!(
+                            next_validator_set.get_delegator_count_of(validator_id)
+                                < maximum_delegator_count.0,
+                            "Too many delegators of validator '{}'.",
+                            validator_id
+                        );
+                    }
+                    amount
+                }
+            };
+        if let Some(minimum_delegation) = &policy.minimum_delegation {
+            assert!(
+                deposit_amount_of_delegator >= minimum_delegation.0,
+                "The delegation to validator '{}' should not be less than {} OCT.",
+                validator_id,
+                minimum_delegation.0 / OCT_DECIMALS_VALUE
+            );
+        }
+        if let Some(maximum_delegated_stake) = &policy.maximum_delegated_stake {
+            let validator = next_validator_set.get_validator(validator_id).unwrap();
+            let maximum_delegated_amount = match maximum_delegated_stake {
+                DelegationCap::Absolute(value) => value.0,
+                DelegationCap::MultipleOfDeposit(multiple) => {
+                    validator.deposit_amount * u128::from(*multiple)
+                }
+            };
+            assert!(
+                validator.total_stake - validator.deposit_amount + amount
+                    <= maximum_delegated_amount,
+                "The total delegation of validator '{}' exceeds the upper limit.",
+                validator_id
+            );
+        }
     }
     //
     pub fn record_staking_fact(&mut self, staking_fact: StakingFact) -> StakingHistory {
+        self.assert_delegators_are_not_being_slashed(&staking_fact);
         //
         let mut staking_histories = self.staking_histories.get().unwrap();
         let staking_history = staking_histories.append(&mut StakingHistory {
@@ -272,7 +410,7 @@ impl AppchainAnchor {
         delegator_id: AccountId,
         validator_id: AccountId,
         amount: U128,
-    ) {
+    ) -> StakingHistory {
         match self.appchain_state {
             AppchainState::Staging | AppchainState::Active => (),
             _ => panic!(
@@ -294,6 +432,12 @@ impl AppchainAnchor {
         );
         let mut next_validator_set = self.next_validator_set.get().unwrap();
         self.assert_delegator_id(&delegator_id, &validator_id, &next_validator_set);
+        self.assert_delegation_policy_is_satisfied(
+            &next_validator_set,
+            &delegator_id,
+            &validator_id,
+            amount.0,
+        );
         let validator = next_validator_set.get_validator(&validator_id).unwrap();
         self.assert_validator_stake_is_valid(
             validator.deposit_amount,
@@ -309,6 +453,81 @@ impl AppchainAnchor {
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
+        //
+        staking_history
+    }
+    // Keep the deposit for a staking action on behalf of another account,
+    // until the beneficiary accepts or either side cancels it.
+    fn add_pending_staking_deposit(
+        &mut self,
+        funder_id: AccountId,
+        beneficiary_id: AccountId,
+        action: FundedStakingAction,
+        deposit_amount: U128,
+    ) {
+        assert!(
+            !self.pending_staking_deposits.contains_key(&beneficiary_id),
+            "There is already a pending staking deposit for '{}'.",
+            &beneficiary_id
+        );
+        if let FundedStakingAction::RegisterValidator = action {
+            assert!(
+                !self
+                    .next_validator_set
+                    .get()
+                    .unwrap()
+                    .contains_validator(&beneficiary_id),
+                "The account '{}' has already been registered.",
+                &beneficiary_id
+            );
+            self.assert_validator_stake_is_valid(deposit_amount.0, None);
+        }
+        self.pending_staking_deposits.insert(
+            &beneficiary_id,
+            &PendingStakingDeposit {
+                funder_id: funder_id.clone(),
+                action,
+                deposit_amount,
+                is_being_refunded: false,
+            },
+        );
+        log!(
+            "Staking deposit for '{}' funded by '{}' is waiting for acceptance. Deposit: '{}'.",
+            &beneficiary_id,
+            &funder_id,
+            deposit_amount.0
+        );
+    }
+    // Remove the pending staking deposit for the beneficiary to accept it.
+    fn remove_pending_staking_deposit_of(
+        &mut self,
+        beneficiary_id: &AccountId,
+    ) -> PendingStakingDeposit {
+        let pending_deposit = self
+            .pending_staking_deposits
+            .remove(beneficiary_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "There is no pending staking deposit for '{}'.",
+                    beneficiary_id
+                )
+            });
+        assert!(
+            !pending_deposit.is_being_refunded,
+            "The staking deposit for '{}' is being refunded.",
+            beneficiary_id
+        );
+        pending_deposit
+    }
+    //
+    fn record_funder_of_staking_history(
+        &mut self,
+        funder_id: &AccountId,
+        staking_history: &StakingHistory,
+    ) {
+        let mut user_staking_histories = self.user_staking_histories.get().unwrap();
+        user_staking_histories.add_funder_of_staking_history(funder_id, staking_history);
+        self.user_staking_histories.set(&user_staking_histories);
     }
 }
 
@@ -367,11 +586,17 @@ impl StakingManager for AppchainAnchor {
         let mut next_validator_set = self.next_validator_set.get().unwrap();
         let protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
-            next_validator_set.validator_count() > protocol_settings.minimum_validator_count.0,
+            next_validator_set.get_remaining_validator_count()
+                > protocol_settings.minimum_validator_count.0,
             "Too few validators. Cannot unbond any more."
         );
         let validator_id = env::predecessor_account_id();
         self.assert_validator_id(&validator_id, &next_validator_set);
+        assert!(
+            !next_validator_set.is_queued_for_unbonding(&validator_id),
+            "Validator '{}' is already queued for unbonding.",
+            &validator_id
+        );
         self.record_unbonding_validator(
             &protocol_settings,
             &mut next_validator_set,
@@ -383,7 +608,7 @@ impl StakingManager for AppchainAnchor {
     //
     fn enable_delegation(&mut self) {
         let mut next_validator_set = self.next_validator_set.get().unwrap();
-        let validator_id = env::predecessor_account_id();
+        let validator_id = self.get_operated_validator_id(&next_validator_set);
         self.assert_validator_id(&validator_id, &next_validator_set);
         let staking_history =
             self.record_staking_fact(StakingFact::ValidatorDelegationEnabled { validator_id });
@@ -396,7 +621,7 @@ impl StakingManager for AppchainAnchor {
     //
     fn disable_delegation(&mut self) {
         let mut next_validator_set = self.next_validator_set.get().unwrap();
-        let validator_id = env::predecessor_account_id();
+        let validator_id = self.get_operated_validator_id(&next_validator_set);
         self.assert_validator_id(&validator_id, &next_validator_set);
         let staking_history =
             self.record_staking_fact(StakingFact::ValidatorDelegationDisabled { validator_id });
@@ -484,6 +709,373 @@ impl StakingManager for AppchainAnchor {
         self.sync_state_to_registry();
     }
     //
+    fn redelegate(
+        &mut self,
+        from_validator_id: AccountId,
+        to_validator_id: AccountId,
+        amount: U128,
+    ) {
+        match self.appchain_state {
+            AppchainState::Staging | AppchainState::Active => (),
+            _ => panic!(
+                "Cannot redelegate while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        assert!(
+            !from_validator_id.eq(&to_validator_id),
+            "Cannot redelegate to the same validator."
+        );
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            amount.0
+                >= protocol_settings
+                    .minimum_delegator_deposit_changing_amount
+                    .0,
+            "Too few amount to redelegate. It should not be less than {} OCT.",
+            protocol_settings
+                .minimum_delegator_deposit_changing_amount
+                .0
+                / OCT_DECIMALS_VALUE
+        );
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let delegator_id = env::predecessor_account_id();
+        self.assert_delegator_id(&delegator_id, &from_validator_id, &next_validator_set);
+        self.assert_validator_id(&to_validator_id, &next_validator_set);
+        let delegator = next_validator_set
+            .get_delegator(&delegator_id, &from_validator_id)
+            .unwrap();
+        assert!(
+            delegator.deposit_amount >= amount.0,
+            "Unable to redelegate so much stake."
+        );
+        let remained_amount = delegator.deposit_amount - amount.0;
+        assert!(
+            remained_amount == 0
+                || remained_amount >= protocol_settings.minimum_delegator_deposit.0,
+            "The remained delegation to validator '{}' is too few.",
+            &from_validator_id
+        );
+        let validator = next_validator_set.get_validator(&to_validator_id).unwrap();
+        assert!(
+            validator.can_be_delegated_to,
+            "Validator '{}' cannot be delegated to.",
+            &to_validator_id
+        );
+        if !next_validator_set.contains_delegator(&delegator_id, &to_validator_id) {
+            let validator_count = match remained_amount == 0 {
+                true => next_validator_set.get_validator_count_of(&delegator_id) - 1,
+                false => next_validator_set.get_validator_count_of(&delegator_id),
+            };
+            assert!(
+                validator_count < protocol_settings.maximum_validators_per_delegator.0,
+                "Too many validators delegated."
+            );
+            assert!(
+                amount.0 >= protocol_settings.minimum_delegator_deposit.0,
+                "The deposit for registering delegator is too few."
+            );
+        }
+        self.assert_delegation_policy_is_satisfied(
+            &next_validator_set,
+            &delegator_id,
+            &to_validator_id,
+            amount.0,
+        );
+        self.assert_validator_stake_is_valid(
+            validator.deposit_amount,
+            Some(validator.total_stake + amount.0),
+        );
+        let staking_history = self.record_staking_fact(StakingFact::DelegationRedelegated {
+            delegator_id,
+            old_validator_id: from_validator_id,
+            new_validator_id: to_validator_id,
+            amount,
+        });
+        //
+        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
+    //
+    fn rebond_stake(&mut self, staking_history_index: U64) {
+        match self.appchain_state {
+            AppchainState::Staging | AppchainState::Active => (),
+            _ => panic!(
+                "Cannot rebond stake while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        let validator_id = env::predecessor_account_id();
+        let staking_history =
+            self.take_unbonded_stake_reference(&validator_id, staking_history_index.0);
+        let amount = match staking_history.staking_fact {
+            StakingFact::StakeDecreased {
+                validator_id: unbonded_validator_id,
+                amount,
+            }
+            | StakingFact::ValidatorUnbonded {
+                validator_id: unbonded_validator_id,
+                amount,
+            }
+            | StakingFact::ValidatorAutoUnbonded {
+                validator_id: unbonded_validator_id,
+                amount,
+            } => {
+                assert!(
+                    validator_id.eq(&unbonded_validator_id),
+                    "Invalid staking history for validator '{}'.",
+                    validator_id
+                );
+                amount
+            }
+            _ => panic!(
+                "Staking history '{}' is not an unbonded stake of a validator.",
+                staking_history_index.0
+            ),
+        };
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let validator_profiles = self.validator_profiles.get().unwrap();
+        if next_validator_set.contains_validator(&validator_id) {
+            assert!(
+                amount.0
+                    >= protocol_settings
+                        .minimum_validator_deposit_changing_amount
+                        .0,
+                "Too few amount to rebond. It should not be less than {} OCT.",
+                protocol_settings
+                    .minimum_validator_deposit_changing_amount
+                    .0
+                    / OCT_DECIMALS_VALUE
+            );
+            let validator = next_validator_set.get_validator(&validator_id).unwrap();
+            self.assert_validator_stake_is_valid(
+                validator.deposit_amount + amount.0,
+                Some(validator.total_stake + amount.0),
+            );
+        } else {
+            assert!(
+                !next_validator_set
+                    .validator_set()
+                    .contains_validator(&validator_id),
+                "Validator '{}' is unbonding in next era.",
+                &validator_id
+            );
+            if let Some(operated_validator_id) =
+                validator_profiles.get_validator_id_by_operator(&validator_id)
+            {
+                panic!(
+                    "The account '{}' is the operator of validator '{}'.",
+                    &validator_id, &operated_validator_id
+                );
+            }
+            self.assert_validator_stake_is_valid(amount.0, Some(amount.0));
+            if self.appchain_state.eq(&AppchainState::Active) {
+                self.assert_validator_entry_is_allowed(
+                    &protocol_settings,
+                    &next_validator_set,
+                    amount.0,
+                );
+            }
+        }
+        let validator_profile = validator_profiles
+            .get(&validator_id)
+            .expect("Missing validator profile.");
+        if let Some(profile) =
+            validator_profiles.get_by_id_in_appchain(&validator_profile.validator_id_in_appchain)
+        {
+            assert!(
+                profile.validator_id.eq(&validator_id),
+                "The account '{}' in appchain is already used by another validator.",
+                &validator_profile.validator_id_in_appchain
+            );
+        }
+        let staking_history = self.record_staking_fact(StakingFact::StakeRebonded {
+            validator_id,
+            validator_id_in_appchain: validator_profile.validator_id_in_appchain,
+            amount,
+            staking_history_index,
+        });
+        //
+        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
+    //
+    fn rebond_delegation(&mut self, validator_id: AccountId, staking_history_index: U64) {
+        match self.appchain_state {
+            AppchainState::Staging | AppchainState::Active => (),
+            _ => panic!(
+                "Cannot rebond delegation while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        let delegator_id = env::predecessor_account_id();
+        let staking_history =
+            self.take_unbonded_stake_reference(&delegator_id, staking_history_index.0);
+        let amount = match staking_history.staking_fact {
+            StakingFact::DelegationDecreased {
+                delegator_id: unbonded_delegator_id,
+                validator_id: unbonded_validator_id,
+                amount,
+            }
+            | StakingFact::DelegatorUnbonded {
+                delegator_id: unbonded_delegator_id,
+                validator_id: unbonded_validator_id,
+                amount,
+            }
+            | StakingFact::DelegatorAutoUnbonded {
+                delegator_id: unbonded_delegator_id,
+                validator_id: unbonded_validator_id,
+                amount,
+            } => {
+                assert!(
+                    delegator_id.eq(&unbonded_delegator_id)
+                        && validator_id.eq(&unbonded_validator_id),
+                    "Invalid staking history for delegator '{}' of validator '{}'.",
+                    delegator_id,
+                    validator_id
+                );
+                amount
+            }
+            _ => panic!(
+                "Staking history '{}' is not an unbonded delegation of a delegator.",
+                staking_history_index.0
+            ),
+        };
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        let validator = next_validator_set.get_validator(&validator_id).unwrap();
+        if !next_validator_set.contains_delegator(&delegator_id, &validator_id) {
+            assert!(
+                validator.can_be_delegated_to,
+                "Validator '{}' cannot be delegated to.",
+                &validator_id
+            );
+            let protocol_settings = self.protocol_settings.get().unwrap();
+            assert!(
+                next_validator_set.get_validator_count_of(&delegator_id)
+                    < protocol_settings.maximum_validators_per_delegator.0,
+                "Too many validators delegated."
+            );
+            assert!(
+                amount.0 >= protocol_settings.minimum_delegator_deposit.0,
+                "The deposit for registering delegator is too few."
+            );
+        }
+        self.assert_delegation_policy_is_satisfied(
+            &next_validator_set,
+            &delegator_id,
+            &validator_id,
+            amount.0,
+        );
+        self.assert_validator_stake_is_valid(
+            validator.deposit_amount,
+            Some(validator.total_stake + amount.0),
+        );
+        let staking_history = self.record_staking_fact(StakingFact::DelegationRebonded {
+            delegator_id,
+            validator_id,
+            amount,
+            staking_history_index,
+        });
+        //
+        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
+    //
+    fn accept_validator_registration(
+        &mut self,
+        validator_id_in_appchain: String,
+        can_be_delegated_to: bool,
+        profile: HashMap<String, String>,
+        commission_percent: Option<u16>,
+    ) {
+        let validator_id = env::predecessor_account_id();
+        let pending_deposit = self.remove_pending_staking_deposit_of(&validator_id);
+        assert!(
+            pending_deposit.action == FundedStakingAction::RegisterValidator,
+            "The pending staking deposit for '{}' is not for validator registration.",
+            &validator_id
+        );
+        let staking_history = self.register_validator(
+            validator_id,
+            validator_id_in_appchain,
+            profile,
+            pending_deposit.deposit_amount,
+            can_be_delegated_to,
+            commission_percent,
+        );
+        self.record_funder_of_staking_history(&pending_deposit.funder_id, &staking_history);
+    }
+    //
+    fn accept_staking_deposit(&mut self) {
+        let account_id = env::predecessor_account_id();
+        let pending_deposit = self.remove_pending_staking_deposit_of(&account_id);
+        let amount = pending_deposit.deposit_amount;
+        let staking_history = match pending_deposit.action {
+            FundedStakingAction::RegisterValidator => panic!(
+                "The validator registration should be accepted by 'accept_validator_registration'."
+            ),
+            FundedStakingAction::IncreaseStake => self.increase_stake(account_id, amount),
+            FundedStakingAction::RegisterDelegator { validator_id } => {
+                self.register_delegator(account_id, validator_id, amount)
+            }
+            FundedStakingAction::IncreaseDelegation { validator_id } => {
+                self.increase_delegation(account_id, validator_id, amount)
+            }
+        };
+        self.record_funder_of_staking_history(&pending_deposit.funder_id, &staking_history);
+    }
+    //
+    fn cancel_staking_deposit(&mut self, beneficiary_id: AccountId) {
+        self.assert_asset_transfer_is_not_paused();
+        let mut pending_deposit = self
+            .pending_staking_deposits
+            .get(&beneficiary_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "There is no pending staking deposit for '{}'.",
+                    &beneficiary_id
+                )
+            });
+        let account_id = env::predecessor_account_id();
+        assert!(
+            account_id.eq(&beneficiary_id) || account_id.eq(&pending_deposit.funder_id),
+            "Only the funder or the beneficiary can cancel the staking deposit."
+        );
+        assert!(
+            !pending_deposit.is_being_refunded,
+            "The staking deposit for '{}' is being refunded.",
+            &beneficiary_id
+        );
+        // The pending deposit is kept until the refund succeeds.
+        pending_deposit.is_being_refunded = true;
+        self.pending_staking_deposits
+            .insert(&beneficiary_id, &pending_deposit);
+        ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(
+                pending_deposit.funder_id,
+                pending_deposit.deposit_amount,
+                None,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_staking_deposit_refund(beneficiary_id),
+            );
+    }
+    //
     fn withdraw_stake(&mut self, account_id: AccountId) {
         self.assert_asset_transfer_is_not_paused();
         let protocol_settings = self.protocol_settings.get().unwrap();
@@ -575,6 +1167,77 @@ impl StakingManager for AppchainAnchor {
         self.assert_asset_transfer_is_not_paused();
         self.assert_rewards_withdrawal_is_not_paused();
         self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let reward_to_withdraw = self.internal_collect_validator_rewards(&validator_id);
+        self.internal_pay_reward(&validator_id, reward_to_withdraw);
+    }
+    //
+    fn withdraw_delegator_rewards(&mut self, delegator_id: AccountId, validator_id: AccountId) {
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_rewards_withdrawal_is_not_paused();
+        self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let reward_to_withdraw =
+            self.internal_collect_delegator_rewards(&delegator_id, &validator_id);
+        self.internal_pay_reward(&delegator_id, reward_to_withdraw);
+    }
+    //
+    fn set_reward_settings(&mut self, destination: RewardDestination, auto_claim: bool) {
+        let account_id = env::predecessor_account_id();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        assert!(
+            next_validator_set.contains_validator(&account_id)
+                || next_validator_set.get_validator_count_of(&account_id) > 0,
+            "Account '{}' is neither a validator nor a delegator.",
+            &account_id
+        );
+        if let RewardDestination::Appchain {
+            receiver_id_in_appchain,
+        } = &destination
+        {
+            AccountIdInAppchain::new(
+                Some(receiver_id_in_appchain.clone()),
+                &self.appchain_template_type,
+            )
+            .assert_valid();
+        }
+        self.reward_settings.insert(
+            &account_id,
+            &RewardSettings {
+                destination,
+                auto_claim,
+            },
+        );
+        match auto_claim {
+            true => self.auto_claim_accounts.insert(&account_id, &()),
+            false => self.auto_claim_accounts.remove(&account_id),
+        };
+    }
+    //
+    fn withdraw_validator_incentive_rewards(
+        &mut self,
+        token_contract: AccountId,
+        validator_id: AccountId,
+    ) {
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_rewards_withdrawal_is_not_paused();
+        self.internal_withdraw_incentive_rewards(token_contract, None, validator_id);
+    }
+    //
+    fn withdraw_delegator_incentive_rewards(
+        &mut self,
+        token_contract: AccountId,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    ) {
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_rewards_withdrawal_is_not_paused();
+        self.internal_withdraw_incentive_rewards(token_contract, Some(delegator_id), validator_id);
+    }
+}
+
+impl AppchainAnchor {
+    /// Collect the unwithdrawn rewards of a validator in withdrawable eras,
+    /// and remove the records of them.
+    pub fn internal_collect_validator_rewards(&mut self, validator_id: &AccountId) -> Balance {
         let end_era = self
             .validator_set_histories
             .get()
@@ -599,25 +1262,15 @@ impl StakingManager for AppchainAnchor {
                     .remove(&(era_number, validator_id.clone()));
             }
         }
-        if reward_to_withdraw > 0 {
-            ext_ft_core::ext(
-                self.wrapped_appchain_token
-                    .get()
-                    .unwrap()
-                    .contract_account
-                    .unwrap(),
-            )
-            .with_attached_deposit(1)
-            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
-            .with_unused_gas_weight(0)
-            .ft_transfer(validator_id, reward_to_withdraw.into(), None);
-        }
+        reward_to_withdraw
     }
-    //
-    fn withdraw_delegator_rewards(&mut self, delegator_id: AccountId, validator_id: AccountId) {
-        self.assert_asset_transfer_is_not_paused();
-        self.assert_rewards_withdrawal_is_not_paused();
-        self.assert_contract_account_of_wrapped_appchain_token_is_set();
+    /// Collect the unwithdrawn rewards of a delegator to a validator in withdrawable eras,
+    /// and record the withdrawn rewards.
+    pub fn internal_collect_delegator_rewards(
+        &mut self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) -> Balance {
         let end_era = self
             .validator_set_histories
             .get()
@@ -631,38 +1284,93 @@ impl StakingManager for AppchainAnchor {
             true => end_era - protocol_settings.maximum_era_count_of_unwithdrawn_reward.0 + 1,
             false => 0,
         };
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let checkpoint_key = (delegator_id.clone(), validator_id.clone());
+        let old_checkpoint = self
+            .delegator_reward_checkpoints
+            .get(&checkpoint_key)
+            .unwrap_or(0);
+        let mut checkpoint = old_checkpoint.max(start_era);
         let mut reward_to_withdraw: u128 = 0;
         for era_number in start_era..end_era {
-            if let Some(reward) = self.unwithdrawn_delegator_rewards.get(&(
-                era_number,
-                delegator_id.clone(),
-                validator_id.clone(),
-            )) {
-                reward_to_withdraw += reward;
-                self.unwithdrawn_delegator_rewards.remove(&(
-                    era_number,
-                    delegator_id.clone(),
-                    validator_id.clone(),
-                ));
+            let validator_set = match validator_set_histories.get(&era_number) {
+                Some(validator_set) => validator_set,
+                None => {
+                    if era_number == checkpoint {
+                        checkpoint += 1;
+                    }
+                    continue;
+                }
+            };
+            let reward = self.get_unwithdrawn_delegator_reward_of(
+                &validator_set,
+                delegator_id,
+                validator_id,
+            );
+            reward_to_withdraw += reward;
+            let reward_key = (era_number, delegator_id.clone(), validator_id.clone());
+            if validator_set.delegator_reward_is_recorded(delegator_id, validator_id) {
+                self.unwithdrawn_delegator_rewards.remove(&reward_key);
+            }
+            // The checkpoint only moves forward over the eras whose reward distribution
+            // is completed, the withdrawn rewards of other eras are recorded separately.
+            if era_number == checkpoint
+                && validator_set.processing_status() == ValidatorSetProcessingStatus::Completed
+            {
+                checkpoint += 1;
+                self.withdrawn_delegator_rewards.remove(&reward_key);
+            } else if reward > 0
+                && !validator_set.delegator_reward_is_recorded(delegator_id, validator_id)
+            {
+                let withdrawn_reward = self
+                    .withdrawn_delegator_rewards
+                    .get(&reward_key)
+                    .unwrap_or(0);
+                self.withdrawn_delegator_rewards
+                    .insert(&reward_key, &(withdrawn_reward + reward));
             }
         }
-        if reward_to_withdraw > 0 {
-            ext_ft_core::ext(
-                self.wrapped_appchain_token
-                    .get()
-                    .unwrap()
-                    .contract_account
-                    .unwrap(),
-            )
-            .with_attached_deposit(1)
-            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
-            .with_unused_gas_weight(0)
-            .ft_transfer(delegator_id, reward_to_withdraw.into(), None);
+        if checkpoint != old_checkpoint {
+            self.delegator_reward_checkpoints
+                .insert(&checkpoint_key, &checkpoint);
         }
+        reward_to_withdraw
+    }
+    /// Get the unwithdrawn reward of a delegator in the given validator set.
+    pub fn get_unwithdrawn_delegator_reward_of(
+        &self,
+        validator_set: &ValidatorSetOfEra,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) -> Balance {
+        let reward_key = (
+            validator_set.era_number(),
+            delegator_id.clone(),
+            validator_id.clone(),
+        );
+        if validator_set.delegator_reward_is_recorded(delegator_id, validator_id) {
+            return self
+                .unwithdrawn_delegator_rewards
+                .get(&reward_key)
+                .unwrap_or(0);
+        }
+        if let Some(checkpoint) = self
+            .delegator_reward_checkpoints
+            .get(&(delegator_id.clone(), validator_id.clone()))
+        {
+            if validator_set.era_number() < checkpoint {
+                return 0;
+            }
+        }
+        validator_set
+            .get_delegator_rewards_of(delegator_id, validator_id)
+            .unwrap_or(0)
+            .saturating_sub(
+                self.withdrawn_delegator_rewards
+                    .get(&reward_key)
+                    .unwrap_or(0),
+            )
     }
-}
-
-impl AppchainAnchor {
     //
     fn assert_total_stake_price(
         &self,
@@ -681,6 +1389,34 @@ impl AppchainAnchor {
             "Market value of stake deposited in anchor is too low."
         );
     }
+    // Remove the unbonded stake reference of the given account and return
+    // the staking history it refers to.
+    fn take_unbonded_stake_reference(
+        &mut self,
+        account_id: &AccountId,
+        staking_history_index: u64,
+    ) -> StakingHistory {
+        let mut unbonded_stake_references = self
+            .unbonded_stakes
+            .get(account_id)
+            .expect("No unbonded stake found.");
+        let position = unbonded_stake_references
+            .iter()
+            .position(|reference| reference.staking_history_index == staking_history_index)
+            .expect("The unbonded stake of the given staking history is not found.");
+        unbonded_stake_references.remove(position);
+        if unbonded_stake_references.len() > 0 {
+            self.unbonded_stakes
+                .insert(account_id, &unbonded_stake_references);
+        } else {
+            self.unbonded_stakes.remove(account_id);
+        }
+        self.staking_histories
+            .get()
+            .unwrap()
+            .get(&staking_history_index)
+            .unwrap()
+    }
     //
     pub fn record_unbonding_validator(
         &self,
@@ -695,11 +1431,148 @@ impl AppchainAnchor {
                 next_validator_set,
                 validator.total_stake,
             );
-            if auto_unbond {
+            if next_validator_set.get_queued_unbonding_validators().len() > 0
+                || !Self::is_validator_exit_allowed(
+                    protocol_settings,
+                    next_validator_set,
+                    validator.total_stake,
+                    self.get_latest_validator_count_and_stake(),
+                )
+            {
+                next_validator_set.queue_unbonding_validator(QueuedUnbondingValidator {
+                    validator_id: validator_id.clone(),
+                    auto_unbond,
+                    queued_era_number: U64::from(self.get_number_of_next_era()),
+                });
+            } else if auto_unbond {
                 next_validator_set.add_auto_unbonding_validator(validator_id);
             } else {
                 next_validator_set.add_unbonding_validator(validator_id);
             }
         }
     }
+    /// Move the queued unbonding validators to the unbonding validators of next era,
+    /// as many as the exit limit allows.
+    pub fn dequeue_unbonding_validators(&mut self, latest_validator_set: &ValidatorSetOfEra) {
+        let latest_count_and_stake = Some((
+            latest_validator_set.validator_count(),
+            latest_validator_set.total_stake(),
+        ));
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        for queued_validator in next_validator_set.get_queued_unbonding_validators() {
+            let validator = match next_validator_set.get_validator(&queued_validator.validator_id) {
+                Some(validator) => validator,
+                None => {
+                    next_validator_set
+                        .remove_queued_unbonding_validator(&queued_validator.validator_id);
+                    continue;
+                }
+            };
+            if !Self::is_validator_exit_allowed(
+                &protocol_settings,
+                &next_validator_set,
+                validator.total_stake,
+                latest_count_and_stake,
+            ) {
+                break;
+            }
+            next_validator_set.remove_queued_unbonding_validator(&queued_validator.validator_id);
+            if queued_validator.auto_unbond {
+                next_validator_set.add_auto_unbonding_validator(&queued_validator.validator_id);
+            } else {
+                next_validator_set.add_unbonding_validator(&queued_validator.validator_id);
+            }
+        }
+        self.next_validator_set.set(&next_validator_set);
+    }
+    // Get the count and total stake of validators in the validator set of latest era.
+    fn get_latest_validator_count_and_stake(&self) -> Option<(u64, u128)> {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        validator_set_histories
+            .get(&validator_set_histories.end_index)
+            .map(|validator_set| (validator_set.validator_count(), validator_set.total_stake()))
+    }
+    // Check whether a validator with the given stake can leave the validator set in next era,
+    // according to the exit limit in protocol settings.
+    fn is_validator_exit_allowed(
+        protocol_settings: &ProtocolSettings,
+        next_validator_set: &NextValidatorSet,
+        stake: u128,
+        latest_count_and_stake: Option<(u64, u128)>,
+    ) -> bool {
+        let (exiting_count, exiting_stake) =
+            next_validator_set.get_exiting_validator_count_and_stake();
+        if exiting_count == 0 {
+            return true;
+        }
+        match latest_count_and_stake {
+            Some((latest_count, latest_stake)) => {
+                let limit = &protocol_settings.validator_exit_limit;
+                exiting_count < limit.get_validator_count_budget(latest_count)
+                    && exiting_stake + stake <= limit.get_stake_budget(latest_stake)
+            }
+            None => true,
+        }
+    }
+    // Assert that a new validator with the given stake can enter the validator set
+    // in next era, according to the entry limit in protocol settings.
+    fn assert_validator_entry_is_allowed(
+        &self,
+        protocol_settings: &ProtocolSettings,
+        next_validator_set: &NextValidatorSet,
+        stake: u128,
+    ) {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let latest_validator_set =
+            match validator_set_histories.get(&validator_set_histories.end_index) {
+                Some(validator_set) => validator_set,
+                None => return,
+            };
+        let (entering_count, entering_stake) = next_validator_set
+            .get_validator_ids()
+            .iter()
+            .filter(|validator_id| !latest_validator_set.contains_validator(validator_id))
+            .filter_map(|validator_id| next_validator_set.get_validator(validator_id))
+            .fold((0, 0), |(count, stake), validator| {
+                (count + 1, stake + validator.total_stake)
+            });
+        if entering_count == 0 {
+            return;
+        }
+        let limit = &protocol_settings.validator_entry_limit;
+        assert!(
+            entering_count
+                < limit.get_validator_count_budget(latest_validator_set.validator_count())
+                && entering_stake + stake
+                    <= limit.get_stake_budget(latest_validator_set.total_stake()),
+            "Too many new validators in this era. Please try again in the next era."
+        );
+    }
+}
+
+#[near_bindgen]
+impl StakingDepositRefundResolver for AppchainAnchor {
+    //
+    fn resolve_staking_deposit_refund(&mut self, beneficiary_id: AccountId) {
+        assert_self();
+        let mut pending_deposit = self.pending_staking_deposits.get(&beneficiary_id).unwrap();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                self.pending_staking_deposits.remove(&beneficiary_id);
+            }
+            PromiseResult::Failed => {
+                log!(
+                    "Failed to refund the staking deposit for '{}' to '{}'. Amount: '{}'.",
+                    &beneficiary_id,
+                    &pending_deposit.funder_id,
+                    pending_deposit.deposit_amount.0
+                );
+                pending_deposit.is_being_refunded = false;
+                self.pending_staking_deposits
+                    .insert(&beneficiary_id, &pending_deposit);
+            }
+        }
+    }
 }
@@ -111,6 +111,47 @@ impl SudoActions for AppchainAnchor {
             .set(&permissionless_actions_status);
     }
     //
+    fn skip_appchain_messages(&mut self, nonces: Vec<u32>, reason: String) {
+        self.assert_owner();
+        assert!(!nonces.is_empty(), "The nonces to skip must not be empty.");
+        assert!(
+            !reason.is_empty(),
+            "The reason for skipping must not be empty."
+        );
+        let latest_applied_nonce = self
+            .permissionless_actions_status
+            .get()
+            .unwrap()
+            .latest_applied_appchain_message_nonce;
+        let mut appchain_messages = self.appchain_messages.get().unwrap();
+        for nonce in nonces {
+            assert!(
+                nonce > latest_applied_nonce && nonce <= appchain_messages.max_nonce(),
+                "Nonce '{}' is out of the range of unapplied appchain messages.",
+                nonce
+            );
+            assert!(
+                appchain_messages.get_message(&nonce).is_none()
+                    && appchain_messages.get_processing_result(&nonce).is_none(),
+                "Appchain message '{}' is not missing, it can not be skipped.",
+                nonce
+            );
+            appchain_messages.insert_processing_result(
+                nonce,
+                &AppchainMessageProcessingResult::Skipped {
+                    nonce,
+                    reason: reason.clone(),
+                },
+            );
+            log!(
+                "Appchain message '{}' is skipped. Reason: '{}'",
+                nonce,
+                reason
+            );
+        }
+        self.appchain_messages.set(&appchain_messages);
+    }
+    //
     fn unlock_auto_unbonded_stake_of(
         &mut self,
         delegator_id: Option<AccountId>,
@@ -187,4 +228,34 @@ impl SudoActions for AppchainAnchor {
                 .remove(&delegator_id.unwrap_or(validator_id));
         }
     }
+    //
+    fn jail_validator(&mut self, validator_id: AccountId, era_count: U64) {
+        self.assert_owner();
+        assert!(era_count.0 > 0, "The era count should be greater than 0.");
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        self.internal_jail_validator(
+            &mut next_validator_set,
+            &validator_id,
+            JailReason::ByOwner,
+            era_count.0,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        self.sync_state_to_registry();
+    }
+    //
+    fn unjail_validator(&mut self, validator_id: AccountId) {
+        self.assert_owner();
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        assert!(
+            next_validator_set
+                .get_jailed_validator(&validator_id)
+                .is_some(),
+            "Validator '{}' is not jailed.",
+            &validator_id
+        );
+        next_validator_set.unjail_validator(&validator_id);
+        self.next_validator_set.set(&next_validator_set);
+        self.sync_state_to_registry();
+    }
 }
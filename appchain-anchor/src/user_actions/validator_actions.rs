@@ -6,7 +6,7 @@ use borsh::maybestd::collections::HashMap;
 impl ValidatorActions for AppchainAnchor {
     //
     fn set_validator_id_in_appchain(&mut self, account_id_in_appchain: String) {
-        let validator_id = env::predecessor_account_id();
+        let validator_id = self.get_operated_validator_id(&self.next_validator_set.get().unwrap());
         self.internal_change_account_id_in_appchain_of_validator(
             &validator_id,
             &account_id_in_appchain,
@@ -14,8 +14,8 @@ impl ValidatorActions for AppchainAnchor {
     }
     //
     fn set_validator_profile(&mut self, profile: HashMap<String, String>) {
-        let validator_id = env::predecessor_account_id();
         let next_validator_set = self.next_validator_set.get().unwrap();
+        let validator_id = self.get_operated_validator_id(&next_validator_set);
         self.assert_validator_id(&validator_id, &next_validator_set);
         let mut validator_profiles = self.validator_profiles.get().unwrap();
         let mut validator_profile = validator_profiles.get(&validator_id).unwrap();
@@ -23,9 +23,177 @@ impl ValidatorActions for AppchainAnchor {
         validator_profiles.insert(validator_profile);
         self.validator_profiles.set(&validator_profiles);
     }
+    //
+    fn set_validator_commission_percent(&mut self, commission_percent: u16) {
+        let validator_id = env::predecessor_account_id();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            commission_percent >= protocol_settings.minimum_validator_commission_percent
+                && commission_percent <= protocol_settings.maximum_validator_commission_percent,
+            "The commission percent should be between {} and {}.",
+            protocol_settings.minimum_validator_commission_percent,
+            protocol_settings.maximum_validator_commission_percent
+        );
+        let next_era_number = self.get_number_of_next_era();
+        let mut validator_commissions = self.validator_commissions.get().unwrap();
+        // Only one change is allowed in a notice window, so that the changes can not
+        // be chained to exceed the limit of change per era.
+        if let Some(effective_era_number) = validator_commissions
+            .get(&validator_id)
+            .and_then(|commission| commission.pending_effective_era_number)
+        {
+            assert!(
+                effective_era_number.0 <= next_era_number,
+                "There is a pending change of commission percent which takes effect in era {}.",
+                effective_era_number.0
+            );
+        }
+        let current_commission_percent =
+            self.get_commission_percent_of_validator_in_era(&validator_id, next_era_number);
+        assert!(
+            commission_percent != current_commission_percent,
+            "The commission percent is not changed."
+        );
+        let changed_percent = match commission_percent > current_commission_percent {
+            true => commission_percent - current_commission_percent,
+            false => current_commission_percent - commission_percent,
+        };
+        assert!(
+            changed_percent
+                <= protocol_settings.maximum_validator_commission_percent_change_per_era,
+            "The change of commission percent should not be greater than {}.",
+            protocol_settings.maximum_validator_commission_percent_change_per_era
+        );
+        validator_commissions.schedule_commission_change(
+            &validator_id,
+            current_commission_percent,
+            commission_percent,
+            next_era_number
+                + protocol_settings
+                    .validator_commission_change_notice_era_count
+                    .0,
+        );
+        self.validator_commissions.set(&validator_commissions);
+    }
+    //
+    fn set_delegation_policy(&mut self, policy: DelegationPolicy) {
+        let validator_id = env::predecessor_account_id();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        if let Some(minimum_delegation) = &policy.minimum_delegation {
+            let protocol_settings = self.protocol_settings.get().unwrap();
+            assert!(
+                minimum_delegation.0 >= protocol_settings.minimum_delegator_deposit.0,
+                "The minimum delegation should not be less than {} OCT.",
+                protocol_settings.minimum_delegator_deposit.0 / OCT_DECIMALS_VALUE
+            );
+        }
+        if let Some(DelegationCap::MultipleOfDeposit(multiple)) = &policy.maximum_delegated_stake {
+            assert!(*multiple > 0, "Invalid multiple of deposit.");
+        }
+        self.delegation_policies.insert(&validator_id, &policy);
+    }
+    //
+    fn set_validator_operator(&mut self, operator_id: AccountId) {
+        let validator_id = env::predecessor_account_id();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        assert!(
+            !operator_id.eq(&validator_id),
+            "The operator should not be the validator itself."
+        );
+        assert!(
+            !next_validator_set.contains_validator(&operator_id),
+            "The account '{}' is a validator.",
+            &operator_id
+        );
+        let mut validator_profiles = self.validator_profiles.get().unwrap();
+        validator_profiles.set_operator(&validator_id, &operator_id);
+        self.validator_profiles.set(&validator_profiles);
+    }
+    //
+    fn revoke_validator_operator(&mut self) {
+        let validator_id = env::predecessor_account_id();
+        let mut validator_profiles = self.validator_profiles.get().unwrap();
+        assert!(
+            validator_profiles.remove_operator(&validator_id).is_some(),
+            "The operator of validator '{}' is not set.",
+            &validator_id
+        );
+        self.validator_profiles.set(&validator_profiles);
+    }
+    //
+    fn unjail(&mut self) {
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let validator_id = self.get_operated_validator_id(&next_validator_set);
+        let jailed_validator = match next_validator_set.get_jailed_validator(&validator_id) {
+            Some(jailed_validator) => jailed_validator,
+            None => panic!("Validator '{}' is not jailed.", &validator_id),
+        };
+        assert!(
+            self.get_number_of_next_era() >= jailed_validator.release_era_number.0,
+            "The validator can not be unjailed before era {}.",
+            jailed_validator.release_era_number.0
+        );
+        next_validator_set.unjail_validator(&validator_id);
+        self.next_validator_set.set(&next_validator_set);
+        self.sync_state_to_registry();
+    }
 }
 
 impl AppchainAnchor {
+    /// Get the id of the validator which the predecessor account can act for.
+    /// The predecessor account can be the validator itself or its operator.
+    pub fn get_operated_validator_id(&self, next_validator_set: &NextValidatorSet) -> AccountId {
+        let account_id = env::predecessor_account_id();
+        if next_validator_set.contains_validator(&account_id) {
+            return account_id;
+        }
+        match self
+            .validator_profiles
+            .get()
+            .unwrap()
+            .get_validator_id_by_operator(&account_id)
+        {
+            Some(validator_id) => validator_id,
+            None => account_id,
+        }
+    }
+    /// Get the number of the era which the staking actions happened now will take effect in.
+    pub fn get_number_of_next_era(&self) -> u64 {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        match validator_set_histories.contains(&validator_set_histories.end_index) {
+            true => validator_set_histories.end_index + 1,
+            false => 0,
+        }
+    }
+    /// Get the commission percent of a validator which is effective in a certain era.
+    /// The result is limited by the range of commission percent in protocol settings.
+    pub fn get_commission_percent_of_validator_in_era(
+        &self,
+        validator_id: &AccountId,
+        era_number: u64,
+    ) -> u16 {
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let commission_percent = self
+            .validator_commissions
+            .get()
+            .unwrap()
+            .get_commission_percent_of_era(
+                validator_id,
+                era_number,
+                protocol_settings.validator_commission_percent,
+            );
+        if commission_percent < protocol_settings.minimum_validator_commission_percent {
+            protocol_settings.minimum_validator_commission_percent
+        } else if commission_percent > protocol_settings.maximum_validator_commission_percent {
+            protocol_settings.maximum_validator_commission_percent
+        } else {
+            commission_percent
+        }
+    }
     ///
     pub fn internal_change_account_id_in_appchain_of_validator(
         &mut self,
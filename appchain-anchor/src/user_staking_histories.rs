@@ -27,20 +27,37 @@ impl UserStakingHistories {
             | StakingFact::ValidatorAutoUnbonded { validator_id, .. }
             | StakingFact::ValidatorDelegationEnabled { validator_id }
             | StakingFact::ValidatorDelegationDisabled { validator_id }
-            | StakingFact::ValidatorIdInAppchainChanged { validator_id, .. } => validator_id,
+            | StakingFact::ValidatorIdInAppchainChanged { validator_id, .. }
+            | StakingFact::StakeRebonded { validator_id, .. }
+            | StakingFact::ValidatorSlashed { validator_id, .. } => validator_id,
             StakingFact::DelegatorRegistered { delegator_id, .. }
             | StakingFact::DelegationIncreased { delegator_id, .. }
             | StakingFact::DelegationDecreased { delegator_id, .. }
             | StakingFact::DelegatorUnbonded { delegator_id, .. }
-            | StakingFact::DelegatorAutoUnbonded { delegator_id, .. } => delegator_id,
+            | StakingFact::DelegatorAutoUnbonded { delegator_id, .. }
+            | StakingFact::DelegationRedelegated { delegator_id, .. }
+            | StakingFact::DelegationRebonded { delegator_id, .. }
+            | StakingFact::DelegatorSlashed { delegator_id, .. } => delegator_id,
         };
+        self.add_staking_history_index_of(account_id, staking_history.index.0);
+    }
+    /// Record the staking history for the account which funded the stake.
+    pub fn add_funder_of_staking_history(
+        &mut self,
+        funder_id: &AccountId,
+        staking_history: &StakingHistory,
+    ) {
+        self.add_staking_history_index_of(funder_id, staking_history.index.0);
+    }
+    //
+    fn add_staking_history_index_of(&mut self, account_id: &AccountId, index: u64) {
         self.account_id_set.insert(account_id);
         let mut staking_histories_indexes = match self.staking_histories_map.get(account_id) {
             Some(indexes) => indexes,
             None => Vec::new(),
         };
-        if !staking_histories_indexes.contains(&staking_history.index.0) {
-            staking_histories_indexes.push(staking_history.index.0);
+        if !staking_histories_indexes.contains(&index) {
+            staking_histories_indexes.push(index);
             self.staking_histories_map
                 .insert(account_id, &staking_histories_indexes);
         }
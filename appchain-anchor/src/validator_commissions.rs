@@ -0,0 +1,76 @@
+use crate::*;
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ValidatorCommissions {
+    /// The commission settings of validators, mapped by their account id in NEAR protocol.
+    /// A validator without a record here takes the default commission in protocol settings.
+    commissions: LookupMap<AccountId, ValidatorCommission>,
+}
+
+impl ValidatorCommissions {
+    ///
+    pub fn new() -> Self {
+        Self {
+            commissions: LookupMap::new(StorageKey::ValidatorCommissionsMap.into_bytes()),
+        }
+    }
+    ///
+    pub fn get(&self, validator_id: &AccountId) -> Option<ValidatorCommission> {
+        self.commissions.get(validator_id)
+    }
+    /// Set the commission of a validator which takes effect immediately.
+    pub fn set_commission_percent(&mut self, validator_id: &AccountId, commission_percent: u16) {
+        self.commissions.insert(
+            validator_id,
+            &ValidatorCommission {
+                commission_percent,
+                pending_commission_percent: None,
+                pending_effective_era_number: None,
+            },
+        );
+    }
+    /// Schedule a commission change of a validator, which will replace
+    /// the existing pending change (if any) which has taken effect.
+    pub fn schedule_commission_change(
+        &mut self,
+        validator_id: &AccountId,
+        current_commission_percent: u16,
+        commission_percent: u16,
+        effective_era_number: u64,
+    ) {
+        self.commissions.insert(
+            validator_id,
+            &ValidatorCommission {
+                commission_percent: current_commission_percent,
+                pending_commission_percent: Some(commission_percent),
+                pending_effective_era_number: Some(U64::from(effective_era_number)),
+            },
+        );
+    }
+    /// Get the commission percent of a validator which is effective in a certain era.
+    pub fn get_commission_percent_of_era(
+        &self,
+        validator_id: &AccountId,
+        era_number: u64,
+        default_commission_percent: u16,
+    ) -> u16 {
+        match self.commissions.get(validator_id) {
+            Some(commission) => match (
+                commission.pending_commission_percent,
+                commission.pending_effective_era_number,
+            ) {
+                (Some(pending_commission_percent), Some(effective_era_number))
+                    if era_number >= effective_era_number.0 =>
+                {
+                    pending_commission_percent
+                }
+                _ => commission.commission_percent,
+            },
+            None => default_commission_percent,
+        }
+    }
+    ///
+    pub fn remove(&mut self, validator_id: &AccountId) {
+        self.commissions.remove(validator_id);
+    }
+}
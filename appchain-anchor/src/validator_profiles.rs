@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{storage_migration::OldValidatorProfiles, *};
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct ValidatorProfiles {
@@ -9,6 +9,10 @@ pub struct ValidatorProfiles {
     /// The mapping for validators' accounts, from account id in the appchain to
     /// account id in NEAR protocol.
     map_by_id_in_appchain: LookupMap<String, AccountId>,
+    /// The mapping for validators' operator accounts, from validator id to operator id.
+    operators: LookupMap<AccountId, AccountId>,
+    /// The mapping for operator accounts, from operator id to validator id.
+    validator_ids_by_operator: LookupMap<AccountId, AccountId>,
 }
 
 impl ValidatorProfiles {
@@ -18,6 +22,22 @@ impl ValidatorProfiles {
             validator_id_set: UnorderedSet::new(StorageKey::ValidatorProfilesIdSet.into_bytes()),
             profiles: LookupMap::new(StorageKey::ValidatorProfilesMap.into_bytes()),
             map_by_id_in_appchain: LookupMap::new(StorageKey::ValidatorProfilesIdMap.into_bytes()),
+            operators: LookupMap::new(StorageKey::ValidatorOperatorsMap.into_bytes()),
+            validator_ids_by_operator: LookupMap::new(
+                StorageKey::ValidatorIdsByOperatorMap.into_bytes(),
+            ),
+        }
+    }
+    ///
+    pub fn from_old_version(old_version: OldValidatorProfiles) -> Self {
+        Self {
+            validator_id_set: old_version.validator_id_set,
+            profiles: old_version.profiles,
+            map_by_id_in_appchain: old_version.map_by_id_in_appchain,
+            operators: LookupMap::new(StorageKey::ValidatorOperatorsMap.into_bytes()),
+            validator_ids_by_operator: LookupMap::new(
+                StorageKey::ValidatorIdsByOperatorMap.into_bytes(),
+            ),
         }
     }
     ///
@@ -54,6 +74,39 @@ impl ValidatorProfiles {
         }
     }
     ///
+    pub fn get_operator_of(&self, validator_id: &AccountId) -> Option<AccountId> {
+        self.operators.get(validator_id)
+    }
+    ///
+    pub fn get_validator_id_by_operator(&self, operator_id: &AccountId) -> Option<AccountId> {
+        self.validator_ids_by_operator.get(operator_id)
+    }
+    ///
+    pub fn set_operator(&mut self, validator_id: &AccountId, operator_id: &AccountId) {
+        if let Some(operated_validator_id) = self.validator_ids_by_operator.get(operator_id) {
+            assert!(
+                operated_validator_id.eq(validator_id),
+                "The account '{}' is already the operator of validator '{}'.",
+                operator_id,
+                operated_validator_id
+            );
+        }
+        if let Some(old_operator_id) = self.operators.get(validator_id) {
+            self.validator_ids_by_operator.remove(&old_operator_id);
+        }
+        self.operators.insert(validator_id, operator_id);
+        self.validator_ids_by_operator
+            .insert(operator_id, validator_id);
+    }
+    ///
+    pub fn remove_operator(&mut self, validator_id: &AccountId) -> Option<AccountId> {
+        let operator_id = self.operators.remove(validator_id);
+        if let Some(operator_id) = &operator_id {
+            self.validator_ids_by_operator.remove(operator_id);
+        }
+        operator_id
+    }
+    ///
     pub fn get_validator_ids(&self) -> Vec<AccountId> {
         self.validator_id_set.to_vec()
     }
@@ -65,6 +118,7 @@ impl ValidatorProfiles {
                     .remove(&profile.validator_id_in_appchain);
                 self.profiles.remove(&validator_id);
             }
+            self.remove_operator(validator_id);
             self.validator_id_set.remove(&validator_id);
             true
         } else {
@@ -1,3 +1,4 @@
+use core::convert::TryFrom;
 use near_sdk::BlockHeight;
 
 use crate::*;
@@ -5,7 +6,7 @@ use crate::*;
 pub mod next_validator_set;
 pub mod validator_set_of_era;
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
 pub struct Validator {
     /// The validator's id in NEAR protocol.
     pub validator_id: AccountId,
@@ -23,7 +24,7 @@ pub struct Validator {
     pub can_be_delegated_to: bool,
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
 pub struct Delegator {
     /// The delegator's id in NEAR protocol.
     pub delegator_id: AccountId,
@@ -136,16 +137,52 @@ impl ValidatorSet {
                 }
                 delegator_id_set.clear();
                 self.validator_id_to_delegator_id_set.remove(&validator_id);
-                self.validators.remove(&validator_id);
-                if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
-                    return MultiTxsOperationProcessingResult::NeedMoreGas;
-                }
+            }
+            self.validators.remove(&validator_id);
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
             }
         }
         self.validator_id_set.clear();
         self.total_stake = 0;
         MultiTxsOperationProcessingResult::Ok
     }
+    /// Rank the validators (except the excluded ones) by their total stake, and
+    /// return the id of the validators which are out of the top `maximum_validator_count`.
+    ///
+    /// The validators with equal total stake are ranked by their registration time.
+    pub fn get_standby_validator_ids(
+        &self,
+        excluded_validator_ids: &Vec<AccountId>,
+        maximum_validator_count: u64,
+    ) -> Vec<AccountId> {
+        Self::rank_standby_validator_ids(
+            self.validator_id_set
+                .iter()
+                .filter(|validator_id| !excluded_validator_ids.contains(validator_id))
+                .map(|validator_id| self.validators.get(&validator_id).unwrap())
+                .collect(),
+            maximum_validator_count,
+        )
+    }
+    /// Rank the given validators by their total stake, and return the id of
+    /// the validators which are out of the top `maximum_validator_count`.
+    pub fn rank_standby_validator_ids(
+        mut validators: Vec<Validator>,
+        maximum_validator_count: u64,
+    ) -> Vec<AccountId> {
+        validators.sort_by(|a, b| {
+            b.total_stake
+                .cmp(&a.total_stake)
+                .then(a.registered_block_height.cmp(&b.registered_block_height))
+                .then(a.validator_id.cmp(&b.validator_id))
+        });
+        validators
+            .into_iter()
+            .skip(usize::try_from(maximum_validator_count).unwrap_or(usize::MAX))
+            .map(|validator| validator.validator_id)
+            .collect()
+    }
     //
     fn apply_staking_fact(&mut self, staking_fact: &StakingFact) {
         match staking_fact {
@@ -366,6 +403,111 @@ impl ValidatorSet {
                 validator.validator_id_in_appchain = validator_id_in_appchain.to_string();
                 self.validators.insert(validator_id, &validator);
             }
+            StakingFact::DelegationRedelegated {
+                delegator_id,
+                old_validator_id,
+                new_validator_id,
+                amount,
+            } => {
+                let delegator = self
+                    .delegators
+                    .get(&(delegator_id.clone(), old_validator_id.clone()))
+                    .unwrap();
+                if delegator.deposit_amount > amount.0 {
+                    self.apply_staking_fact(&StakingFact::DelegationDecreased {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: old_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::DelegatorUnbonded {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: old_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                }
+                if self
+                    .delegators
+                    .contains_key(&(delegator_id.clone(), new_validator_id.clone()))
+                {
+                    self.apply_staking_fact(&StakingFact::DelegationIncreased {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: new_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::DelegatorRegistered {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: new_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                }
+            }
+            StakingFact::StakeRebonded {
+                validator_id,
+                validator_id_in_appchain,
+                amount,
+                staking_history_index: _,
+            } => {
+                if self.validators.contains_key(validator_id) {
+                    self.apply_staking_fact(&StakingFact::StakeIncreased {
+                        validator_id: validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::ValidatorRegistered {
+                        validator_id: validator_id.clone(),
+                        validator_id_in_appchain: validator_id_in_appchain.clone(),
+                        amount: amount.clone(),
+                        can_be_delegated_to: false,
+                    });
+                }
+            }
+            StakingFact::DelegationRebonded {
+                delegator_id,
+                validator_id,
+                amount,
+                staking_history_index: _,
+            } => {
+                if self
+                    .delegators
+                    .contains_key(&(delegator_id.clone(), validator_id.clone()))
+                {
+                    self.apply_staking_fact(&StakingFact::DelegationIncreased {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::DelegatorRegistered {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                }
+            }
+            StakingFact::ValidatorSlashed {
+                validator_id,
+                amount,
+                ..
+            } => {
+                self.apply_staking_fact(&StakingFact::StakeDecreased {
+                    validator_id: validator_id.clone(),
+                    amount: amount.clone(),
+                });
+            }
+            StakingFact::DelegatorSlashed {
+                delegator_id,
+                validator_id,
+                amount,
+                ..
+            } => {
+                self.apply_staking_fact(&StakingFact::DelegationDecreased {
+                    delegator_id: delegator_id.clone(),
+                    validator_id: validator_id.clone(),
+                    amount: amount.clone(),
+                });
+            }
         }
     }
 }
@@ -465,7 +607,12 @@ impl ValidatorSetViewer for ValidatorSet {
 
 impl AppchainValidator {
     ///
-    pub fn from_validator(validator: Validator, delegators_count: u64, is_unbonding: bool) -> Self {
+    pub fn from_validator(
+        validator: Validator,
+        delegators_count: u64,
+        is_unbonding: bool,
+        is_standby: bool,
+    ) -> Self {
         Self {
             validator_id: validator.validator_id,
             validator_id_in_appchain: validator.validator_id_in_appchain,
@@ -474,6 +621,7 @@ impl AppchainValidator {
             delegators_count: U64::from(delegators_count),
             can_be_delegated_to: validator.can_be_delegated_to,
             is_unbonding,
+            is_standby,
         }
     }
 }
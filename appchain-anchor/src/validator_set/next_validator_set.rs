@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{storage_migration::OldNextValidatorSet, *};
 
 use super::{Delegator, Validator, ValidatorSet};
 
@@ -10,6 +10,10 @@ pub struct NextValidatorSet {
     unbonding_validator_ids: Vec<AccountId>,
     /// The auto-unbonding validator ids in next era
     auto_unbonding_validator_ids: Vec<AccountId>,
+    /// The jailed validators, which are excluded from the validator set of next eras
+    jailed_validators: Vec<JailedValidator>,
+    /// The unbonding requests exceeding the exit limit, in the order of submission
+    queued_unbonding_validators: Vec<QueuedUnbondingValidator>,
 }
 
 impl NextValidatorSet {
@@ -19,6 +23,8 @@ impl NextValidatorSet {
             validator_set: ValidatorSet::new(era_number),
             unbonding_validator_ids: Vec::<AccountId>::new(),
             auto_unbonding_validator_ids: Vec::<AccountId>::new(),
+            jailed_validators: Vec::<JailedValidator>::new(),
+            queued_unbonding_validators: Vec::<QueuedUnbondingValidator>::new(),
         }
     }
     ///
@@ -27,6 +33,18 @@ impl NextValidatorSet {
             validator_set,
             unbonding_validator_ids: Vec::<AccountId>::new(),
             auto_unbonding_validator_ids: Vec::<AccountId>::new(),
+            jailed_validators: Vec::<JailedValidator>::new(),
+            queued_unbonding_validators: Vec::<QueuedUnbondingValidator>::new(),
+        }
+    }
+    ///
+    pub fn from_old_version(old_version: OldNextValidatorSet) -> Self {
+        Self {
+            validator_set: old_version.validator_set,
+            unbonding_validator_ids: old_version.unbonding_validator_ids,
+            auto_unbonding_validator_ids: old_version.auto_unbonding_validator_ids,
+            jailed_validators: Vec::<JailedValidator>::new(),
+            queued_unbonding_validators: Vec::<QueuedUnbondingValidator>::new(),
         }
     }
     ///
@@ -39,12 +57,22 @@ impl NextValidatorSet {
         if result.is_ok() {
             self.unbonding_validator_ids.clear();
             self.auto_unbonding_validator_ids.clear();
+            self.jailed_validators.clear();
+            self.queued_unbonding_validators.clear();
         }
         result
     }
     ///
     pub fn apply_staking_fact(&mut self, staking_fact: &StakingFact) {
         self.validator_set.apply_staking_fact(staking_fact);
+        match staking_fact {
+            StakingFact::ValidatorUnbonded { validator_id, .. }
+            | StakingFact::ValidatorAutoUnbonded { validator_id, .. } => {
+                self.unjail_validator(validator_id);
+                self.remove_queued_unbonding_validator(validator_id);
+            }
+            _ => (),
+        }
     }
     ///
     pub fn add_unbonding_validator(&mut self, validator_id: &AccountId) {
@@ -59,6 +87,53 @@ impl NextValidatorSet {
         }
     }
     ///
+    pub fn queue_unbonding_validator(&mut self, queued_validator: QueuedUnbondingValidator) {
+        if !self.is_queued_for_unbonding(&queued_validator.validator_id) {
+            self.queued_unbonding_validators.push(queued_validator);
+        }
+    }
+    ///
+    pub fn remove_queued_unbonding_validator(&mut self, validator_id: &AccountId) {
+        self.queued_unbonding_validators
+            .retain(|v| !v.validator_id.eq(validator_id));
+    }
+    ///
+    pub fn is_queued_for_unbonding(&self, validator_id: &AccountId) -> bool {
+        self.queued_unbonding_validators
+            .iter()
+            .any(|v| v.validator_id.eq(validator_id))
+    }
+    ///
+    pub fn get_queued_unbonding_validators(&self) -> Vec<QueuedUnbondingValidator> {
+        self.queued_unbonding_validators.to_vec()
+    }
+    /// Get the count and the total stake of validators which will leave the validator set
+    /// in next era.
+    pub fn get_exiting_validator_count_and_stake(&self) -> (u64, u128) {
+        self.unbonding_validator_ids
+            .iter()
+            .chain(self.auto_unbonding_validator_ids.iter())
+            .filter_map(|validator_id| self.validator_set.get_validator(validator_id))
+            .fold((0, 0), |(count, stake), validator| {
+                (count + 1, stake + validator.total_stake)
+            })
+    }
+    /// Get the count of validators which will stay in the validator set, excluding
+    /// all pending exits (unbonding, auto-unbonding and queued for unbonding).
+    pub fn get_remaining_validator_count(&self) -> u64 {
+        self.validator_set
+            .get_validator_ids()
+            .iter()
+            .filter(|validator_id| {
+                !self.unbonding_validator_ids.contains(validator_id)
+                    && !self.auto_unbonding_validator_ids.contains(validator_id)
+                    && !self.is_queued_for_unbonding(validator_id)
+            })
+            .count()
+            .try_into()
+            .unwrap()
+    }
+    ///
     pub fn get_unbonding_validator_ids(&self) -> Vec<AccountId> {
         self.unbonding_validator_ids.to_vec()
     }
@@ -74,17 +149,68 @@ impl NextValidatorSet {
     pub fn clear_auto_unbonding_validator_ids(&mut self) {
         self.auto_unbonding_validator_ids.clear();
     }
+    /// Jail a validator, or extend the sentence of a jailed validator.
+    pub fn jail_validator(&mut self, jailed_validator: JailedValidator) {
+        match self
+            .jailed_validators
+            .iter_mut()
+            .find(|v| v.validator_id.eq(&jailed_validator.validator_id))
+        {
+            Some(existing) => {
+                if existing.release_era_number.0 < jailed_validator.release_era_number.0 {
+                    existing.reason = jailed_validator.reason;
+                    existing.release_era_number = jailed_validator.release_era_number;
+                }
+            }
+            None => self.jailed_validators.push(jailed_validator),
+        }
+    }
+    ///
+    pub fn unjail_validator(&mut self, validator_id: &AccountId) {
+        self.jailed_validators
+            .retain(|v| !v.validator_id.eq(validator_id));
+    }
+    ///
+    pub fn get_jailed_validator(&self, validator_id: &AccountId) -> Option<JailedValidator> {
+        self.jailed_validators
+            .iter()
+            .find(|v| v.validator_id.eq(validator_id))
+            .cloned()
+    }
+    ///
+    pub fn get_jailed_validators(&self) -> Vec<JailedValidator> {
+        self.jailed_validators.to_vec()
+    }
+    ///
+    pub fn get_jailed_validator_ids(&self) -> Vec<AccountId> {
+        self.jailed_validators
+            .iter()
+            .map(|v| v.validator_id.clone())
+            .collect()
+    }
+    /// Get the id of validators which will be standby in next era, if the era switches now.
+    pub fn get_standby_validator_ids(&self, maximum_validator_count: u64) -> Vec<AccountId> {
+        let mut excluded_validator_ids = self.get_jailed_validator_ids();
+        excluded_validator_ids.extend(self.unbonding_validator_ids.iter().cloned());
+        excluded_validator_ids.extend(self.auto_unbonding_validator_ids.iter().cloned());
+        self.validator_set
+            .get_standby_validator_ids(&excluded_validator_ids, maximum_validator_count)
+    }
     ///
-    pub fn get_validator_list(&self) -> Vec<AppchainValidator> {
+    pub fn get_validator_list(&self, maximum_validator_count: u64) -> Vec<AppchainValidator> {
+        let jailed_validator_ids = self.get_jailed_validator_ids();
+        let standby_validator_ids = self.get_standby_validator_ids(maximum_validator_count);
         self.validator_set
             .get_validator_ids()
             .iter()
+            .filter(|validator_id| !jailed_validator_ids.contains(validator_id))
             .map(|validator_id| {
                 AppchainValidator::from_validator(
                     self.validator_set.get_validator(validator_id).unwrap(),
                     self.validator_set.get_delegator_count_of(validator_id),
                     self.unbonding_validator_ids.contains(validator_id)
                         || self.auto_unbonding_validator_ids.contains(validator_id),
+                    standby_validator_ids.contains(validator_id),
                 )
             })
             .collect()
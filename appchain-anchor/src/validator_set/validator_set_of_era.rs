@@ -1,6 +1,9 @@
+use core::convert::{TryFrom, TryInto};
 use near_sdk::BlockHeight;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
-use crate::*;
+use crate::{storage_migration::OldValidatorSetOfEra, *};
 
 use super::{Delegator, Validator, ValidatorSet};
 
@@ -16,19 +19,80 @@ pub struct ValidatorSetOfEra {
     staking_history_index: u64,
     /// The set of validator id which will not be profited.
     unprofitable_validator_id_set: UnorderedSet<AccountId>,
-    /// Total stake excluding all unprofitable validators' stake.
+    /// Total stake excluding all unprofitable, jailed and standby validators' stake.
     valid_total_stake: Balance,
     /// The rewards of validators in this era
     validator_rewards: LookupMap<AccountId, Balance>,
-    /// The rewards of delegators in this era
+    /// The rewards of delegators in this era (only recorded by the earlier versions)
     delegator_rewards: LookupMap<(AccountId, AccountId), Balance>,
     /// The status of creation of this set
     processing_status: ValidatorSetProcessingStatus,
+    /// The commission percents of validators which are applied in this era
+    validator_commission_percents: LookupMap<AccountId, u16>,
+    /// The set of validator id which are jailed in this era.
+    jailed_validator_id_set: UnorderedSet<AccountId>,
+    /// The set of validator id which are standby (not active) in this era.
+    standby_validator_id_set: UnorderedSet<AccountId>,
+    /// The amount of era reward calculated by the reward schedule for this era.
+    era_reward: Balance,
+    /// The account which received the treasury share of era reward in this era.
+    treasury_account: Option<AccountId>,
+    /// The amount of era reward minted to the treasury account in this era.
+    treasury_reward: Balance,
+    /// The share of era reward of unprofitable validators which is not distributed
+    /// to validators and delegators in this era.
+    withheld_reward: Balance,
+    /// The amount of era reward shared by the profitable validators in this era.
+    profitable_validators_reward: Balance,
+    /// The amount of era reward shared by the unprofitable validators in this era,
+    /// which is only distributed to their delegators.
+    unprofitable_validators_reward: Balance,
+    /// The total amount of era reward to be distributed in this era.
+    total_reward: Balance,
+    /// The amount of reward which has been distributed to validators and delegators.
+    distributed_reward: Balance,
+    /// The amount of undistributed reward (the dust) which has been swept.
+    swept_reward_dust: Balance,
+    /// The status of transferring the swept reward dust to the treasury account.
+    reward_dust_transfer_status: SweptRewardTransferStatus,
+    /// The incentive tokens allocated to the stakes of this era.
+    /// It is `None` if the incentive tokens have not been allocated yet.
+    incentive_rewards: Option<Vec<IncentiveRewardOfEra>>,
+    /// The reward shared by all stakes of each validator in this era, excluding the commission.
+    /// The reward per share of a validator is `pool / total_stake_of_validator`.
+    delegation_reward_pools: LookupMap<AccountId, Balance>,
+    /// The era number of the validator set which this set is based on.
+    /// If it is `None`, this set holds all validators and delegators of this era (a checkpoint),
+    /// otherwise this set only holds the validators and delegators changed in this era.
+    parent_era_number: Option<u64>,
+    /// The delegators (in tuple of delegator id and validator id) which are removed
+    /// from the validator set of parent era in this era.
+    removed_delegators: UnorderedSet<(AccountId, AccountId)>,
+    /// The validator set of parent era, which is loaded from the lookup array of
+    /// validator set histories when this set is read.
+    #[borsh_skip]
+    parent: Option<Box<ValidatorSetOfEra>>,
+    /// The delegator ids of validators, which are materialized from the parent sets
+    /// at most once for each validator while this set is loaded.
+    #[borsh_skip]
+    delegator_ids_cache: RefCell<HashMap<AccountId, Vec<AccountId>>>,
+    /// The validators looked up in the parent sets, which are cached while this set is loaded,
+    /// as the parent sets are not changed after this set is based on them.
+    #[borsh_skip]
+    parent_validators_cache: RefCell<HashMap<AccountId, Option<Validator>>>,
+    /// The delegators looked up in the parent sets, which are cached while this set is loaded,
+    /// as the parent sets are not changed after this set is based on them.
+    #[borsh_skip]
+    parent_delegators_cache: RefCell<HashMap<(AccountId, AccountId), Option<Delegator>>>,
 }
 
 impl ValidatorSetOfEra {
     ///
-    pub fn new(era_number: u64, staking_history_index: u64) -> Self {
+    pub fn new(
+        era_number: u64,
+        staking_history_index: u64,
+        parent_era_number: Option<u64>,
+    ) -> Self {
         Self {
             start_block_height: env::block_height(),
             start_timestamp: env::block_timestamp(),
@@ -48,6 +112,83 @@ impl ValidatorSetOfEra {
                 copying_validator_index: U64::from(0),
                 copying_delegator_index: U64::from(0),
             },
+            validator_commission_percents: LookupMap::new(
+                StorageKey::ValidatorCommissionPercentsOfEra(era_number).into_bytes(),
+            ),
+            jailed_validator_id_set: UnorderedSet::new(
+                StorageKey::JailedValidatorIdsOfEra(era_number).into_bytes(),
+            ),
+            standby_validator_id_set: UnorderedSet::new(
+                StorageKey::StandbyValidatorIdsOfEra(era_number).into_bytes(),
+            ),
+            era_reward: 0,
+            treasury_account: None,
+            treasury_reward: 0,
+            withheld_reward: 0,
+            profitable_validators_reward: 0,
+            unprofitable_validators_reward: 0,
+            total_reward: 0,
+            distributed_reward: 0,
+            swept_reward_dust: 0,
+            reward_dust_transfer_status: SweptRewardTransferStatus::NotTransferred,
+            incentive_rewards: None,
+            delegation_reward_pools: LookupMap::new(
+                StorageKey::DelegationRewardPoolsOfEra(era_number).into_bytes(),
+            ),
+            parent_era_number,
+            removed_delegators: UnorderedSet::new(
+                StorageKey::RemovedDelegatorsOfEra(era_number).into_bytes(),
+            ),
+            parent: None,
+            delegator_ids_cache: RefCell::new(HashMap::new()),
+            parent_validators_cache: RefCell::new(HashMap::new()),
+            parent_delegators_cache: RefCell::new(HashMap::new()),
+        }
+    }
+    ///
+    pub fn from_old_version(old_version: OldValidatorSetOfEra) -> Self {
+        let era_number = old_version.validator_set.era_number();
+        Self {
+            validator_set: old_version.validator_set,
+            start_block_height: old_version.start_block_height,
+            start_timestamp: old_version.start_timestamp,
+            staking_history_index: old_version.staking_history_index,
+            unprofitable_validator_id_set: old_version.unprofitable_validator_id_set,
+            valid_total_stake: old_version.valid_total_stake,
+            validator_rewards: old_version.validator_rewards,
+            delegator_rewards: old_version.delegator_rewards,
+            processing_status: old_version.processing_status,
+            validator_commission_percents: LookupMap::new(
+                StorageKey::ValidatorCommissionPercentsOfEra(era_number).into_bytes(),
+            ),
+            jailed_validator_id_set: UnorderedSet::new(
+                StorageKey::JailedValidatorIdsOfEra(era_number).into_bytes(),
+            ),
+            standby_validator_id_set: UnorderedSet::new(
+                StorageKey::StandbyValidatorIdsOfEra(era_number).into_bytes(),
+            ),
+            era_reward: 0,
+            treasury_account: None,
+            treasury_reward: 0,
+            withheld_reward: 0,
+            profitable_validators_reward: 0,
+            unprofitable_validators_reward: 0,
+            total_reward: 0,
+            distributed_reward: 0,
+            swept_reward_dust: 0,
+            reward_dust_transfer_status: SweptRewardTransferStatus::NotTransferred,
+            incentive_rewards: None,
+            delegation_reward_pools: LookupMap::new(
+                StorageKey::DelegationRewardPoolsOfEra(era_number).into_bytes(),
+            ),
+            parent_era_number: None,
+            removed_delegators: UnorderedSet::new(
+                StorageKey::RemovedDelegatorsOfEra(era_number).into_bytes(),
+            ),
+            parent: None,
+            delegator_ids_cache: RefCell::new(HashMap::new()),
+            parent_validators_cache: RefCell::new(HashMap::new()),
+            parent_delegators_cache: RefCell::new(HashMap::new()),
         }
     }
     ///
@@ -62,20 +203,121 @@ impl ValidatorSetOfEra {
     pub fn get_validator_rewards_of(&self, validator_id: &AccountId) -> Option<u128> {
         self.validator_rewards.get(validator_id)
     }
+    /// Get the reward of a delegator in this era.
     ///
+    /// The reward recorded by the earlier versions takes precedence, otherwise the reward
+    /// is calculated by the reward per share of the delegation reward pool of the validator.
     pub fn get_delegator_rewards_of(
         &self,
         delegator_id: &AccountId,
         validator_id: &AccountId,
     ) -> Option<u128> {
-        self.delegator_rewards
+        if let Some(reward) = self
+            .delegator_rewards
             .get(&(delegator_id.clone(), validator_id.clone()))
+        {
+            return Some(reward);
+        }
+        let reward_per_share = self.get_delegation_reward_per_share_of(validator_id)?;
+        let delegator = self.find_delegator(delegator_id, validator_id)?;
+        Some(mul_div(
+            delegator.deposit_amount,
+            reward_per_share,
+            RATIO_SCALE,
+        ))
+    }
+    /// Get the reward per share of the delegation reward pool of a validator,
+    /// which is scaled by `RATIO_SCALE` and rounded down.
+    pub fn get_delegation_reward_per_share_of(&self, validator_id: &AccountId) -> Option<u128> {
+        let pool = self.delegation_reward_pools.get(validator_id)?;
+        let validator = self.find_validator(validator_id)?;
+        match validator.total_stake > 0 {
+            true => Some(mul_div(pool, RATIO_SCALE, validator.total_stake)),
+            false => Some(0),
+        }
+    }
+    /// Get the incentive reward of a validator (or a delegator of it) in a certain token.
+    ///
+    /// The incentive token of this era is shared by the stakes of all validators
+    /// which are not unprofitable, jailed or standby, in proportion to the amount of stake.
+    pub fn get_incentive_reward_of(
+        &self,
+        token_contract: &AccountId,
+        delegator_id: &Option<AccountId>,
+        validator_id: &AccountId,
+    ) -> Option<u128> {
+        let incentive_reward = self
+            .incentive_rewards
+            .as_ref()?
+            .iter()
+            .find(|reward| reward.token_contract.eq(token_contract))?;
+        if self.valid_total_stake == 0
+            || self.unprofitable_validator_id_set.contains(validator_id)
+            || self.jailed_validator_id_set.contains(validator_id)
+            || self.standby_validator_id_set.contains(validator_id)
+        {
+            return None;
+        }
+        let validator = self.find_validator(validator_id)?;
+        let stake = match delegator_id {
+            Some(delegator_id) => {
+                self.find_delegator(delegator_id, validator_id)?
+                    .deposit_amount
+            }
+            None => validator.deposit_amount,
+        };
+        Some(mul_div(
+            incentive_reward.amount.0,
+            stake,
+            self.valid_total_stake,
+        ))
+    }
+    ///
+    pub fn delegator_reward_is_recorded(
+        &self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) -> bool {
+        self.delegator_rewards
+            .contains_key(&(delegator_id.clone(), validator_id.clone()))
+    }
+    ///
+    pub fn get_validator_commission_percent_of(&self, validator_id: &AccountId) -> Option<u16> {
+        self.validator_commission_percents.get(validator_id)
     }
     ///
     pub fn unprofitable_validator_ids(&self) -> Vec<AccountId> {
         self.unprofitable_validator_id_set.to_vec()
     }
     ///
+    pub fn jailed_validator_ids(&self) -> Vec<AccountId> {
+        self.jailed_validator_id_set.to_vec()
+    }
+    ///
+    pub fn is_jailed(&self, validator_id: &AccountId) -> bool {
+        self.jailed_validator_id_set.contains(validator_id)
+    }
+    ///
+    pub fn standby_validator_ids(&self) -> Vec<AccountId> {
+        self.standby_validator_id_set.to_vec()
+    }
+    ///
+    pub fn is_standby(&self, validator_id: &AccountId) -> bool {
+        self.standby_validator_id_set.contains(validator_id)
+    }
+    /// Get the amount of undistributed reward which has not been swept.
+    ///
+    /// The dust is only meaningful after the distribution of reward is completed.
+    pub fn reward_dust(&self) -> u128 {
+        self.total_reward
+            .saturating_sub(self.distributed_reward)
+            .saturating_sub(self.swept_reward_dust)
+    }
+    ///
+    pub fn parent_era_number(&self) -> Option<u64> {
+        self.parent_era_number
+    }
+    ///
     pub fn processing_status(&self) -> ValidatorSetProcessingStatus {
         self.processing_status.clone()
     }
@@ -89,6 +331,7 @@ impl ValidatorSetOfEra {
             ValidatorSetProcessingStatus::ReadyForDistributingReward
             | ValidatorSetProcessingStatus::DistributingReward { .. }
             | ValidatorSetProcessingStatus::CheckingForAutoUnbondingValidator { .. }
+            | ValidatorSetProcessingStatus::AutoClaimingRewards { .. }
             | ValidatorSetProcessingStatus::Completed => true,
             _ => false,
         }
@@ -112,6 +355,30 @@ impl ValidatorSetOfEra {
         });
     }
     ///
+    pub fn set_jailed_validator_ids(&mut self, jailed_validator_ids: Vec<AccountId>) {
+        jailed_validator_ids.iter().for_each(|v_id| {
+            if self.validator_set.validator_id_set.contains(v_id) {
+                self.jailed_validator_id_set.insert(&v_id);
+            }
+        });
+    }
+    /// Select the active validators by their total stake, and put the rest
+    /// (except the jailed ones) into the standby validator set.
+    pub fn select_standby_validators(&mut self, maximum_validator_count: u64) {
+        let standby_validator_ids = ValidatorSet::rank_standby_validator_ids(
+            self.validator_set
+                .validator_id_set
+                .iter()
+                .filter(|validator_id| !self.jailed_validator_id_set.contains(validator_id))
+                .map(|validator_id| self.find_validator(&validator_id).unwrap())
+                .collect(),
+            maximum_validator_count,
+        );
+        standby_validator_ids.iter().for_each(|v_id| {
+            self.standby_validator_id_set.insert(v_id);
+        });
+    }
+    ///
     pub fn insert_validator(&mut self, validator: &Validator) {
         self.validator_set
             .validator_id_set
@@ -122,6 +389,7 @@ impl ValidatorSetOfEra {
     }
     ///
     pub fn insert_delegator(&mut self, delegator: &Delegator) {
+        self.delegator_ids_cache.get_mut().clear();
         let delegator_id = &delegator.delegator_id;
         let validator_id = &delegator.validator_id;
         self.validator_set
@@ -184,21 +452,117 @@ impl ValidatorSetOfEra {
         self.validator_rewards.insert(validator_id, &amount);
     }
     ///
-    pub fn set_delegator_reward(
+    pub fn set_validator_commission_percent(
         &mut self,
-        delegator_id: &AccountId,
         validator_id: &AccountId,
-        amount: u128,
+        commission_percent: u16,
     ) {
-        self.delegator_rewards
-            .insert(&(delegator_id.clone(), validator_id.clone()), &amount);
+        self.validator_commission_percents
+            .insert(validator_id, &commission_percent);
+    }
+    ///
+    pub fn profitable_validators_reward(&self) -> u128 {
+        self.profitable_validators_reward
+    }
+    ///
+    pub fn unprofitable_validators_reward(&self) -> u128 {
+        self.unprofitable_validators_reward
+    }
+    /// Set the era reward used for distributing reward in this era and the treasury share of it.
+    pub fn set_era_reward(
+        &mut self,
+        era_reward: u128,
+        treasury_account: Option<AccountId>,
+        treasury_reward: u128,
+    ) {
+        self.era_reward = era_reward;
+        self.treasury_account = treasury_account;
+        self.treasury_reward = treasury_reward;
+    }
+    /// Set the shares of era reward of profitable and unprofitable validators,
+    /// and the amount of reward withheld from unprofitable validators in this era.
+    pub fn set_reward_shares(
+        &mut self,
+        profitable_validators_reward: u128,
+        unprofitable_validators_reward: u128,
+        withheld_reward: u128,
+    ) {
+        self.profitable_validators_reward = profitable_validators_reward;
+        self.unprofitable_validators_reward = unprofitable_validators_reward;
+        self.withheld_reward = withheld_reward;
+    }
+    ///
+    pub fn add_total_reward(&mut self, amount: u128) {
+        self.total_reward += amount;
+    }
+    ///
+    pub fn incentive_rewards_are_allocated(&self) -> bool {
+        self.incentive_rewards.is_some()
+    }
+    ///
+    pub fn set_incentive_rewards(&mut self, incentive_rewards: Vec<IncentiveRewardOfEra>) {
+        self.incentive_rewards = Some(incentive_rewards);
+    }
+    ///
+    pub fn add_distributed_reward(&mut self, amount: u128) {
+        self.distributed_reward += amount;
+    }
+    /// Sweep the reward dust to be transferred to the treasury account.
+    pub fn sweep_reward_dust(&mut self) -> u128 {
+        let reward_dust = self.reward_dust();
+        self.swept_reward_dust += reward_dust;
+        self.reward_dust_transfer_status = SweptRewardTransferStatus::Transferring;
+        reward_dust
+    }
+    ///
+    pub fn reward_dust_transfer_status(&self) -> &SweptRewardTransferStatus {
+        &self.reward_dust_transfer_status
+    }
+    /// Set the result of transferring the swept reward dust. The amount of a failed
+    /// transfer is restored to the reward dust, so that it can be swept again.
+    pub fn resolve_reward_dust_transfer(&mut self, amount: u128, succeeded: bool) {
+        if succeeded {
+            self.reward_dust_transfer_status = SweptRewardTransferStatus::Transferred;
+        } else {
+            self.swept_reward_dust -= amount;
+            self.reward_dust_transfer_status = SweptRewardTransferStatus::Failed;
+        }
+    }
+    ///
+    pub fn add_delegation_reward(&mut self, validator_id: &AccountId, amount: u128) {
+        let pool = self.delegation_reward_pools.get(validator_id).unwrap_or(0);
+        self.delegation_reward_pools
+            .insert(validator_id, &(pool + amount));
+    }
+    /// Get the unprofitable validators which are neither jailed nor standby in this era.
+    pub fn get_active_unprofitable_validators(&self) -> Vec<Validator> {
+        self.unprofitable_validator_id_set
+            .iter()
+            .filter(|v_id| {
+                !self.jailed_validator_id_set.contains(v_id)
+                    && !self.standby_validator_id_set.contains(v_id)
+            })
+            .map(|v_id| self.find_validator(&v_id).unwrap())
+            .collect()
+    }
+    ///
+    pub fn remove_delegation_reward_pool(&mut self, validator_id: &AccountId) {
+        self.delegation_reward_pools.remove(validator_id);
     }
     ///
     pub fn calculate_valid_total_stake(&mut self) {
-        let unprofitable_validator_ids = self.unprofitable_validator_id_set.to_vec();
+        let mut excluded_validator_ids = self.unprofitable_validator_id_set.to_vec();
+        self.jailed_validator_id_set
+            .iter()
+            .chain(self.standby_validator_id_set.iter())
+            .for_each(|v_id| {
+                if !excluded_validator_ids.contains(&v_id) {
+                    excluded_validator_ids.push(v_id);
+                }
+            });
         self.valid_total_stake = self.validator_set.total_stake;
-        unprofitable_validator_ids.iter().for_each(|v_id| {
-            let validator = self.validator_set.validators.get(v_id).unwrap();
+        excluded_validator_ids.iter().for_each(|v_id| {
+            let validator = self.find_validator(v_id).unwrap();
             self.valid_total_stake -= validator.total_stake;
         });
     }
@@ -212,6 +576,18 @@ impl ValidatorSetOfEra {
             start_timestamp: U64::from(self.start_timestamp),
             staking_history_index: U64::from(self.staking_history_index),
             unprofitable_validator_ids: self.unprofitable_validator_id_set.to_vec(),
+            jailed_validator_ids: self.jailed_validator_id_set.to_vec(),
+            standby_validator_ids: self.standby_validator_id_set.to_vec(),
+            era_reward: U128::from(self.era_reward),
+            treasury_account: self.treasury_account.clone(),
+            treasury_reward: U128::from(self.treasury_reward),
+            withheld_reward: U128::from(self.withheld_reward),
+            total_reward: U128::from(self.total_reward),
+            distributed_reward: U128::from(self.distributed_reward),
+            reward_dust: U128::from(self.reward_dust()),
+            swept_reward_dust: U128::from(self.swept_reward_dust),
+            reward_dust_transfer_status: self.reward_dust_transfer_status.clone(),
+            incentive_rewards: self.incentive_rewards.clone().unwrap_or_default(),
             valid_total_stake: U128::from(self.valid_total_stake),
             processing_status: self.processing_status.clone(),
         }
@@ -221,6 +597,7 @@ impl ValidatorSetOfEra {
         let validator_ids = self.validator_set.validator_id_set.to_vec();
         for validator_id in validator_ids {
             if self.unprofitable_validator_id_set.contains(&validator_id) {
+                self.delegation_reward_pools.remove(&validator_id);
                 continue;
             }
             if let Some(delegator_id_set) = self
@@ -238,11 +615,16 @@ impl ValidatorSetOfEra {
                 }
             }
             self.validator_rewards.remove(&validator_id);
+            self.validator_commission_percents.remove(&validator_id);
+            self.delegation_reward_pools.remove(&validator_id);
             if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
                 return MultiTxsOperationProcessingResult::NeedMoreGas;
             }
         }
         self.unprofitable_validator_id_set.clear();
+        self.jailed_validator_id_set.clear();
+        self.standby_validator_id_set.clear();
+        self.removed_delegators.clear();
         MultiTxsOperationProcessingResult::Ok
     }
     ///
@@ -253,9 +635,355 @@ impl ValidatorSetOfEra {
         }
         result
     }
-    //
+    /// Copy the validator ids and total stake from the validator set of last era,
+    /// for the validator set which is based on the set of last era.
+    pub fn copy_validator_ids_from(&mut self, last_validator_set: &ValidatorSetOfEra) {
+        last_validator_set
+            .get_validator_ids()
+            .iter()
+            .for_each(|validator_id| {
+                self.validator_set.validator_id_set.insert(validator_id);
+            });
+        self.validator_set.total_stake = last_validator_set.total_stake();
+    }
+    /// Apply a staking fact to this validator set.
+    ///
+    /// If this set is based on the validator set of parent era, the validators and delegators
+    /// which will be changed by the staking fact are copied from the parent set first.
     pub fn apply_staking_fact(&mut self, staking_fact: &StakingFact) {
-        self.validator_set.apply_staking_fact(staking_fact);
+        self.delegator_ids_cache.get_mut().clear();
+        if self.parent_era_number.is_none() {
+            self.validator_set.apply_staking_fact(staking_fact);
+            return;
+        }
+        match staking_fact {
+            StakingFact::ValidatorRegistered { .. } => {
+                self.validator_set.apply_staking_fact(staking_fact);
+            }
+            StakingFact::StakeIncreased { validator_id, .. }
+            | StakingFact::StakeDecreased { validator_id, .. }
+            | StakingFact::ValidatorDelegationEnabled { validator_id }
+            | StakingFact::ValidatorDelegationDisabled { validator_id }
+            | StakingFact::ValidatorIdInAppchainChanged { validator_id, .. } => {
+                self.copy_validator_from_parent(validator_id);
+                self.validator_set.apply_staking_fact(staking_fact);
+            }
+            StakingFact::ValidatorUnbonded { validator_id, .. }
+            | StakingFact::ValidatorAutoUnbonded { validator_id, .. } => {
+                assert!(
+                    self.find_delegator_ids_of(validator_id).is_empty(),
+                    "All delegators should be unbonded first, before unbonding validator '{}'.",
+                    validator_id
+                );
+                self.copy_validator_from_parent(validator_id);
+                self.validator_set.apply_staking_fact(staking_fact);
+            }
+            StakingFact::DelegatorRegistered {
+                delegator_id,
+                validator_id,
+                ..
+            } => {
+                self.removed_delegators
+                    .remove(&(delegator_id.clone(), validator_id.clone()));
+                self.copy_validator_from_parent(validator_id);
+                self.validator_set.apply_staking_fact(staking_fact);
+            }
+            StakingFact::DelegationIncreased {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegationDecreased {
+                delegator_id,
+                validator_id,
+                ..
+            } => {
+                self.copy_validator_from_parent(validator_id);
+                self.copy_delegator_from_parent(delegator_id, validator_id);
+                self.validator_set.apply_staking_fact(staking_fact);
+            }
+            StakingFact::DelegatorUnbonded {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegatorAutoUnbonded {
+                delegator_id,
+                validator_id,
+                ..
+            } => {
+                let exists_in_parent = self
+                    .find_delegator_in_parent(delegator_id, validator_id)
+                    .is_some();
+                self.copy_validator_from_parent(validator_id);
+                self.copy_delegator_from_parent(delegator_id, validator_id);
+                self.validator_set.apply_staking_fact(staking_fact);
+                if exists_in_parent {
+                    self.removed_delegators
+                        .insert(&(delegator_id.clone(), validator_id.clone()));
+                }
+            }
+            StakingFact::DelegationRedelegated {
+                delegator_id,
+                old_validator_id,
+                new_validator_id,
+                amount,
+            } => {
+                let delegator = self.find_delegator(delegator_id, old_validator_id).unwrap();
+                if delegator.deposit_amount > amount.0 {
+                    self.apply_staking_fact(&StakingFact::DelegationDecreased {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: old_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::DelegatorUnbonded {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: old_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                }
+                if self
+                    .find_delegator(delegator_id, new_validator_id)
+                    .is_some()
+                {
+                    self.apply_staking_fact(&StakingFact::DelegationIncreased {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: new_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::DelegatorRegistered {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: new_validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                }
+            }
+            StakingFact::StakeRebonded {
+                validator_id,
+                validator_id_in_appchain,
+                amount,
+                ..
+            } => {
+                if self.validator_set.validator_id_set.contains(validator_id) {
+                    self.apply_staking_fact(&StakingFact::StakeIncreased {
+                        validator_id: validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::ValidatorRegistered {
+                        validator_id: validator_id.clone(),
+                        validator_id_in_appchain: validator_id_in_appchain.clone(),
+                        amount: amount.clone(),
+                        can_be_delegated_to: false,
+                    });
+                }
+            }
+            StakingFact::DelegationRebonded {
+                delegator_id,
+                validator_id,
+                amount,
+                ..
+            } => {
+                if self.find_delegator(delegator_id, validator_id).is_some() {
+                    self.apply_staking_fact(&StakingFact::DelegationIncreased {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::DelegatorRegistered {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: validator_id.clone(),
+                        amount: amount.clone(),
+                    });
+                }
+            }
+            StakingFact::ValidatorSlashed {
+                validator_id,
+                amount,
+                ..
+            } => {
+                self.apply_staking_fact(&StakingFact::StakeDecreased {
+                    validator_id: validator_id.clone(),
+                    amount: amount.clone(),
+                });
+            }
+            StakingFact::DelegatorSlashed {
+                delegator_id,
+                validator_id,
+                amount,
+                ..
+            } => {
+                self.apply_staking_fact(&StakingFact::DelegationDecreased {
+                    delegator_id: delegator_id.clone(),
+                    validator_id: validator_id.clone(),
+                    amount: amount.clone(),
+                });
+            }
+        }
+    }
+    //
+    fn get_parent(&self) -> Option<&ValidatorSetOfEra> {
+        if self.parent_era_number.is_some() {
+            assert!(
+                self.parent.is_some(),
+                "The validator set of parent era is not loaded."
+            );
+        }
+        self.parent.as_deref()
+    }
+    //
+    fn copy_validator_from_parent(&mut self, validator_id: &AccountId) {
+        if !self.validator_set.validators.contains_key(validator_id) {
+            if let Some(validator) = self.find_validator(validator_id) {
+                self.validator_set
+                    .validators
+                    .insert(validator_id, &validator);
+            }
+        }
+    }
+    //
+    fn copy_delegator_from_parent(&mut self, delegator_id: &AccountId, validator_id: &AccountId) {
+        if !self
+            .validator_set
+            .delegators
+            .contains_key(&(delegator_id.clone(), validator_id.clone()))
+        {
+            if let Some(delegator) = self.find_delegator(delegator_id, validator_id) {
+                self.insert_delegator(&delegator);
+            }
+        }
+    }
+    //
+    fn find_validator(&self, validator_id: &AccountId) -> Option<Validator> {
+        if !self.validator_set.validator_id_set.contains(validator_id) {
+            return None;
+        }
+        self.find_validator_record(validator_id)
+    }
+    //
+    fn find_validator_record(&self, validator_id: &AccountId) -> Option<Validator> {
+        match self.validator_set.validators.get(validator_id) {
+            Some(validator) => Some(validator),
+            None => self.find_validator_in_parent(validator_id),
+        }
+    }
+    // The result is cached, so that a validator is looked up through the chain of
+    // parent sets at most once while this set is loaded.
+    fn find_validator_in_parent(&self, validator_id: &AccountId) -> Option<Validator> {
+        if self.parent_era_number.is_none() {
+            return None;
+        }
+        if let Some(validator) = self.parent_validators_cache.borrow().get(validator_id) {
+            return validator.clone();
+        }
+        let validator = self
+            .get_parent()
+            .and_then(|parent| parent.find_validator_record(validator_id));
+        self.parent_validators_cache
+            .borrow_mut()
+            .insert(validator_id.clone(), validator.clone());
+        validator
+    }
+    //
+    fn find_delegator(
+        &self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) -> Option<Delegator> {
+        let key = (delegator_id.clone(), validator_id.clone());
+        if self.removed_delegators.contains(&key) {
+            return None;
+        }
+        match self.validator_set.delegators.get(&key) {
+            Some(delegator) => Some(delegator),
+            None => self.find_delegator_in_parent(delegator_id, validator_id),
+        }
+    }
+    // The result is cached, so that a delegator is looked up through the chain of
+    // parent sets at most once while this set is loaded.
+    fn find_delegator_in_parent(
+        &self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) -> Option<Delegator> {
+        if self.parent_era_number.is_none() {
+            return None;
+        }
+        let key = (delegator_id.clone(), validator_id.clone());
+        if let Some(delegator) = self.parent_delegators_cache.borrow().get(&key) {
+            return delegator.clone();
+        }
+        let delegator = self
+            .get_parent()
+            .and_then(|parent| parent.find_delegator(delegator_id, validator_id));
+        self.parent_delegators_cache
+            .borrow_mut()
+            .insert(key, delegator.clone());
+        delegator
+    }
+    //
+    fn find_delegator_ids_of(&self, validator_id: &AccountId) -> Vec<AccountId> {
+        self.with_delegator_ids_of(validator_id, |delegator_ids| delegator_ids.clone())
+    }
+    // Call the given function with the delegator ids of a validator, which are
+    // materialized from the parent sets only once while this set is loaded.
+    fn with_delegator_ids_of<R>(
+        &self,
+        validator_id: &AccountId,
+        f: impl FnOnce(&Vec<AccountId>) -> R,
+    ) -> R {
+        if !self.delegator_ids_cache.borrow().contains_key(validator_id) {
+            let delegator_ids = self.materialize_delegator_ids_of(validator_id);
+            self.delegator_ids_cache
+                .borrow_mut()
+                .insert(validator_id.clone(), delegator_ids);
+        }
+        f(self.delegator_ids_cache.borrow().get(validator_id).unwrap())
+    }
+    //
+    fn materialize_delegator_ids_of(&self, validator_id: &AccountId) -> Vec<AccountId> {
+        let mut delegator_ids = match self.get_parent() {
+            Some(parent) => {
+                let removed_delegator_ids: HashSet<AccountId> = self
+                    .removed_delegators
+                    .iter()
+                    .filter(|(_, v_id)| v_id.eq(validator_id))
+                    .map(|(d_id, _)| d_id)
+                    .collect();
+                parent.with_delegator_ids_of(validator_id, |delegator_ids| {
+                    delegator_ids
+                        .iter()
+                        .filter(|delegator_id| !removed_delegator_ids.contains(delegator_id))
+                        .cloned()
+                        .collect()
+                })
+            }
+            None => Vec::new(),
+        };
+        let mut delegator_id_set: HashSet<AccountId> = delegator_ids.iter().cloned().collect();
+        self.validator_set
+            .get_delegator_ids_of(validator_id)
+            .into_iter()
+            .for_each(|delegator_id| {
+                if delegator_id_set.insert(delegator_id.clone()) {
+                    delegator_ids.push(delegator_id);
+                }
+            });
+        delegator_ids
+    }
+    //
+    fn find_validator_ids_of(&self, delegator_id: &AccountId) -> Vec<AccountId> {
+        if self.parent_era_number.is_none() {
+            return self.validator_set.get_validator_ids_of(delegator_id);
+        }
+        self.validator_set
+            .validator_id_set
+            .iter()
+            .filter(|validator_id| self.find_delegator(delegator_id, validator_id).is_some())
+            .collect()
     }
     ///
     pub fn get_validator_list(&self) -> Vec<AppchainValidator> {
@@ -265,15 +993,25 @@ impl ValidatorSetOfEra {
         self.validator_set
             .get_validator_ids()
             .iter()
+            .filter(|validator_id| !self.jailed_validator_id_set.contains(validator_id))
             .map(|validator_id| {
                 AppchainValidator::from_validator(
-                    self.validator_set.get_validator(validator_id).unwrap(),
-                    self.validator_set.get_delegator_count_of(validator_id),
+                    self.find_validator(validator_id).unwrap(),
+                    self.get_delegator_count_of(validator_id),
                     false,
+                    self.standby_validator_id_set.contains(validator_id),
                 )
             })
             .collect()
     }
+    /// Get the validator list for the validator set of appchain, which excludes
+    /// the jailed and standby validators.
+    pub fn get_appchain_validator_list(&self) -> Vec<AppchainValidator> {
+        self.get_validator_list()
+            .into_iter()
+            .filter(|validator| !validator.is_standby)
+            .collect()
+    }
 }
 
 impl ValidatorSetViewer for ValidatorSetOfEra {
@@ -282,29 +1020,32 @@ impl ValidatorSetViewer for ValidatorSetOfEra {
         if !self.all_staking_histories_are_applied() {
             return false;
         }
-        self.validator_set.contains_validator(validator_id)
+        self.validator_set.validator_id_set.contains(validator_id)
     }
     //
     fn contains_delegator(&self, delegator_id: &AccountId, validator_id: &AccountId) -> bool {
         if !self.all_staking_histories_are_applied() {
             return false;
         }
-        self.validator_set
-            .contains_delegator(delegator_id, validator_id)
+        self.find_delegator(delegator_id, validator_id).is_some()
     }
     //
     fn get_validator(&self, validator_id: &AccountId) -> Option<Validator> {
         if !self.all_staking_histories_are_applied() {
             return None;
         }
-        self.validator_set.get_validator(validator_id)
+        self.find_validator(validator_id)
     }
     //
     fn get_validator_by_index(&self, index: &u64) -> Option<Validator> {
         if !self.all_staking_histories_are_applied() {
             return None;
         }
-        self.validator_set.get_validator_by_index(index)
+        self.validator_set
+            .validator_id_set
+            .as_vector()
+            .get(*index)
+            .and_then(|validator_id| self.find_validator(&validator_id))
     }
     //
     fn get_delegator(
@@ -315,15 +1056,17 @@ impl ValidatorSetViewer for ValidatorSetOfEra {
         if !self.all_staking_histories_are_applied() {
             return None;
         }
-        self.validator_set.get_delegator(delegator_id, validator_id)
+        self.find_delegator(delegator_id, validator_id)
     }
     //
     fn get_delegator_by_index(&self, index: &u64, validator_id: &AccountId) -> Option<Delegator> {
         if !self.all_staking_histories_are_applied() {
             return None;
         }
-        self.validator_set
-            .get_delegator_by_index(index, validator_id)
+        self.with_delegator_ids_of(validator_id, |delegator_ids| {
+            delegator_ids.get(usize::try_from(*index).unwrap()).cloned()
+        })
+        .and_then(|delegator_id| self.find_delegator(&delegator_id, validator_id))
     }
     //
     fn get_validator_ids(&self) -> Vec<AccountId> {
@@ -337,28 +1080,33 @@ impl ValidatorSetViewer for ValidatorSetOfEra {
         if !self.all_staking_histories_are_applied() {
             return Vec::new();
         }
-        self.validator_set.get_validator_ids_of(delegator_id)
+        self.find_validator_ids_of(delegator_id)
     }
     //
     fn get_delegator_ids_of(&self, validator_id: &AccountId) -> Vec<AccountId> {
         if !self.all_staking_histories_are_applied() {
             return Vec::new();
         }
-        self.validator_set.get_delegator_ids_of(validator_id)
+        self.find_delegator_ids_of(validator_id)
     }
     //
     fn get_validator_count_of(&self, delegator_id: &AccountId) -> u64 {
         if !self.all_staking_histories_are_applied() {
             return 0;
         }
-        self.validator_set.get_validator_count_of(delegator_id)
+        self.find_validator_ids_of(delegator_id)
+            .len()
+            .try_into()
+            .unwrap()
     }
     //
     fn get_delegator_count_of(&self, validator_id: &AccountId) -> u64 {
         if !self.all_staking_histories_are_applied() {
             return 0;
         }
-        self.validator_set.get_delegator_count_of(validator_id)
+        self.with_delegator_ids_of(validator_id, |delegator_ids| delegator_ids.len())
+            .try_into()
+            .unwrap()
     }
     //
     fn era_number(&self) -> u64 {
@@ -383,7 +1131,11 @@ impl ValidatorSetViewer for ValidatorSetOfEra {
         if !self.all_staking_histories_are_applied() {
             return 0;
         }
-        self.validator_set.delegator_count()
+        self.validator_set
+            .validator_id_set
+            .iter()
+            .map(|validator_id| self.get_delegator_count_of(&validator_id))
+            .sum()
     }
 }
 
@@ -396,4 +1148,12 @@ impl IndexedAndClearable for ValidatorSetOfEra {
     fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
         self.clear()
     }
+    //
+    fn load_base_records(&mut self, lookup_array: &LookupArray<Self>) {
+        if let Some(era_number) = self.parent_era_number {
+            self.parent = Some(Box::new(lookup_array.get(&era_number).unwrap_or_else(
+                || panic!("Missing validator set of era '{}'.", era_number),
+            )));
+        }
+    }
 }
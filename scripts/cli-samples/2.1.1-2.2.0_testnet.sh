@@ -14,3 +14,4 @@ near call $ANCHOR_ACCOUNT_ID migrate_state '' --accountId $ANCHOR_ACCOUNT_ID --g
 near call $ANCHOR_ACCOUNT_ID migrate_staking_histories '{"start_index":"0"}' --accountId $ANCHOR_ACCOUNT_ID --gas 200000000000000
 near call $ANCHOR_ACCOUNT_ID migrate_appchain_notification_histories '{"start_index":"0"}' --accountId $ANCHOR_ACCOUNT_ID --gas 200000000000000
 near call $ANCHOR_ACCOUNT_ID migrate_appchain_messages '{"start_nonce":0}' --accountId $ANCHOR_ACCOUNT_ID --gas 200000000000000
+near call $ANCHOR_ACCOUNT_ID migrate_appchain_message_processing_results '' --accountId $ANCHOR_ACCOUNT_ID --gas 200000000000000
@@ -10,9 +10,12 @@ use crate::{
     contract_interfaces::{anchor_viewer, permissionless_actions, staking_actions},
 };
 use appchain_anchor::{
-    appchain_messages::{EraPayoutPayload, RawMessage},
+    appchain_messages::{EraPayoutPayload, Offender, RawMessage},
     appchain_messages::{PayloadType, PlanNewEraPayload},
-    types::{AnchorStatus, MultiTxsOperationProcessingResult, ValidatorSetInfo},
+    types::{
+        AnchorStatus, ExpiredRewardSweepHistory, MultiTxsOperationProcessingResult,
+        ValidatorSetInfo,
+    },
 };
 use near_primitives::borsh::BorshSerialize;
 use near_sdk::{json_types::U64, serde_json};
@@ -108,6 +111,7 @@ pub async fn distribute_reward_of(
     nonce: u32,
     era_number: u32,
     unprofitable_validator_ids: Vec<String>,
+    offenders: Vec<Offender>,
     to_confirm_view_result: bool,
 ) -> anyhow::Result<()> {
     let anchor_balance_of_wat =
@@ -115,7 +119,7 @@ pub async fn distribute_reward_of(
     let payload = EraPayoutPayload {
         end_era: era_number,
         excluded_validators: unprofitable_validator_ids,
-        offenders: Vec::new(),
+        offenders,
     };
     let raw_message = RawMessage {
         nonce: nonce as u64,
@@ -144,11 +148,15 @@ pub async fn distribute_reward_of(
         println!();
     }
     process_appchain_messages(worker, relayer, anchor).await?;
+    let validator_set_info =
+        anchor_viewer::get_validator_set_info_of(worker, anchor, U64::from(u64::from(era_number)))
+            .await?;
     assert_eq!(
         common::get_ft_balance_of(worker, &anchor.as_account(), &wrapped_appchain_token)
             .await?
             .0,
-        anchor_balance_of_wat.0 + common::to_actual_amount(10, 18)
+        anchor_balance_of_wat.0 + validator_set_info.era_reward.0
+            - validator_set_info.treasury_reward.0
     );
     if to_confirm_view_result {
         let anchor_status = anchor_viewer::get_anchor_status(worker, anchor).await?;
@@ -169,6 +177,12 @@ pub async fn distribute_reward_of(
             serde_json::to_string::<ValidatorSetInfo>(&validator_set_info).unwrap()
         );
         println!();
+        assert_eq!(
+            validator_set_info.total_reward.0,
+            validator_set_info.distributed_reward.0
+                + validator_set_info.reward_dust.0
+                + validator_set_info.swept_reward_dust.0
+        );
         print_appchain_notifications(worker, &anchor).await?;
     }
     Ok(())
@@ -215,6 +229,12 @@ pub async fn withdraw_delegator_rewards_of(
     print_wat_balance_of_anchor(worker, anchor, wrapped_appchain_token).await?;
     let wat_balance_before_withdraw =
         common::get_ft_balance_of(worker, user, wrapped_appchain_token).await?;
+    let unwithdrawn_reward_before_withdraw: u128 =
+        anchor_viewer::get_delegator_rewards_of(worker, anchor, 0, end_era, user, validator)
+            .await?
+            .iter()
+            .map(|reward_history| reward_history.unwithdrawn_reward.0)
+            .sum();
     staking_actions::withdraw_delegator_rewards(
         worker,
         user,
@@ -223,13 +243,24 @@ pub async fn withdraw_delegator_rewards_of(
         &validator.id().to_string().parse().unwrap(),
     )
     .await?;
+    let withdrawn_reward = common::get_ft_balance_of(worker, user, wrapped_appchain_token)
+        .await?
+        .0
+        - wat_balance_before_withdraw.0;
     println!(
         "User '{}' withdrawed delegator rewards: {}",
         &user.id(),
-        common::get_ft_balance_of(worker, user, wrapped_appchain_token)
+        withdrawn_reward
+    );
+    let unwithdrawn_reward_after_withdraw: u128 =
+        anchor_viewer::get_delegator_rewards_of(worker, anchor, 0, end_era, user, validator)
             .await?
-            .0
-            - wat_balance_before_withdraw.0
+            .iter()
+            .map(|reward_history| reward_history.unwithdrawn_reward.0)
+            .sum();
+    assert_eq!(
+        unwithdrawn_reward_before_withdraw - unwithdrawn_reward_after_withdraw,
+        withdrawn_reward
     );
     println!();
     print_delegator_reward_histories(worker, anchor, user, validator, end_era).await?;
@@ -259,3 +290,35 @@ pub async fn withdraw_stake_of(
     print_unbonded_stakes_of(worker, anchor, user).await?;
     Ok(())
 }
+
+pub async fn sweep_expired_rewards_of(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<ExpiredRewardSweepHistory> {
+    loop {
+        let result =
+            permissionless_actions::sweep_expired_rewards(worker, signer, anchor, era_number)
+                .await?
+                .json::<MultiTxsOperationProcessingResult>()?;
+        println!(
+            "Sweep expired rewards of era {}: {}",
+            era_number,
+            serde_json::to_string::<MultiTxsOperationProcessingResult>(&result).unwrap()
+        );
+        println!();
+        match result {
+            MultiTxsOperationProcessingResult::Ok => break,
+            MultiTxsOperationProcessingResult::NeedMoreGas => (),
+            MultiTxsOperationProcessingResult::Error(message) => {
+                panic!("Failed to sweep expired rewards: {}", &message);
+            }
+        }
+    }
+    let history = anchor_viewer::get_expired_reward_sweep_history_of(worker, anchor, era_number)
+        .await?
+        .expect("The history of sweeping expired rewards is not found.");
+    assert!(history.is_completed);
+    Ok(history)
+}
@@ -203,6 +203,7 @@ pub async fn test_normal_actions(
         amount0,
         true,
         HashMap::new(),
+        None,
     )
     .await
     .expect("Failed in calling 'register_validator'");
@@ -231,6 +232,7 @@ pub async fn test_normal_actions(
         amount0,
         true,
         HashMap::new(),
+        None,
     )
     .await
     .expect("Failed in calling 'register_validator'");
@@ -274,6 +276,7 @@ pub async fn test_normal_actions(
         amount1,
         false,
         HashMap::new(),
+        None,
     )
     .await
     .expect("Failed in calling 'register_validator'");
@@ -597,6 +600,7 @@ pub async fn test_normal_actions(
         amount4,
         true,
         user4_profile,
+        Some(10),
     )
     .await
     .expect("Failed in calling 'register_validator'");
@@ -1,12 +1,16 @@
-use appchain_anchor::appchain_challenge::AppchainChallenge;
+use appchain_anchor::appchain_challenge::AppchainChallengeInfo;
 use appchain_anchor::types::{
     AnchorSettings, AnchorStatus, AppchainCommitment, AppchainDelegator,
     AppchainMessageProcessingResult, AppchainNotificationHistory, AppchainSettings, AppchainState,
-    AppchainValidator, IndexRange, NearFungibleToken, RewardHistory, StakingHistory, UnbondedStake,
-    UserStakingHistory, ValidatorProfile, ValidatorSetInfo, WrappedAppchainToken,
+    AppchainValidator, ContractCallSettings, DelegationPolicy, ExpiredRewardSweepHistory,
+    IncentivePool, IndexRange, JailedValidator, NearFungibleToken, PayloadCodec,
+    PendingStakingDeposit, ProtocolSettings, QueuedUnbondingValidator, RewardHistory,
+    RewardSchedule, RewardSettings, SlashingSettings, StakingHistory, UnbondedStake,
+    UserStakingHistory, ValidatorCommission, ValidatorProfile, ValidatorSetInfo,
+    WrappedAppchainToken,
 };
 use appchain_anchor::AppchainMessage;
-use near_sdk::json_types::U64;
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde_json::json;
 use near_sdk::AccountId;
 use workspaces::{network::Sandbox, Account, Contract, Worker};
@@ -22,6 +26,111 @@ pub async fn get_anchor_settings(
         .json::<AnchorSettings>()
 }
 
+pub async fn get_protocol_settings(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<ProtocolSettings> {
+    anchor
+        .call(worker, "get_protocol_settings")
+        .view()
+        .await?
+        .json::<ProtocolSettings>()
+}
+
+pub async fn get_slashing_settings(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<SlashingSettings> {
+    anchor
+        .call(worker, "get_slashing_settings")
+        .view()
+        .await?
+        .json::<SlashingSettings>()
+}
+
+pub async fn get_reward_schedule(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<RewardSchedule> {
+    anchor
+        .call(worker, "get_reward_schedule")
+        .view()
+        .await?
+        .json::<RewardSchedule>()
+}
+
+pub async fn get_payload_codec(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<PayloadCodec> {
+    anchor
+        .call(worker, "get_payload_codec")
+        .view()
+        .await?
+        .json::<PayloadCodec>()
+}
+
+pub async fn get_contract_call_settings(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<ContractCallSettings> {
+    anchor
+        .call(worker, "get_contract_call_settings")
+        .view()
+        .await?
+        .json::<ContractCallSettings>()
+}
+
+pub async fn get_expired_reward_sweep_history_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<Option<ExpiredRewardSweepHistory>> {
+    anchor
+        .call(worker, "get_expired_reward_sweep_history_of")
+        .args_json(json!({ "era_number": U64::from(era_number) }))?
+        .view()
+        .await?
+        .json::<Option<ExpiredRewardSweepHistory>>()
+}
+
+pub async fn get_reward_settings_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<Option<RewardSettings>> {
+    anchor
+        .call(worker, "get_reward_settings_of")
+        .args_json(json!({ "account_id": account_id }))?
+        .view()
+        .await?
+        .json::<Option<RewardSettings>>()
+}
+
+pub async fn get_reward_credit_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<U128> {
+    anchor
+        .call(worker, "get_reward_credit_of")
+        .args_json(json!({ "account_id": account_id }))?
+        .view()
+        .await?
+        .json::<U128>()
+}
+
+pub async fn get_storage_fund_balance(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<U128> {
+    anchor
+        .call(worker, "get_storage_fund_balance")
+        .view()
+        .await?
+        .json::<U128>()
+}
+
 pub async fn get_appchain_settings(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
@@ -171,6 +280,19 @@ pub async fn get_validator_list_of(
         .json::<Vec<AppchainValidator>>()
 }
 
+pub async fn get_appchain_validator_list_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<Vec<AppchainValidator>> {
+    anchor
+        .call(worker, "get_appchain_validator_list_of")
+        .args_json(json!({ "era_number": U64::from(era_number) }))?
+        .view()
+        .await?
+        .json::<Vec<AppchainValidator>>()
+}
+
 pub async fn get_validator_profile(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
@@ -199,6 +321,75 @@ pub async fn get_validator_profile_by_id_in_appchain(
         .json::<Option<ValidatorProfile>>()
 }
 
+pub async fn get_validator_commission_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    validator: &Account,
+) -> anyhow::Result<Option<ValidatorCommission>> {
+    anchor
+        .call(worker, "get_validator_commission_of")
+        .args_json(json!({ "validator_id": validator.id() }))?
+        .view()
+        .await?
+        .json::<Option<ValidatorCommission>>()
+}
+
+pub async fn get_validator_commission_percent_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    validator: &Account,
+    era_number: Option<u64>,
+) -> anyhow::Result<u16> {
+    anchor
+        .call(worker, "get_validator_commission_percent_of")
+        .args_json(json!({
+            "validator_id": validator.id(),
+            "era_number": era_number.map(|i| U64::from(i))
+        }))?
+        .view()
+        .await?
+        .json::<u16>()
+}
+
+pub async fn get_validator_operator_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    validator: &Account,
+) -> anyhow::Result<Option<AccountId>> {
+    anchor
+        .call(worker, "get_validator_operator_of")
+        .args_json(json!({ "validator_id": validator.id() }))?
+        .view()
+        .await?
+        .json::<Option<AccountId>>()
+}
+
+pub async fn get_pending_staking_deposit_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    account: &Account,
+) -> anyhow::Result<Option<PendingStakingDeposit>> {
+    anchor
+        .call(worker, "get_pending_staking_deposit_of")
+        .args_json(json!({ "account_id": account.id() }))?
+        .view()
+        .await?
+        .json::<Option<PendingStakingDeposit>>()
+}
+
+pub async fn get_delegation_policy_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    validator: &Account,
+) -> anyhow::Result<Option<DelegationPolicy>> {
+    anchor
+        .call(worker, "get_delegation_policy_of")
+        .args_json(json!({ "validator_id": validator.id() }))?
+        .view()
+        .await?
+        .json::<Option<DelegationPolicy>>()
+}
+
 pub async fn get_delegators_of_validator_in_era(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
@@ -271,6 +462,61 @@ pub async fn get_delegator_rewards_of(
         .json::<Vec<RewardHistory>>()
 }
 
+pub async fn get_incentive_pools(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<Vec<IncentivePool>> {
+    anchor
+        .call(worker, "get_incentive_pools")
+        .view()
+        .await?
+        .json::<Vec<IncentivePool>>()
+}
+
+pub async fn get_validator_incentive_rewards_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    token_contract: &Contract,
+    start_era: u64,
+    end_era: u64,
+    validator: &Account,
+) -> anyhow::Result<Vec<RewardHistory>> {
+    anchor
+        .call(worker, "get_validator_incentive_rewards_of")
+        .args_json(json!({
+            "token_contract": token_contract.id(),
+            "start_era": U64::from(start_era),
+            "end_era": U64::from(end_era),
+            "validator_id": validator.id()
+        }))?
+        .view()
+        .await?
+        .json::<Vec<RewardHistory>>()
+}
+
+pub async fn get_delegator_incentive_rewards_of(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    token_contract: &Contract,
+    start_era: u64,
+    end_era: u64,
+    delegator: &Account,
+    validator: &Account,
+) -> anyhow::Result<Vec<RewardHistory>> {
+    anchor
+        .call(worker, "get_delegator_incentive_rewards_of")
+        .args_json(json!({
+            "token_contract": token_contract.id(),
+            "start_era": U64::from(start_era),
+            "end_era": U64::from(end_era),
+            "delegator_id": delegator.id(),
+            "validator_id": validator.id()
+        }))?
+        .view()
+        .await?
+        .json::<Vec<RewardHistory>>()
+}
+
 pub async fn get_latest_commitment_of_appchain(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
@@ -329,17 +575,34 @@ pub async fn get_appchain_message_processing_results(
         .json::<Vec<AppchainMessageProcessingResult>>()
 }
 
+pub async fn get_missing_appchain_message_nonces(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    start_nonce: u32,
+    quantity: Option<u32>,
+) -> anyhow::Result<Vec<u32>> {
+    anchor
+        .call(worker, "get_missing_appchain_message_nonces")
+        .args_json(json!({
+            "start_nonce": start_nonce,
+            "quantity": quantity
+        }))?
+        .view()
+        .await?
+        .json::<Vec<u32>>()
+}
+
 pub async fn get_appchain_challenge(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
     index: u64,
-) -> anyhow::Result<Option<AppchainChallenge>> {
+) -> anyhow::Result<Option<AppchainChallengeInfo>> {
     anchor
         .call(worker, "get_appchain_challenge")
         .args_json(json!({ "index": Some(U64::from(index)) }))?
         .view()
         .await?
-        .json::<Option<AppchainChallenge>>()
+        .json::<Option<AppchainChallengeInfo>>()
 }
 
 pub async fn get_appchain_challenges(
@@ -347,7 +610,7 @@ pub async fn get_appchain_challenges(
     anchor: &Contract,
     start_index: u64,
     quantity: Option<U64>,
-) -> anyhow::Result<Vec<AppchainChallenge>> {
+) -> anyhow::Result<Vec<AppchainChallengeInfo>> {
     anchor
         .call(worker, "get_appchain_challenges")
         .args_json(json!({
@@ -356,5 +619,27 @@ pub async fn get_appchain_challenges(
         }))?
         .view()
         .await?
-        .json::<Vec<AppchainChallenge>>()
+        .json::<Vec<AppchainChallengeInfo>>()
+}
+
+pub async fn get_jailed_validators(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<Vec<JailedValidator>> {
+    anchor
+        .call(worker, "get_jailed_validators")
+        .view()
+        .await?
+        .json::<Vec<JailedValidator>>()
+}
+
+pub async fn get_queued_unbonding_validators(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<Vec<QueuedUnbondingValidator>> {
+    anchor
+        .call(worker, "get_queued_unbonding_validators")
+        .view()
+        .await?
+        .json::<Vec<QueuedUnbondingValidator>>()
 }
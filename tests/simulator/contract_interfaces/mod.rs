@@ -1,9 +1,11 @@
 pub mod anchor_viewer;
 pub mod lifecycle_actions;
 pub mod near_fungible_token_manager;
+pub mod owner_actions;
 pub mod permissionless_actions;
 pub mod settings_manager;
 pub mod staking_actions;
+pub mod sudo_actions;
 pub mod validator_actions;
 pub mod wrapped_appchain_nft_manager;
 pub mod wrapped_appchain_token_manager;
@@ -0,0 +1,30 @@
+use near_sdk::{json_types::U64, serde_json::json};
+use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
+
+pub async fn sweep_reward_dust_of_era(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "sweep_reward_dust_of_era")
+        .args_json(json!({ "era_number": U64::from(era_number) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn deposit_to_storage_fund(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    amount: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "deposit_to_storage_fund")
+        .deposit(amount)
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
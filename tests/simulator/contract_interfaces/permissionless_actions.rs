@@ -1,5 +1,5 @@
 use appchain_anchor::types::{MultiTxsOperationProcessingResult, ValidatorMerkleProof};
-use near_sdk::serde_json::json;
+use near_sdk::{json_types::U64, serde_json::json};
 use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
 
 pub async fn verify_and_stage_appchain_messages(
@@ -87,3 +87,20 @@ pub async fn try_complete_updating_state_of_beefy_light_client(
     println!("{:?}", result);
     result.json::<MultiTxsOperationProcessingResult>()
 }
+
+pub async fn sweep_expired_rewards(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    let result = signer
+        .call(worker, anchor.id(), "sweep_expired_rewards")
+        .gas(300_000_000_000_000)
+        .args_json(json!({ "era_number": U64::from(era_number) }))?
+        .transact()
+        .await;
+    println!("{:?}", result);
+    println!();
+    result
+}
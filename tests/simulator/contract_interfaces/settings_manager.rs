@@ -1,6 +1,11 @@
+use appchain_anchor::types::{
+    PayloadCodec, RewardSchedule, SlashedTokenDestination, UnprofitableValidatorRewardPolicy,
+    ValidatorChurnLimit,
+};
 use near_sdk::{
     json_types::{U128, U64},
     serde_json::json,
+    AccountId,
 };
 use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
 
@@ -18,6 +23,98 @@ pub async fn change_minimum_validator_count(
         .await
 }
 
+pub async fn change_maximum_validator_count(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_maximum_validator_count")
+        .args_json(json!({ "value": U64::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_maximum_era_count_of_unwithdrawn_reward(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(
+            worker,
+            anchor.id(),
+            "change_maximum_era_count_of_unwithdrawn_reward",
+        )
+        .args_json(json!({ "value": U64::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_validator_exit_limit(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: &ValidatorChurnLimit,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_validator_exit_limit")
+        .args_json(json!({ "value": value }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_treasury_account(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: &Account,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_treasury_account")
+        .args_json(json!({ "value": value.id() }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_treasury_reward_percent(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u16,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_treasury_reward_percent")
+        .args_json(json!({ "value": value }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_unprofitable_validator_reward_policy(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: UnprofitableValidatorRewardPolicy,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(
+            worker,
+            anchor.id(),
+            "change_unprofitable_validator_reward_policy",
+        )
+        .args_json(json!({ "value": value }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
 pub async fn set_rpc_endpoint(
     worker: &Worker<Sandbox>,
     signer: &Account,
@@ -60,6 +157,20 @@ pub async fn set_era_reward(
         .await
 }
 
+pub async fn set_reward_schedule(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    reward_schedule: RewardSchedule,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_reward_schedule")
+        .args_json(json!({ "reward_schedule": reward_schedule }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
 pub async fn set_bonus_for_new_validator(
     worker: &Worker<Sandbox>,
     signer: &Account,
@@ -74,6 +185,20 @@ pub async fn set_bonus_for_new_validator(
         .await
 }
 
+pub async fn set_payload_codec(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    payload_codec: PayloadCodec,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_payload_codec")
+        .args_json(json!({ "payload_codec": payload_codec }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
 pub async fn set_token_price_maintainer_account(
     worker: &Worker<Sandbox>,
     signer: &Account,
@@ -187,3 +312,117 @@ pub async fn turn_off_beefy_light_client_witness_mode(
         .transact()
         .await
 }
+
+pub async fn set_slash_percent_of_offence_kind(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    offence_kind: String,
+    slash_percent: u16,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_slash_percent_of_offence_kind")
+        .args_json(json!({
+            "offence_kind": offence_kind,
+            "slash_percent": slash_percent,
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_slashed_token_destination(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    destination: SlashedTokenDestination,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_slashed_token_destination")
+        .args_json(json!({ "destination": destination }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn allow_contract_call(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    contract_id: &AccountId,
+    method_name: String,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "allow_contract_call")
+        .args_json(json!({
+            "contract_id": contract_id,
+            "method_name": method_name
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn disallow_contract_call(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    contract_id: &AccountId,
+    method_name: String,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "disallow_contract_call")
+        .args_json(json!({
+            "contract_id": contract_id,
+            "method_name": method_name
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_maximum_gas_of_contract_call(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_maximum_gas_of_contract_call")
+        .args_json(json!({ "value": U64::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_maximum_deposit_of_contract_call(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_maximum_deposit_of_contract_call")
+        .args_json(json!({ "value": U128::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_contract_call_budget(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    gas: u64,
+    deposit: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_contract_call_budget")
+        .args_json(json!({
+            "gas": U64::from(gas),
+            "deposit": U128::from(deposit)
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
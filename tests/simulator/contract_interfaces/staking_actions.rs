@@ -1,5 +1,10 @@
 use crate::common;
-use near_sdk::{json_types::U128, serde_json::json, AccountId};
+use appchain_anchor::types::RewardDestination;
+use near_sdk::{
+    json_types::{U128, U64},
+    serde_json::json,
+    AccountId,
+};
 use std::collections::HashMap;
 use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
 
@@ -12,6 +17,7 @@ pub async fn register_validator(
     amount: u128,
     can_be_delegated_to: bool,
     profile: HashMap<String, String>,
+    commission_percent: Option<u16>,
 ) -> anyhow::Result<CallExecutionDetails> {
     let result = common::call_ft_transfer_call(
         worker,
@@ -22,7 +28,8 @@ pub async fn register_validator(
             "RegisterValidator": {
                 "validator_id_in_appchain": account_id_in_appchain,
                 "can_be_delegated_to": can_be_delegated_to,
-                "profile": profile
+                "profile": profile,
+                "commission_percent": commission_percent
             }
         })
         .to_string(),
@@ -58,6 +65,32 @@ pub async fn register_delegator(
     .await
 }
 
+pub async fn fund_incentive_pool(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    token_contract: &Contract,
+    anchor: &Contract,
+    eras: u64,
+    per_era_amount: u128,
+    amount: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    common::call_ft_transfer_call(
+        worker,
+        signer,
+        &anchor.as_account(),
+        amount,
+        json!({
+            "FundIncentivePool": {
+                "eras": U64::from(eras),
+                "per_era_amount": U128::from(per_era_amount)
+            }
+        })
+        .to_string(),
+        token_contract,
+    )
+    .await
+}
+
 pub async fn increase_stake(
     worker: &Worker<Sandbox>,
     signer: &Account,
@@ -100,6 +133,104 @@ pub async fn increase_delegation(
     .await
 }
 
+pub async fn increase_delegation_for(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    oct_token: &Contract,
+    anchor: &Contract,
+    delegator_id: &AccountId,
+    validator_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    common::call_ft_transfer_call(
+        worker,
+        signer,
+        &anchor.as_account(),
+        amount,
+        json!({
+            "IncreaseDelegationFor": {
+                "delegator_id": delegator_id,
+                "validator_id": validator_id
+            }
+        })
+        .to_string(),
+        oct_token,
+    )
+    .await
+}
+
+pub async fn register_validator_for(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    oct_token: &Contract,
+    anchor: &Contract,
+    validator_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    common::call_ft_transfer_call(
+        worker,
+        signer,
+        &anchor.as_account(),
+        amount,
+        json!({
+            "RegisterValidatorFor": {
+                "validator_id": validator_id
+            }
+        })
+        .to_string(),
+        oct_token,
+    )
+    .await
+}
+
+pub async fn accept_validator_registration(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    account_id_in_appchain: &String,
+    can_be_delegated_to: bool,
+    profile: HashMap<String, String>,
+    commission_percent: Option<u16>,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "accept_validator_registration")
+        .args_json(json!({
+            "validator_id_in_appchain": account_id_in_appchain,
+            "can_be_delegated_to": can_be_delegated_to,
+            "profile": profile,
+            "commission_percent": commission_percent
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn accept_staking_deposit(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "accept_staking_deposit")
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn cancel_staking_deposit(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    beneficiary_id: &AccountId,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "cancel_staking_deposit")
+        .args_json(json!({ "beneficiary_id": beneficiary_id }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
 pub async fn decrease_stake(
     worker: &Worker<Sandbox>,
     signer: &Account,
@@ -158,6 +289,58 @@ pub async fn unbond_delegation(
         .await
 }
 
+pub async fn redelegate(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    from_validator_id: &AccountId,
+    to_validator_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "redelegate")
+        .args_json(json!({
+            "from_validator_id": from_validator_id,
+            "to_validator_id": to_validator_id,
+            "amount": U128::from(amount)
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn rebond_stake(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    staking_history_index: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "rebond_stake")
+        .args_json(json!({ "staking_history_index": U64::from(staking_history_index) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn rebond_delegation(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    validator_id: &AccountId,
+    staking_history_index: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "rebond_delegation")
+        .args_json(json!({
+            "validator_id": validator_id,
+            "staking_history_index": U64::from(staking_history_index)
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
 pub async fn withdraw_stake(
     worker: &Worker<Sandbox>,
     signer: &Account,
@@ -203,3 +386,59 @@ pub async fn withdraw_delegator_rewards(
         .transact()
         .await
 }
+
+pub async fn withdraw_validator_incentive_rewards(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    token_contract: &AccountId,
+    validator_id: &AccountId,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "withdraw_validator_incentive_rewards")
+        .args_json(json!({
+            "token_contract": token_contract,
+            "validator_id": validator_id
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn withdraw_delegator_incentive_rewards(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    token_contract: &AccountId,
+    delegator_id: &AccountId,
+    validator_id: &AccountId,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "withdraw_delegator_incentive_rewards")
+        .args_json(json!({
+            "token_contract": token_contract,
+            "delegator_id": delegator_id,
+            "validator_id": validator_id
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_reward_settings(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    destination: RewardDestination,
+    auto_claim: bool,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_reward_settings")
+        .args_json(json!({
+            "destination": destination,
+            "auto_claim": auto_claim
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -0,0 +1,52 @@
+use near_sdk::{json_types::U64, serde_json::json};
+use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
+
+pub async fn jail_validator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    validator: &Account,
+    era_count: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "jail_validator")
+        .args_json(json!({
+            "validator_id": validator.id(),
+            "era_count": U64::from(era_count)
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn unjail_validator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    validator: &Account,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "unjail_validator")
+        .args_json(json!({ "validator_id": validator.id() }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn skip_appchain_messages(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    nonces: Vec<u32>,
+    reason: String,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "skip_appchain_messages")
+        .args_json(json!({
+            "nonces": nonces,
+            "reason": reason
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
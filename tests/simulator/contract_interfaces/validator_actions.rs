@@ -1,3 +1,4 @@
+use appchain_anchor::types::DelegationPolicy;
 use near_sdk::serde_json::json;
 use std::collections::HashMap;
 use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
@@ -31,3 +32,69 @@ pub async fn set_validator_profile(
         .transact()
         .await
 }
+
+pub async fn set_validator_commission_percent(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    commission_percent: u16,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_validator_commission_percent")
+        .args_json(json!({ "commission_percent": commission_percent }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_delegation_policy(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    policy: &DelegationPolicy,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_delegation_policy")
+        .args_json(json!({ "policy": policy }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_validator_operator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    operator: &Account,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_validator_operator")
+        .args_json(json!({ "operator_id": operator.id() }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn revoke_validator_operator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "revoke_validator_operator")
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn unjail(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "unjail")
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -4,6 +4,7 @@ mod contract_interfaces;
 mod test_anchor_actions;
 mod test_beefy_light_client;
 mod test_beefy_light_client_2;
+mod test_contract_calls;
 mod test_equivocation_challenge;
 mod test_migration;
 mod test_transfer_nft;
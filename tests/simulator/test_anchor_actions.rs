@@ -1,8 +1,18 @@
 use crate::{
     common,
-    contract_interfaces::{anchor_viewer, settings_manager, staking_actions},
+    contract_interfaces::{
+        anchor_viewer, owner_actions, permissionless_actions, settings_manager, staking_actions,
+        sudo_actions, validator_actions,
+    },
 };
-use near_sdk::json_types::U64;
+use appchain_anchor::appchain_messages::Offender;
+use appchain_anchor::types::{
+    DelegationCap, DelegationPolicy, FundedStakingAction, JailReason, RewardDestination,
+    RewardSchedule, RewardScheduleStep, SlashedTokenDestination, StakingFact,
+    SweptRewardTransferStatus, UnprofitableValidatorRewardPolicy, ValidatorChurnLimit,
+};
+use near_sdk::json_types::{U128, U64};
+use near_units::parse_near;
 use std::collections::HashMap;
 use workspaces::{network::Sandbox, Account, Contract, Worker};
 
@@ -48,6 +58,7 @@ async fn test_anchor_actions() -> anyhow::Result<()> {
         appchain_message_nonce,
         0,
         Vec::new(),
+        Vec::new(),
         false,
     )
     .await
@@ -137,6 +148,370 @@ async fn test_staking_actions(
         .expect("Failed to unbond stakes");
     assert!(unbonded_stakes.len() == 0);
     //
+    // user3 redelegate from user0 to user1 (error)
+    //
+    assert!(!staking_actions::redelegate(
+        &worker,
+        &users[3],
+        &anchor,
+        &users[0].id().to_string().parse().unwrap(),
+        &users[1].id().to_string().parse().unwrap(),
+        common::to_actual_amount(500, 18),
+    )
+    .await?
+    .is_success());
+    common::complex_viewer::print_anchor_status(&worker, &anchor).await?;
+    //
+    // user3 redelegate from user0 to user4
+    //
+    assert!(staking_actions::redelegate(
+        &worker,
+        &users[3],
+        &anchor,
+        &users[0].id().to_string().parse().unwrap(),
+        &users[4].id().to_string().parse().unwrap(),
+        common::to_actual_amount(500, 18),
+    )
+    .await?
+    .is_success());
+    common::complex_viewer::print_anchor_status(&worker, &anchor).await?;
+    let unbonded_stakes = anchor_viewer::get_unbonded_stakes_of(&worker, &anchor, &users[3])
+        .await
+        .expect("Failed to unbond stakes");
+    assert!(unbonded_stakes.len() == 0);
+    //
+    // user0 change commission percent
+    //
+    assert!(
+        !validator_actions::set_validator_commission_percent(&worker, &users[0], &anchor, 30)
+            .await?
+            .is_success()
+    );
+    assert!(
+        anchor_viewer::get_validator_commission_of(&worker, &anchor, &users[0])
+            .await?
+            .is_none()
+    );
+    assert!(
+        validator_actions::set_validator_commission_percent(&worker, &users[0], &anchor, 25)
+            .await?
+            .is_success()
+    );
+    // Another change is not allowed before the pending change takes effect
+    assert!(
+        !validator_actions::set_validator_commission_percent(&worker, &users[0], &anchor, 22)
+            .await?
+            .is_success()
+    );
+    let commission = anchor_viewer::get_validator_commission_of(&worker, &anchor, &users[0])
+        .await?
+        .unwrap();
+    assert_eq!(commission.commission_percent, 20);
+    assert_eq!(commission.pending_commission_percent, Some(25));
+    assert_eq!(
+        anchor_viewer::get_validator_commission_percent_of(&worker, &anchor, &users[0], None)
+            .await?,
+        20
+    );
+    assert_eq!(
+        anchor_viewer::get_validator_commission_percent_of(&worker, &anchor, &users[4], Some(1))
+            .await?,
+        10
+    );
+    //
+    // user4 set delegation policy
+    //
+    let delegation_policy = DelegationPolicy {
+        allowed_delegators: Some(vec![users[3].id().to_string().parse().unwrap()]),
+        maximum_delegated_stake: Some(DelegationCap::MultipleOfDeposit(2)),
+        maximum_delegator_count: Some(U64::from(5)),
+        minimum_delegation: Some(U128::from(common::to_actual_amount(300, 18))),
+    };
+    validator_actions::set_delegation_policy(&worker, &users[4], &anchor, &delegation_policy)
+        .await
+        .expect("Failed to set delegation policy");
+    assert_eq!(
+        anchor_viewer::get_delegation_policy_of(&worker, &anchor, &users[4]).await?,
+        Some(delegation_policy)
+    );
+    //
+    // user2 register delegator to user4 (error)
+    //
+    let user2_balance = common::get_ft_balance_of(&worker, &users[2], &oct_token).await?;
+    staking_actions::register_delegator(
+        &worker,
+        &users[2],
+        &oct_token,
+        &anchor,
+        &users[4].id().to_string().parse().unwrap(),
+        common::to_actual_amount(300, 18),
+    )
+    .await
+    .expect("Failed to register delegator");
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[2], &oct_token)
+            .await?
+            .0,
+        user2_balance.0
+    );
+    //
+    // user0 set user5 as operator, and user5 change the profile of user0
+    //
+    validator_actions::set_validator_operator(&worker, &users[0], &anchor, &users[5])
+        .await
+        .expect("Failed to set validator operator");
+    assert_eq!(
+        anchor_viewer::get_validator_operator_of(&worker, &anchor, &users[0])
+            .await?
+            .unwrap()
+            .to_string(),
+        users[5].id().to_string()
+    );
+    let mut user0_profile_by_operator = HashMap::<String, String>::new();
+    user0_profile_by_operator.insert("key0".to_string(), "value5".to_string());
+    validator_actions::set_validator_profile(
+        &worker,
+        &users[5],
+        &anchor,
+        &user0_profile_by_operator,
+    )
+    .await
+    .expect("Failed to set validator profile");
+    let user0_profile_in_anchor = anchor_viewer::get_validator_profile(
+        &worker,
+        &anchor,
+        &users[0].id().to_string().parse().unwrap(),
+    )
+    .await?
+    .unwrap();
+    assert_eq!(
+        user0_profile_in_anchor.profile.get("key0"),
+        Some(&"value5".to_string())
+    );
+    //
+    // user0 revoke the operator, user5 can not change the profile any more
+    //
+    validator_actions::revoke_validator_operator(&worker, &users[0], &anchor)
+        .await
+        .expect("Failed to revoke validator operator");
+    assert!(
+        anchor_viewer::get_validator_operator_of(&worker, &anchor, &users[0])
+            .await?
+            .is_none()
+    );
+    validator_actions::set_validator_profile(&worker, &users[5], &anchor, &user0_profile)
+        .await
+        .expect("Failed to set validator profile");
+    let user0_profile_in_anchor = anchor_viewer::get_validator_profile(
+        &worker,
+        &anchor,
+        &users[0].id().to_string().parse().unwrap(),
+    )
+    .await?
+    .unwrap();
+    assert_eq!(
+        user0_profile_in_anchor.profile.get("key0"),
+        Some(&"value5".to_string())
+    );
+    //
+    // root jail user0 for 1 era, user0 can not unjail itself before the sentence expires
+    //
+    sudo_actions::jail_validator(&worker, &root, &anchor, &users[0], 1)
+        .await
+        .expect("Failed to jail validator");
+    let jailed_validators = anchor_viewer::get_jailed_validators(&worker, &anchor).await?;
+    assert_eq!(jailed_validators.len(), 1);
+    assert_eq!(
+        jailed_validators[0].validator_id.to_string(),
+        users[0].id().to_string()
+    );
+    assert_eq!(jailed_validators[0].reason, JailReason::ByOwner);
+    assert!(anchor_viewer::get_validator_list_of(&worker, &anchor, None)
+        .await?
+        .iter()
+        .all(|v| !v.validator_id.to_string().eq(&users[0].id().to_string())));
+    assert!(!validator_actions::unjail(&worker, &users[0], &anchor)
+        .await?
+        .is_success());
+    assert_eq!(
+        anchor_viewer::get_jailed_validators(&worker, &anchor)
+            .await?
+            .len(),
+        1
+    );
+    sudo_actions::unjail_validator(&worker, &root, &anchor, &users[0])
+        .await
+        .expect("Failed to unjail validator");
+    assert!(anchor_viewer::get_jailed_validators(&worker, &anchor)
+        .await?
+        .is_empty());
+    //
+    // Reduce the maximum validator count, the validators with less stake become standby
+    //
+    settings_manager::change_maximum_validator_count(&worker, &root, &anchor, 2)
+        .await
+        .expect("Failed to change maximum validator count");
+    let validator_list = anchor_viewer::get_validator_list_of(&worker, &anchor, None).await?;
+    let active_validators: Vec<_> = validator_list.iter().filter(|v| !v.is_standby).collect();
+    let standby_validators: Vec<_> = validator_list.iter().filter(|v| v.is_standby).collect();
+    assert_eq!(active_validators.len(), validator_list.len().min(2));
+    assert_eq!(
+        standby_validators.len(),
+        validator_list.len() - active_validators.len()
+    );
+    for standby_validator in standby_validators {
+        assert!(active_validators
+            .iter()
+            .all(|v| v.total_stake.0 >= standby_validator.total_stake.0));
+    }
+    settings_manager::change_maximum_validator_count(&worker, &root, &anchor, 60)
+        .await
+        .expect("Failed to change maximum validator count");
+    assert!(anchor_viewer::get_validator_list_of(&worker, &anchor, None)
+        .await?
+        .iter()
+        .all(|v| !v.is_standby));
+    //
+    // Change the exit limit of validators
+    //
+    let exit_limit = ValidatorChurnLimit {
+        maximum_validator_count: Some(U64::from(2)),
+        maximum_validator_count_percent: None,
+        maximum_stake: None,
+        maximum_stake_percent: Some(20),
+    };
+    settings_manager::change_validator_exit_limit(&worker, &root, &anchor, &exit_limit)
+        .await
+        .expect("Failed to change validator exit limit");
+    assert_eq!(
+        anchor_viewer::get_protocol_settings(&worker, &anchor)
+            .await?
+            .validator_exit_limit,
+        exit_limit
+    );
+    assert!(
+        anchor_viewer::get_queued_unbonding_validators(&worker, &anchor)
+            .await?
+            .is_empty()
+    );
+    //
+    // user2 increase delegation of user3 to user4
+    //
+    let user2_staking_histories = anchor_viewer::get_user_staking_histories_of(
+        &worker,
+        &anchor,
+        users[2].id().to_string().parse().unwrap(),
+    )
+    .await?;
+    staking_actions::increase_delegation_for(
+        &worker,
+        &users[2],
+        &oct_token,
+        &anchor,
+        &users[3].id().to_string().parse().unwrap(),
+        &users[4].id().to_string().parse().unwrap(),
+        common::to_actual_amount(100, 18),
+    )
+    .await
+    .expect("Failed to increase delegation");
+    let pending_deposit =
+        anchor_viewer::get_pending_staking_deposit_of(&worker, &anchor, &users[3])
+            .await?
+            .expect("The pending staking deposit should exist.");
+    assert_eq!(
+        pending_deposit.action,
+        FundedStakingAction::IncreaseDelegation {
+            validator_id: users[4].id().to_string().parse().unwrap()
+        }
+    );
+    assert!(
+        !staking_actions::accept_staking_deposit(&worker, &users[4], &anchor)
+            .await?
+            .is_success()
+    );
+    assert!(
+        staking_actions::accept_staking_deposit(&worker, &users[3], &anchor)
+            .await?
+            .is_success()
+    );
+    let staking_histories = anchor_viewer::get_user_staking_histories_of(
+        &worker,
+        &anchor,
+        users[2].id().to_string().parse().unwrap(),
+    )
+    .await?;
+    assert_eq!(staking_histories.len(), user2_staking_histories.len() + 1);
+    match &staking_histories.last().unwrap().staking_fact {
+        StakingFact::DelegationIncreased { delegator_id, .. } => {
+            assert_eq!(delegator_id.to_string(), users[3].id().to_string())
+        }
+        _ => panic!("Unexpected staking fact."),
+    }
+    //
+    // user2 fund the validator registration of user5, and user5 reject it
+    //
+    let user2_balance = common::get_ft_balance_of(&worker, &users[2], &oct_token).await?;
+    assert!(staking_actions::register_validator_for(
+        &worker,
+        &users[2],
+        &oct_token,
+        &anchor,
+        &users[5].id().to_string().parse().unwrap(),
+        common::to_actual_amount(25_000, 18),
+    )
+    .await?
+    .is_success());
+    let pending_registration =
+        anchor_viewer::get_pending_staking_deposit_of(&worker, &anchor, &users[5])
+            .await?
+            .expect("The pending staking deposit should exist.");
+    assert_eq!(
+        pending_registration.funder_id.to_string(),
+        users[2].id().to_string()
+    );
+    assert_eq!(
+        pending_registration.deposit_amount.0,
+        common::to_actual_amount(25_000, 18)
+    );
+    assert!(!staking_actions::accept_validator_registration(
+        &worker,
+        &users[3],
+        &anchor,
+        &"0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48".to_string(),
+        false,
+        HashMap::new(),
+        None,
+    )
+    .await?
+    .is_success());
+    assert!(!staking_actions::cancel_staking_deposit(
+        &worker,
+        &users[3],
+        &anchor,
+        &users[5].id().to_string().parse().unwrap(),
+    )
+    .await?
+    .is_success());
+    assert!(staking_actions::cancel_staking_deposit(
+        &worker,
+        &users[5],
+        &anchor,
+        &users[5].id().to_string().parse().unwrap(),
+    )
+    .await?
+    .is_success());
+    assert!(
+        anchor_viewer::get_pending_staking_deposit_of(&worker, &anchor, &users[5])
+            .await?
+            .is_none()
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[2], &oct_token)
+            .await?
+            .0,
+        user2_balance.0
+    );
+    //
     // Print staking histories
     //
     common::complex_viewer::print_staking_histories(&worker, &anchor).await?;
@@ -155,6 +530,21 @@ async fn test_staking_actions(
     .await
     .expect("Failed to switch era");
     common::complex_viewer::print_validator_list_of(&worker, &anchor, Some(2)).await?;
+    let active_validator_ids: Vec<String> =
+        anchor_viewer::get_validator_list_of(&worker, &anchor, Some(2))
+            .await?
+            .iter()
+            .filter(|v| !v.is_standby)
+            .map(|v| v.validator_id.to_string())
+            .collect();
+    assert_eq!(
+        anchor_viewer::get_appchain_validator_list_of(&worker, &anchor, 2)
+            .await?
+            .iter()
+            .map(|v| v.validator_id.to_string())
+            .collect::<Vec<String>>(),
+        active_validator_ids
+    );
     common::complex_viewer::print_delegator_list_of(&worker, &anchor, 2, &users[0]).await?;
     //
     // Distribute reward of era1
@@ -168,6 +558,7 @@ async fn test_staking_actions(
         appchain_message_nonce,
         1,
         [user0_id_in_appchain.clone()].to_vec(),
+        Vec::new(),
         true,
     )
     .await
@@ -239,7 +630,54 @@ async fn test_staking_actions(
     common::complex_viewer::print_validator_list_of(&worker, &anchor, Some(3)).await?;
     common::complex_viewer::print_delegator_list_of(&worker, &anchor, 3, &users[0]).await?;
     //
-    // Distribute reward of era2
+    // Check the validator set of era3, which is based on the validator set of era2
+    //
+    let delegators =
+        anchor_viewer::get_delegators_of_validator_in_era(&worker, &anchor, 3, &users[0]).await?;
+    let validator = anchor_viewer::get_validator_list_of(&worker, &anchor, Some(3))
+        .await?
+        .into_iter()
+        .find(|validator| validator.validator_id.as_str() == users[0].id().as_str())
+        .expect("Validator is not found in era3");
+    assert_eq!(validator.delegators_count.0, delegators.len() as u64);
+    assert_eq!(
+        validator.total_stake.0,
+        validator.deposit_amount.0
+            + delegators
+                .iter()
+                .map(|delegator| delegator.delegation_amount.0)
+                .sum::<u128>()
+    );
+    //
+    // Set slashing settings
+    //
+    settings_manager::set_slash_percent_of_offence_kind(
+        &worker,
+        &root,
+        &anchor,
+        "im-online".to_string(),
+        10,
+    )
+    .await
+    .expect("Failed in calling 'set_slash_percent_of_offence_kind'");
+    settings_manager::set_slashed_token_destination(
+        &worker,
+        &root,
+        &anchor,
+        SlashedTokenDestination::Treasury(users[1].id().to_string().parse().unwrap()),
+    )
+    .await
+    .expect("Failed in calling 'set_slashed_token_destination'");
+    let slashing_settings = anchor_viewer::get_slashing_settings(&worker, &anchor).await?;
+    assert_eq!(
+        slashing_settings
+            .slash_percents_of_offence_kinds
+            .get("im-online"),
+        Some(&10)
+    );
+    let user1_oct_balance = common::get_ft_balance_of(&worker, &users[1], &oct_token).await?;
+    //
+    // Distribute reward of era2 with user4 as an offender
     //
     appchain_message_nonce += 1;
     common::complex_actions::distribute_reward_of(
@@ -250,10 +688,38 @@ async fn test_staking_actions(
         appchain_message_nonce,
         2,
         [user0_id_in_appchain.clone(), user4_id_in_appchain.clone()].to_vec(),
+        [Offender {
+            kind: "im-online".to_string(),
+            who: user4_id_in_appchain.clone(),
+            offences: 1,
+        }]
+        .to_vec(),
         true,
     )
     .await
     .expect("Failed to distribute rewards");
+    let staking_histories = anchor_viewer::get_user_staking_histories_of(
+        &worker,
+        &anchor,
+        users[4].id().to_string().parse().unwrap(),
+    )
+    .await?;
+    match &staking_histories.last().unwrap().staking_fact {
+        StakingFact::ValidatorSlashed {
+            slash_percent,
+            amount,
+            ..
+        } => {
+            assert_eq!(*slash_percent, 10);
+            assert_eq!(
+                common::get_ft_balance_of(&worker, &users[1], &oct_token)
+                    .await?
+                    .0,
+                user1_oct_balance.0 + amount.0
+            );
+        }
+        _ => panic!("Unexpected staking fact."),
+    }
     common::complex_viewer::print_wrapped_appchain_token_info(&worker, &anchor).await?;
     common::complex_viewer::print_staking_histories(&worker, &anchor).await?;
     common::complex_viewer::print_validator_reward_histories(&worker, &anchor, &users[0], 2)
@@ -276,6 +742,40 @@ async fn test_staking_actions(
     common::complex_viewer::print_unbonded_stakes_of(&worker, &anchor, &users[3]).await?;
     common::complex_viewer::print_unbonded_stakes_of(&worker, &anchor, &users[4]).await?;
     //
+    // user1 rebond the decreased stake
+    //
+    let unbonded_stakes = anchor_viewer::get_unbonded_stakes_of(&worker, &anchor, &users[1])
+        .await
+        .expect("Failed to unbond stakes");
+    assert!(unbonded_stakes.len() == 1);
+    staking_actions::rebond_stake(
+        &worker,
+        &users[1],
+        &anchor,
+        unbonded_stakes[0].staking_history_index.0,
+    )
+    .await
+    .expect("Failed to rebond stake");
+    common::complex_viewer::print_unbonded_stakes_of(&worker, &anchor, &users[1]).await?;
+    //
+    // user2 rebond the unbonded delegation
+    //
+    let unbonded_stakes = anchor_viewer::get_unbonded_stakes_of(&worker, &anchor, &users[2])
+        .await
+        .expect("Failed to unbond stakes");
+    staking_actions::rebond_delegation(
+        &worker,
+        &users[2],
+        &anchor,
+        &users[0].id().to_string().parse().unwrap(),
+        unbonded_stakes[unbonded_stakes.len() - 1]
+            .staking_history_index
+            .0,
+    )
+    .await
+    .expect("Failed to rebond delegation");
+    common::complex_viewer::print_unbonded_stakes_of(&worker, &anchor, &users[2]).await?;
+    //
     // user0 unbond stake
     //
     // let result = staking_actions::unbond_stake(&users[0], &anchor).await?;
@@ -312,6 +812,29 @@ async fn test_staking_actions(
     common::complex_viewer::print_validator_list_of(&worker, &anchor, Some(4)).await?;
     common::complex_viewer::print_delegator_list_of(&worker, &anchor, 4, &users[0]).await?;
     //
+    // Only reward the delegators of unprofitable validators
+    //
+    assert!(
+        !settings_manager::change_unprofitable_validator_reward_policy(
+            &worker,
+            &root,
+            &anchor,
+            UnprofitableValidatorRewardPolicy::Treasury,
+        )
+        .await?
+        .is_success()
+    );
+    assert!(
+        settings_manager::change_unprofitable_validator_reward_policy(
+            &worker,
+            &root,
+            &anchor,
+            UnprofitableValidatorRewardPolicy::Delegators,
+        )
+        .await?
+        .is_success()
+    );
+    //
     // Distribute reward of era3
     //
     common::complex_viewer::print_validator_set_info_of(&worker, &anchor, U64::from(3)).await?;
@@ -324,10 +847,31 @@ async fn test_staking_actions(
         appchain_message_nonce,
         3,
         [user0_id_in_appchain.clone(), user4_id_in_appchain.clone()].to_vec(),
+        Vec::new(),
         true,
     )
     .await
     .expect("Failed to distribute rewards");
+    let validator_set_info =
+        anchor_viewer::get_validator_set_info_of(&worker, &anchor, U64::from(3)).await?;
+    assert!(validator_set_info.withheld_reward.0 > 0);
+    assert!(
+        anchor_viewer::get_delegator_rewards_of(&worker, &anchor, 3, 3, &users[2], &users[0])
+            .await?
+            .iter()
+            .any(|reward_history| reward_history.era_number.0 == 3
+                && reward_history.total_reward.0 > 0)
+    );
+    assert!(
+        settings_manager::change_unprofitable_validator_reward_policy(
+            &worker,
+            &root,
+            &anchor,
+            UnprofitableValidatorRewardPolicy::Redistribute,
+        )
+        .await?
+        .is_success()
+    );
     common::complex_viewer::print_wrapped_appchain_token_info(&worker, &anchor).await?;
     common::complex_viewer::print_staking_histories(&worker, &anchor).await?;
     common::complex_viewer::print_validator_reward_histories(&worker, &anchor, &users[0], 3)
@@ -349,6 +893,14 @@ async fn test_staking_actions(
     common::complex_viewer::print_unbonded_stakes_of(&worker, &anchor, &users[2]).await?;
     common::complex_viewer::print_unbonded_stakes_of(&worker, &anchor, &users[3]).await?;
     common::complex_viewer::print_unbonded_stakes_of(&worker, &anchor, &users[4]).await?;
+    //
+    // Sweep the reward dust of era3 (error, the treasury account is not set)
+    //
+    assert!(
+        !owner_actions::sweep_reward_dust_of_era(&worker, &root, &anchor, 3)
+            .await?
+            .is_success()
+    );
     appchain_message_nonce += 1;
     common::complex_actions::distribute_reward_of(
         &worker,
@@ -358,6 +910,7 @@ async fn test_staking_actions(
         appchain_message_nonce,
         3,
         Vec::new(),
+        Vec::new(),
         true,
     )
     .await
@@ -399,6 +952,143 @@ async fn test_staking_actions(
     common::complex_viewer::print_validator_list_of(&worker, &anchor, Some(5)).await?;
     common::complex_viewer::print_delegator_list_of(&worker, &anchor, 5, &users[0]).await?;
     //
+    // Change the reward schedule to a step table which doubles the era reward from era4
+    //
+    let reward_schedule = RewardSchedule::StepTable {
+        steps: vec![RewardScheduleStep {
+            start_era_number: U64::from(4),
+            era_reward: U128::from(common::to_actual_amount(20, 18)),
+        }],
+    };
+    assert!(!settings_manager::set_reward_schedule(
+        &worker,
+        &users[5],
+        &anchor,
+        reward_schedule.clone()
+    )
+    .await?
+    .is_success());
+    assert!(!settings_manager::set_reward_schedule(
+        &worker,
+        &root,
+        &anchor,
+        RewardSchedule::StepTable { steps: Vec::new() },
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::set_reward_schedule(
+        &worker,
+        &root,
+        &anchor,
+        reward_schedule.clone()
+    )
+    .await?
+    .is_success());
+    assert_eq!(
+        anchor_viewer::get_reward_schedule(&worker, &anchor).await?,
+        reward_schedule
+    );
+    //
+    // Send 10 percent of era reward to user1 as the treasury account
+    //
+    assert!(
+        settings_manager::change_treasury_account(&worker, &root, &anchor, &users[1])
+            .await?
+            .is_success()
+    );
+    assert!(
+        !settings_manager::change_treasury_reward_percent(&worker, &root, &anchor, 100)
+            .await?
+            .is_success()
+    );
+    assert!(
+        settings_manager::change_treasury_reward_percent(&worker, &root, &anchor, 10)
+            .await?
+            .is_success()
+    );
+    //
+    // Sweep the reward dust of era3 to the treasury account
+    //
+    let validator_set_info =
+        anchor_viewer::get_validator_set_info_of(&worker, &anchor, U64::from(3)).await?;
+    let user1_wat_balance =
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token).await?;
+    let result = owner_actions::sweep_reward_dust_of_era(&worker, &root, &anchor, 3).await?;
+    assert_eq!(result.is_success(), validator_set_info.reward_dust.0 > 0);
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token)
+            .await?
+            .0,
+        user1_wat_balance.0 + validator_set_info.reward_dust.0
+    );
+    let validator_set_info =
+        anchor_viewer::get_validator_set_info_of(&worker, &anchor, U64::from(3)).await?;
+    assert_eq!(validator_set_info.reward_dust.0, 0);
+    if result.is_success() {
+        assert_eq!(
+            validator_set_info.reward_dust_transfer_status,
+            SweptRewardTransferStatus::Transferred
+        );
+    }
+    let user1_wat_balance =
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token).await?;
+    //
+    // Fund an incentive pool of OCT token for 2 eras
+    //
+    let root_oct_balance = common::get_ft_balance_of(&worker, &root, &oct_token).await?;
+    assert!(staking_actions::fund_incentive_pool(
+        &worker,
+        &root,
+        &oct_token,
+        &anchor,
+        2,
+        common::to_actual_amount(10, 18),
+        common::to_actual_amount(25, 18),
+    )
+    .await?
+    .is_success());
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &root, &oct_token)
+            .await?
+            .0,
+        root_oct_balance.0 - common::to_actual_amount(20, 18)
+    );
+    let incentive_pools = anchor_viewer::get_incentive_pools(&worker, &anchor).await?;
+    assert!(incentive_pools.len() == 1);
+    assert_eq!(incentive_pools[0].remaining_era_count.0, 2);
+    //
+    // user3 enables auto-claim and pays rewards to user4
+    //
+    assert!(staking_actions::set_reward_settings(
+        &worker,
+        &users[3],
+        &anchor,
+        RewardDestination::Account {
+            account_id: users[4].id().to_string().parse().unwrap(),
+        },
+        true,
+    )
+    .await?
+    .is_success());
+    assert!(anchor_viewer::get_reward_settings_of(
+        &worker,
+        &anchor,
+        &users[3].id().to_string().parse().unwrap()
+    )
+    .await?
+    .is_some());
+    let user4_wat_balance =
+        common::get_ft_balance_of(&worker, &users[4], &wrapped_appchain_token).await?;
+    let mut user3_unwithdrawn_reward: u128 = 0;
+    for validator in [&users[0], &users[4]] {
+        user3_unwithdrawn_reward +=
+            anchor_viewer::get_delegator_rewards_of(&worker, &anchor, 0, 3, &users[3], validator)
+                .await?
+                .iter()
+                .map(|history| history.unwithdrawn_reward.0)
+                .sum::<u128>();
+    }
+    //
     // Distribute reward of era4
     //
     common::complex_viewer::print_validator_set_info_of(&worker, &anchor, U64::from(4)).await?;
@@ -411,10 +1101,143 @@ async fn test_staking_actions(
         appchain_message_nonce,
         4,
         Vec::new(),
+        Vec::new(),
         true,
     )
     .await
     .expect("Failed to distribute rewards");
+    assert_eq!(
+        anchor_viewer::get_validator_set_info_of(&worker, &anchor, U64::from(3))
+            .await?
+            .era_reward
+            .0,
+        common::to_actual_amount(10, 18)
+    );
+    let validator_set_info =
+        anchor_viewer::get_validator_set_info_of(&worker, &anchor, U64::from(4)).await?;
+    assert_eq!(
+        validator_set_info.era_reward.0,
+        common::to_actual_amount(20, 18)
+    );
+    assert_eq!(
+        validator_set_info.treasury_account,
+        Some(users[1].id().to_string().parse().unwrap())
+    );
+    assert_eq!(
+        validator_set_info.treasury_reward.0,
+        common::to_actual_amount(2, 18)
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token)
+            .await?
+            .0,
+        user1_wat_balance.0 + validator_set_info.treasury_reward.0
+    );
+    //
+    // Check auto-claimed rewards of user3
+    //
+    let mut user3_era4_reward: u128 = 0;
+    let mut user3_remained_reward: u128 = 0;
+    for validator in [&users[0], &users[4]] {
+        let reward_histories =
+            anchor_viewer::get_delegator_rewards_of(&worker, &anchor, 0, 4, &users[3], validator)
+                .await?;
+        user3_era4_reward += reward_histories
+            .iter()
+            .filter(|history| history.era_number.0 == 4)
+            .map(|history| history.total_reward.0)
+            .sum::<u128>();
+        user3_remained_reward += reward_histories
+            .iter()
+            .map(|history| history.unwithdrawn_reward.0)
+            .sum::<u128>();
+    }
+    assert_eq!(user3_remained_reward, 0);
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[4], &wrapped_appchain_token)
+            .await?
+            .0,
+        user4_wat_balance.0 + user3_unwithdrawn_reward + user3_era4_reward
+    );
+    assert_eq!(
+        anchor_viewer::get_reward_credit_of(
+            &worker,
+            &anchor,
+            &users[3].id().to_string().parse().unwrap()
+        )
+        .await?
+        .0,
+        0
+    );
+    //
+    // Withdraw incentive rewards of era4
+    //
+    assert_eq!(
+        anchor_viewer::get_incentive_pools(&worker, &anchor).await?[0]
+            .remaining_era_count
+            .0,
+        1
+    );
+    assert_eq!(
+        validator_set_info.incentive_rewards[0].amount.0,
+        common::to_actual_amount(10, 18)
+    );
+    let reward_histories = anchor_viewer::get_validator_incentive_rewards_of(
+        &worker, &anchor, &oct_token, 4, 4, &users[0],
+    )
+    .await?;
+    assert!(reward_histories.len() == 1);
+    assert!(reward_histories[0].unwithdrawn_reward.0 > 0);
+    let user0_oct_balance = common::get_ft_balance_of(&worker, &users[0], &oct_token).await?;
+    assert!(staking_actions::withdraw_validator_incentive_rewards(
+        &worker,
+        &users[5],
+        &anchor,
+        &oct_token.id().to_string().parse().unwrap(),
+        &users[0].id().to_string().parse().unwrap(),
+    )
+    .await?
+    .is_success());
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[0], &oct_token)
+            .await?
+            .0,
+        user0_oct_balance.0 + reward_histories[0].total_reward.0
+    );
+    assert_eq!(
+        anchor_viewer::get_validator_incentive_rewards_of(
+            &worker, &anchor, &oct_token, 4, 4, &users[0],
+        )
+        .await?[0]
+            .unwithdrawn_reward
+            .0,
+        0
+    );
+    let reward_histories = anchor_viewer::get_delegator_incentive_rewards_of(
+        &worker, &anchor, &oct_token, 4, 4, &users[2], &users[0],
+    )
+    .await?;
+    let user2_oct_balance = common::get_ft_balance_of(&worker, &users[2], &oct_token).await?;
+    assert!(staking_actions::withdraw_delegator_incentive_rewards(
+        &worker,
+        &users[5],
+        &anchor,
+        &oct_token.id().to_string().parse().unwrap(),
+        &users[2].id().to_string().parse().unwrap(),
+        &users[0].id().to_string().parse().unwrap(),
+    )
+    .await?
+    .is_success());
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[2], &oct_token)
+            .await?
+            .0,
+        user2_oct_balance.0
+            + reward_histories
+                .iter()
+                .map(|history| history.unwithdrawn_reward.0)
+                .sum::<u128>()
+    );
     common::complex_viewer::print_wrapped_appchain_token_info(&worker, &anchor).await?;
     common::complex_viewer::print_staking_histories(&worker, &anchor).await?;
     common::complex_viewer::print_validator_reward_histories(&worker, &anchor, &users[0], 4)
@@ -508,6 +1331,70 @@ async fn test_staking_actions(
         .await
         .expect("Failed in calling 'withdraw_stake_of'");
     //
+    // Sweep the expired rewards of era1 to the treasury account
+    //
+    assert!(
+        settings_manager::change_maximum_era_count_of_unwithdrawn_reward(
+            &worker, &root, &anchor, 1
+        )
+        .await?
+        .is_success()
+    );
+    assert!(
+        anchor_viewer::get_expired_reward_sweep_history_of(&worker, &anchor, 1)
+            .await?
+            .is_none()
+    );
+    let treasury = root
+        .create_subaccount(&worker, "treasury")
+        .initial_balance(parse_near!("1 N"))
+        .transact()
+        .await?
+        .unwrap();
+    assert!(
+        settings_manager::change_treasury_account(&worker, &root, &anchor, &treasury)
+            .await?
+            .is_success()
+    );
+    let history = common::complex_actions::sweep_expired_rewards_of(&worker, &users[5], &anchor, 1)
+        .await
+        .expect("Failed to sweep expired rewards");
+    let total_reward = history.validator_reward.0 + history.delegator_reward.0;
+    if total_reward > 0 {
+        // The treasury account is not registered in the token contract,
+        // so the transfer fails and can be re-sent after the registration.
+        assert_eq!(history.transfer_status, SweptRewardTransferStatus::Failed);
+        common::basic_actions::register_user_to_ft_contract(
+            &worker,
+            &treasury,
+            &wrapped_appchain_token,
+        )
+        .await?;
+        assert!(
+            permissionless_actions::sweep_expired_rewards(&worker, &users[5], &anchor, 1)
+                .await?
+                .is_success()
+        );
+    }
+    assert_eq!(
+        anchor_viewer::get_expired_reward_sweep_history_of(&worker, &anchor, 1)
+            .await?
+            .unwrap()
+            .transfer_status,
+        SweptRewardTransferStatus::Transferred
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &treasury, &wrapped_appchain_token)
+            .await?
+            .0,
+        total_reward
+    );
+    assert!(
+        !permissionless_actions::sweep_expired_rewards(&worker, &users[5], &anchor, 1)
+            .await?
+            .is_success()
+    );
+    //
     // Print whole status
     //
     common::complex_viewer::print_anchor_status(&worker, &anchor).await?;
@@ -74,6 +74,7 @@ async fn test_beefy_light_client() -> anyhow::Result<()> {
         appchain_message_nonce,
         0,
         Vec::new(),
+        Vec::new(),
         true,
     )
     .await?;
@@ -154,6 +155,7 @@ async fn test_beefy_light_client() -> anyhow::Result<()> {
         appchain_message_nonce,
         1,
         Vec::new(),
+        Vec::new(),
         true,
     )
     .await?;
@@ -234,6 +236,7 @@ async fn test_beefy_light_client() -> anyhow::Result<()> {
         appchain_message_nonce,
         2,
         Vec::new(),
+        Vec::new(),
         true,
     )
     .await?;
@@ -297,6 +300,7 @@ async fn test_beefy_light_client() -> anyhow::Result<()> {
         appchain_message_nonce,
         3,
         Vec::new(),
+        Vec::new(),
         true,
     )
     .await?;
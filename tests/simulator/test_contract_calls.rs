@@ -0,0 +1,222 @@
+use crate::{
+    common,
+    contract_interfaces::{anchor_viewer, permissionless_actions, settings_manager},
+};
+use appchain_anchor::{
+    appchain_messages::{ContractCallPayload, PayloadType, RawMessage},
+    types::{AppchainMessageError, AppchainMessageProcessingResult},
+};
+use near_sdk::{borsh::BorshSerialize, serde_json::json, AccountId};
+use near_units::parse_near;
+use parity_scale_codec::Encode;
+
+#[tokio::test]
+async fn test_contract_calls_from_appchain() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let (
+        root,
+        oct_token,
+        _wrapped_appchain_token,
+        registry,
+        anchor,
+        _wat_faucet,
+        users,
+        mut appchain_message_nonce,
+    ) = common::test_normal_actions(&worker, false, true, vec!["0x00".to_string()]).await?;
+    let oct_token_id: AccountId = oct_token.id().to_string().parse().unwrap();
+    let registry_id: AccountId = registry.id().to_string().parse().unwrap();
+    let user0_id_in_appchain =
+        "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d".to_string();
+    //
+    // Manage contract call settings
+    //
+    assert!(!settings_manager::allow_contract_call(
+        &worker,
+        &users[0],
+        &anchor,
+        &registry_id,
+        "sync_state_of".to_string()
+    )
+    .await?
+    .is_success());
+    // The token contracts managed by the anchor are protected
+    assert!(!settings_manager::allow_contract_call(
+        &worker,
+        &root,
+        &anchor,
+        &oct_token_id,
+        "ft_transfer".to_string()
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::allow_contract_call(
+        &worker,
+        &root,
+        &anchor,
+        &registry_id,
+        "sync_state_of".to_string()
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::allow_contract_call(
+        &worker,
+        &root,
+        &anchor,
+        &registry_id,
+        "new".to_string()
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::disallow_contract_call(
+        &worker,
+        &root,
+        &anchor,
+        &registry_id,
+        "new".to_string()
+    )
+    .await?
+    .is_success());
+    assert!(!settings_manager::set_maximum_gas_of_contract_call(
+        &worker,
+        &root,
+        &anchor,
+        200_000_000_000_000
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::set_maximum_gas_of_contract_call(
+        &worker,
+        &root,
+        &anchor,
+        10_000_000_000_000
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::set_maximum_deposit_of_contract_call(
+        &worker,
+        &root,
+        &anchor,
+        parse_near!("1 N")
+    )
+    .await?
+    .is_success());
+    assert!(!settings_manager::set_contract_call_budget(
+        &worker,
+        &users[0],
+        &anchor,
+        10_000_000_000_000,
+        0
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::set_contract_call_budget(
+        &worker,
+        &root,
+        &anchor,
+        10_000_000_000_000,
+        0
+    )
+    .await?
+    .is_success());
+    let contract_call_settings =
+        anchor_viewer::get_contract_call_settings(&worker, &anchor).await?;
+    assert_eq!(
+        contract_call_settings.allowed_methods.get(&registry_id),
+        Some(&vec!["sync_state_of".to_string()])
+    );
+    assert_eq!(contract_call_settings.maximum_gas.0, 10_000_000_000_000);
+    assert_eq!(contract_call_settings.maximum_deposit.0, parse_near!("1 N"));
+    assert_eq!(
+        contract_call_settings.remaining_gas_budget.0,
+        10_000_000_000_000
+    );
+    assert_eq!(contract_call_settings.remaining_deposit_budget.0, 0);
+    //
+    // Request contract calls from appchain
+    //
+    let start_nonce = appchain_message_nonce + 1;
+    let mut raw_messages = Vec::new();
+    for (method_name, gas) in [
+        ("sync_state_of", 10_000_000_000_000),
+        ("new", 10_000_000_000_000),
+        ("sync_state_of", 20_000_000_000_000),
+        ("sync_state_of", 10_000_000_000_000),
+    ] {
+        appchain_message_nonce += 1;
+        let payload = ContractCallPayload {
+            sender: user0_id_in_appchain.clone(),
+            contract_id: registry_id.clone(),
+            method_name: method_name.to_string(),
+            args: json!({
+                "appchain_id": "appchain",
+                "appchain_state": "Active",
+                "validator_count": 0,
+                "total_stake": "0"
+            })
+            .to_string(),
+            deposit: 0,
+            gas,
+        };
+        raw_messages.push(RawMessage {
+            nonce: appchain_message_nonce as u64,
+            payload_type: PayloadType::ContractCall,
+            payload: payload.try_to_vec().unwrap(),
+        });
+    }
+    permissionless_actions::verify_and_stage_appchain_messages(
+        &worker,
+        &users[5],
+        &anchor,
+        raw_messages.encode(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    )
+    .await
+    .expect("Failed to call 'verify_and_stage_appchain_messages'");
+    common::complex_actions::process_appchain_messages(&worker, &users[4], &anchor).await?;
+    common::complex_viewer::print_appchain_messages(&worker, &anchor).await?;
+    common::complex_viewer::print_appchain_messages_processing_results(&worker, &anchor).await?;
+    let processing_results = anchor_viewer::get_appchain_message_processing_results(
+        &worker,
+        &anchor,
+        start_nonce,
+        Some(4),
+    )
+    .await?;
+    assert!(match &processing_results[0] {
+        AppchainMessageProcessingResult::Ok { .. } => true,
+        _ => false,
+    });
+    assert!(match &processing_results[1] {
+        AppchainMessageProcessingResult::Error { error, .. } =>
+            error.eq(&AppchainMessageError::ContractCallNotAllowed {
+                contract_id: registry_id.clone(),
+                method_name: "new".to_string(),
+            }),
+        _ => false,
+    });
+    assert!(match &processing_results[2] {
+        AppchainMessageProcessingResult::Error { error, .. } => match error {
+            AppchainMessageError::ContractCallGasExceeded { .. } => true,
+            _ => false,
+        },
+        _ => false,
+    });
+    // The gas budget is exhausted by the first call
+    assert!(match &processing_results[3] {
+        AppchainMessageProcessingResult::Error { error, .. } => match error {
+            AppchainMessageError::ContractCallGasBudgetExhausted { .. } => true,
+            _ => false,
+        },
+        _ => false,
+    });
+    assert_eq!(
+        anchor_viewer::get_contract_call_settings(&worker, &anchor)
+            .await?
+            .remaining_gas_budget
+            .0,
+        0
+    );
+    Ok(())
+}
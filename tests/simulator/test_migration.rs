@@ -86,6 +86,16 @@ async fn test_migration() -> anyhow::Result<()> {
         .expect("Failed to call 'migrate_staking_histories'");
     common::complex_viewer::print_staking_histories(&worker, &anchor).await?;
     //
+    anchor
+        .call(&worker, "migrate_validator_set_histories")
+        .args_json(json!({
+            "start_index": "0"
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .expect("Failed to call 'migrate_validator_set_histories'");
+    //
     anchor
         .call(&worker, "migrate_appchain_notification_histories")
         .args_json(json!({
@@ -106,6 +116,13 @@ async fn test_migration() -> anyhow::Result<()> {
         .transact()
         .await
         .expect("Failed to call 'migrate_appchain_messages'");
+    //
+    anchor
+        .call(&worker, "migrate_appchain_message_processing_results")
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .expect("Failed to call 'migrate_appchain_message_processing_results'");
     common::complex_viewer::print_appchain_messages(&worker, &anchor).await?;
     //
     //
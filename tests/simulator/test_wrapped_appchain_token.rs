@@ -1,11 +1,19 @@
 use crate::{
     common,
-    contract_interfaces::{permissionless_actions, wrapped_appchain_token_manager},
+    contract_interfaces::{
+        anchor_viewer, owner_actions, permissionless_actions, settings_manager, sudo_actions,
+        wrapped_appchain_token_manager,
+    },
 };
 use appchain_anchor::appchain_messages::{
-    EraPayoutPayload, LockPayload, PayloadType, PlanNewEraPayload, RawMessage,
+    EraPayoutPayload, LockPayload, PayloadType, PlanNewEraPayload, RawMessage, ScaleLockPayload,
+};
+use appchain_anchor::types::{
+    AppchainMessageError, AppchainMessageProcessingResult, MultiTxsOperationProcessingResult,
+    PayloadCodec,
 };
 use near_sdk::{borsh::BorshSerialize, AccountId};
+use near_units::parse_near;
 use parity_scale_codec::Encode;
 use std::str::FromStr;
 
@@ -15,7 +23,7 @@ const TOTAL_SUPPLY: u128 = 100_000_000;
 async fn test_wrapped_appchain_token_bridging() -> anyhow::Result<()> {
     let worker = workspaces::sandbox().await?;
     let (
-        _root,
+        root,
         _oct_token,
         wrapped_appchain_token,
         _registry,
@@ -69,6 +77,20 @@ async fn test_wrapped_appchain_token_bridging() -> anyhow::Result<()> {
             .0,
         user1_wat_balance.0
     );
+    assert!(
+        match anchor_viewer::get_appchain_message_processing_results(
+            &worker,
+            &anchor,
+            appchain_message_nonce,
+            Some(1)
+        )
+        .await?[0]
+        {
+            AppchainMessageProcessingResult::Error { ref error, .. } =>
+                error.eq(&AppchainMessageError::WrappedAppchainTokenMintFailed),
+            _ => false,
+        }
+    );
     //
     // Burn wrapped appchain token from user0
     //
@@ -278,6 +300,248 @@ async fn test_wrapped_appchain_token_bridging() -> anyhow::Result<()> {
         user1_wat_balance.0 + common::to_actual_amount(515, 18)
     );
     //
+    // Mint wrapped appchain token for an unregistered account, without using storage fund
+    //
+    assert!(!owner_actions::deposit_to_storage_fund(
+        &worker,
+        &users[0],
+        &anchor,
+        parse_near!("1 N")
+    )
+    .await?
+    .is_success());
+    assert!(
+        owner_actions::deposit_to_storage_fund(&worker, &root, &anchor, parse_near!("1 N"))
+            .await?
+            .is_success()
+    );
+    assert_eq!(
+        anchor_viewer::get_storage_fund_balance(&worker, &anchor)
+            .await?
+            .0,
+        parse_near!("1 N")
+    );
+    let unregistered_user = root
+        .create_subaccount(&worker, "unregistered_user")
+        .initial_balance(parse_near!("1 N"))
+        .transact()
+        .await?
+        .unwrap();
+    appchain_message_nonce += 1;
+    let payload = LockPayload {
+        sender: user4_id_in_appchain.clone(),
+        receiver_id: unregistered_user.id().to_string().parse().unwrap(),
+        amount: common::to_actual_amount(50, 18),
+    };
+    let raw_message = RawMessage {
+        nonce: appchain_message_nonce as u64,
+        payload_type: PayloadType::Lock,
+        payload: payload.try_to_vec().unwrap(),
+    };
+    let mut raw_messages = Vec::new();
+    raw_messages.push(raw_message);
+    permissionless_actions::verify_and_stage_appchain_messages(
+        &worker,
+        &users[5],
+        &anchor,
+        raw_messages.encode(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    )
+    .await
+    .expect("Failed to call 'verify_and_stage_appchain_messages'");
+    common::complex_actions::process_appchain_messages(&worker, &users[3], &anchor).await?;
+    common::complex_viewer::print_appchain_messages_processing_results(&worker, &anchor).await?;
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &unregistered_user, &wrapped_appchain_token)
+            .await?
+            .0,
+        common::to_actual_amount(50, 18)
+    );
+    assert_eq!(
+        anchor_viewer::get_storage_fund_balance(&worker, &anchor)
+            .await?
+            .0,
+        parse_near!("1 N")
+    );
+    //
+    // Skip a missing appchain message
+    //
+    let user1_wat_balance =
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token).await?;
+    appchain_message_nonce += 1;
+    let missing_nonce = appchain_message_nonce;
+    appchain_message_nonce += 1;
+    let payload = LockPayload {
+        sender: user4_id_in_appchain.clone(),
+        receiver_id: users[1].id().to_string().parse().unwrap(),
+        amount: common::to_actual_amount(10, 18),
+    };
+    let raw_message = RawMessage {
+        nonce: appchain_message_nonce as u64,
+        payload_type: PayloadType::Lock,
+        payload: payload.try_to_vec().unwrap(),
+    };
+    let mut raw_messages = Vec::new();
+    raw_messages.push(raw_message);
+    permissionless_actions::verify_and_stage_appchain_messages(
+        &worker,
+        &users[5],
+        &anchor,
+        raw_messages.encode(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    )
+    .await
+    .expect("Failed to call 'verify_and_stage_appchain_messages'");
+    assert!(
+        match permissionless_actions::process_appchain_messages(&worker, &users[3], &anchor).await?
+        {
+            MultiTxsOperationProcessingResult::Error(..) => true,
+            _ => false,
+        }
+    );
+    assert_eq!(
+        anchor_viewer::get_missing_appchain_message_nonces(&worker, &anchor, missing_nonce, None)
+            .await?,
+        vec![missing_nonce]
+    );
+    assert!(!sudo_actions::skip_appchain_messages(
+        &worker,
+        &users[0],
+        &anchor,
+        vec![missing_nonce],
+        "Never relayed.".to_string(),
+    )
+    .await?
+    .is_success());
+    assert!(!sudo_actions::skip_appchain_messages(
+        &worker,
+        &root,
+        &anchor,
+        vec![appchain_message_nonce],
+        "Never relayed.".to_string(),
+    )
+    .await?
+    .is_success());
+    assert!(sudo_actions::skip_appchain_messages(
+        &worker,
+        &root,
+        &anchor,
+        vec![missing_nonce],
+        "Never relayed.".to_string(),
+    )
+    .await?
+    .is_success());
+    assert!(anchor_viewer::get_missing_appchain_message_nonces(
+        &worker,
+        &anchor,
+        missing_nonce,
+        None
+    )
+    .await?
+    .is_empty());
+    common::complex_actions::process_appchain_messages(&worker, &users[3], &anchor).await?;
+    assert!(
+        match anchor_viewer::get_appchain_message_processing_results(
+            &worker,
+            &anchor,
+            missing_nonce,
+            Some(1)
+        )
+        .await?[0]
+        {
+            AppchainMessageProcessingResult::Skipped { .. } => true,
+            _ => false,
+        }
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token)
+            .await?
+            .0,
+        user1_wat_balance.0 + common::to_actual_amount(10, 18)
+    );
+    //
+    // Mint wrapped appchain token for user1 by Borsh encoded payload before the cut-over nonce
+    // and SCALE encoded payload after it
+    //
+    assert!(!settings_manager::set_payload_codec(
+        &worker,
+        &users[0],
+        &anchor,
+        PayloadCodec::ScaleFromNonce {
+            nonce: appchain_message_nonce + 2
+        }
+    )
+    .await?
+    .is_success());
+    assert!(settings_manager::set_payload_codec(
+        &worker,
+        &root,
+        &anchor,
+        PayloadCodec::ScaleFromNonce {
+            nonce: appchain_message_nonce + 2
+        }
+    )
+    .await?
+    .is_success());
+    assert_eq!(
+        anchor_viewer::get_payload_codec(&worker, &anchor).await?,
+        PayloadCodec::ScaleFromNonce {
+            nonce: appchain_message_nonce + 2
+        }
+    );
+    let user1_wat_balance =
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token).await?;
+    let mut raw_messages = Vec::new();
+    appchain_message_nonce += 1;
+    let payload = LockPayload {
+        sender: user4_id_in_appchain.clone(),
+        receiver_id: users[1].id().to_string().parse().unwrap(),
+        amount: common::to_actual_amount(10, 18),
+    };
+    raw_messages.push(RawMessage {
+        nonce: appchain_message_nonce as u64,
+        payload_type: PayloadType::Lock,
+        payload: payload.try_to_vec().unwrap(),
+    });
+    appchain_message_nonce += 1;
+    let payload = ScaleLockPayload {
+        sender: user4_id_in_appchain.clone(),
+        receiver_id: users[1].id().to_string(),
+        amount: common::to_actual_amount(10, 18),
+    };
+    raw_messages.push(RawMessage {
+        nonce: appchain_message_nonce as u64,
+        payload_type: PayloadType::Lock,
+        payload: payload.encode(),
+    });
+    permissionless_actions::verify_and_stage_appchain_messages(
+        &worker,
+        &users[5],
+        &anchor,
+        raw_messages.encode(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    )
+    .await
+    .expect("Failed to call 'verify_and_stage_appchain_messages'");
+    common::complex_actions::process_appchain_messages(&worker, &users[3], &anchor).await?;
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[1], &wrapped_appchain_token)
+            .await?
+            .0,
+        user1_wat_balance.0 + common::to_actual_amount(20, 18)
+    );
+    assert!(
+        settings_manager::set_payload_codec(&worker, &root, &anchor, PayloadCodec::Borsh)
+            .await?
+            .is_success()
+    );
+    //
     //
     //
     let mut raw_messages = Vec::new();